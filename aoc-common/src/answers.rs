@@ -0,0 +1,270 @@
+use crate::{write_string, Answer, Error};
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+/// The known answers for one puzzle input, read from a small TOML file next to the input:
+///
+/// ```toml
+/// part1 = 24000
+/// part2 = "45000"
+/// ```
+///
+/// Only integers and strings (basic, literal and their multi-line variants) are supported, which
+/// is all we need for the answers. A part without answer is simply left out.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct ExpectedAnswers {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl ExpectedAnswers {
+    /// Where the answers for `input` are stored: `day-07.txt` → `day-07.answers.toml`.
+    pub fn path_for(input: &Path) -> PathBuf {
+        input.with_extension("answers.toml")
+    }
+
+    pub fn parse(input: &str) -> Result<ExpectedAnswers, Error> {
+        let mut answers = ExpectedAnswers::default();
+        let mut lines = input.lines().enumerate();
+        while let Some((i, line)) = lines.next() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let (key, value) = trimmed.split_once('=').ok_or_else(|| {
+                Error::syntax(line, line, "expected 'key = value'").at_line(i + 1)
+            })?;
+            let (key, value) = (key.trim(), value.trim());
+            let answer = parse_value(line, value, &mut lines).map_err(|e| e.at_line(i + 1))?;
+            match key {
+                "part1" => answers.part1 = Some(answer),
+                "part2" => answers.part2 = Some(answer),
+                _ => {
+                    return Err(Error::syntax(
+                        line,
+                        key,
+                        format!("unknown key '{key}', expected 'part1' or 'part2'"),
+                    )
+                    .at_line(i + 1))
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Load the answers for the input file `input`. A missing file means there are no answers.
+    pub fn load_for(input: &Path) -> Result<ExpectedAnswers, Error> {
+        match read_to_string(ExpectedAnswers::path_for(input)) {
+            Ok(content) => ExpectedAnswers::parse(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ExpectedAnswers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The answer for part 1 or 2, `None` for any other part.
+    pub fn get(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+// multi-line strings continue on the following lines, hence the iterator
+fn parse_value<'a, I>(line: &str, value: &str, lines: &mut I) -> Result<Answer, Error>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    for quotes in ["\"\"\"", "'''"] {
+        if let Some(first) = value.strip_prefix(quotes) {
+            // a newline right after the opening quotes is not part of the string
+            let mut raw = String::from(first);
+            if !first.is_empty() {
+                raw.push('\n');
+            }
+            while !raw.trim_end().ends_with(quotes) {
+                let (_, next) = lines.next().ok_or_else(|| {
+                    Error::syntax(line, value, "multi-line string is never closed")
+                })?;
+                raw.push_str(next);
+                raw.push('\n');
+            }
+            let raw = raw.trim_end().strip_suffix(quotes).unwrap();
+            return if quotes == "'''" {
+                Ok(Answer::Text(raw.to_owned()))
+            } else {
+                unescape(line, value, raw).map(Answer::Text)
+            };
+        }
+    }
+    if let Some(inner) = value.strip_prefix('"') {
+        let inner = inner
+            .strip_suffix('"')
+            .ok_or_else(|| Error::syntax(line, value, "string is not closed"))?;
+        return unescape(line, value, inner).map(Answer::Text);
+    }
+    if let Some(inner) = value.strip_prefix('\'') {
+        let inner = inner
+            .strip_suffix('\'')
+            .ok_or_else(|| Error::syntax(line, value, "string is not closed"))?;
+        return Ok(Answer::Text(inner.to_owned()));
+    }
+    // TOML allows underscores as separators in integers
+    value
+        .replace('_', "")
+        .parse::<i128>()
+        .map(Answer::Int)
+        .map_err(|_| Error::syntax(line, value, "expected an integer or a string"))
+}
+
+fn unescape(line: &str, value: &str, s: &str) -> Result<String, Error> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                let c = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| {
+                        Error::syntax(line, value, format!("invalid unicode escape '\\u{hex}'"))
+                    })?;
+                result.push(c);
+            }
+            _ => return Err(Error::syntax(line, value, "invalid escape sequence")),
+        }
+    }
+    Ok(result)
+}
+
+impl fmt::Display for ExpectedAnswers {
+    /// Writes the answers in the format [`ExpectedAnswers::parse`] reads.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (key, answer) in [("part1", &self.part1), ("part2", &self.part2)] {
+            match answer {
+                Some(Answer::Int(v)) => writeln!(f, "{key} = {v}")?,
+                Some(Answer::Text(s)) if s.contains('\n') => {
+                    writeln!(f, "{key} = '''\n{s}'''")?;
+                }
+                Some(Answer::Text(s)) => {
+                    let mut quoted = String::with_capacity(s.len() + 2);
+                    write_string(&mut quoted, s);
+                    writeln!(f, "{key} = {quoted}")?;
+                }
+                Some(Answer::Empty) | None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of checking one part against its known answer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// There is no known answer to compare with.
+    Missing,
+    /// The solver did not even come up with an answer.
+    Error(Error),
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&Answer>, actual: &Result<Answer, Error>) -> Verdict {
+        match (expected, actual) {
+            (_, Err(e)) => Verdict::Error(e.clone()),
+            // nothing to verify, e.g. the second part of day 25
+            (None, Ok(Answer::Empty)) => Verdict::Pass,
+            (None, Ok(_)) => Verdict::Missing,
+            (Some(expected), Ok(actual)) => {
+                // text answers like the CRT screen may or may not end with a newline
+                if expected.to_string().trim_end() == actual.to_string().trim_end() {
+                    Verdict::Pass
+                } else {
+                    Verdict::Fail {
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_works_for_all_supported_values() {
+        // given
+        let input = r#"# day 10
+part1 = 13_140
+part2 = """
+██░░
+░░██
+"""
+"#;
+
+        // when
+        let answers = ExpectedAnswers::parse(input);
+
+        // then
+        assert_eq!(
+            answers,
+            Ok(ExpectedAnswers {
+                part1: Some(Answer::Int(13140)),
+                part2: Some(Answer::Text("██░░\n░░██\n".to_owned())),
+            })
+        );
+        let answers = answers.expect("expected successful parsing");
+        assert_eq!(answers.get(2), answers.part2.as_ref());
+        assert_eq!(answers.get(3), None);
+    }
+
+    #[test]
+    fn written_answers_can_be_parsed_again() {
+        // given
+        let answers = ExpectedAnswers {
+            part1: Some(Answer::Text("2=-1=\"0".to_owned())),
+            part2: Some(Answer::Text("#.\n.#\n".to_owned())),
+        };
+
+        // when
+        let parsed = ExpectedAnswers::parse(&answers.to_string());
+
+        // then
+        assert_eq!(parsed, Ok(answers));
+    }
+
+    #[test]
+    fn check_compares_answers_by_their_text() {
+        assert_eq!(
+            Verdict::check(Some(&Answer::Text("42".to_owned())), &Ok(Answer::Int(42))),
+            Verdict::Pass
+        );
+        assert_eq!(
+            Verdict::check(Some(&Answer::Int(41)), &Ok(Answer::Int(42))),
+            Verdict::Fail {
+                expected: Answer::Int(41),
+                actual: Answer::Int(42)
+            }
+        );
+        assert_eq!(Verdict::check(None, &Ok(Answer::Int(42))), Verdict::Missing);
+    }
+}
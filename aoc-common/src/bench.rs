@@ -0,0 +1,247 @@
+use crate::{parse_number, Error, Solution};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The steps of solving a puzzle that are measured separately.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        })
+    }
+}
+
+impl FromStr for Phase {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part1),
+            "part2" => Ok(Phase::Part2),
+            _ => Err(Error::syntax(s, s, format!("unknown phase '{s}'"))),
+        }
+    }
+}
+
+/// Summary of repeated measurements of the same thing.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Will panic if there are no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort_unstable();
+        // nearest rank method, so p95 of less than 20 samples is just the maximum
+        let p95_rank = (samples.len() * 95).div_ceil(100);
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            p95: samples[p95_rank - 1],
+        }
+    }
+
+    /// Relative change of the median compared to `baseline`, e.g. 0.1 for 10% slower.
+    pub fn change_from(&self, baseline: &Stats) -> f64 {
+        self.median.as_secs_f64() / baseline.median.as_secs_f64().max(1e-9) - 1.0
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BenchResult {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl BenchResult {
+    pub fn stats(&self, phase: Phase) -> &Stats {
+        match phase {
+            Phase::Parse => &self.parse,
+            Phase::Part1 => &self.part1,
+            Phase::Part2 => &self.part2,
+        }
+    }
+}
+
+/// Run each phase `iterations` times. Fails if any phase fails, there is no point in measuring
+/// how fast we get a wrong result.
+pub fn bench<S: Solution>(input: &str, iterations: usize) -> Result<BenchResult, Error> {
+    if iterations == 0 {
+        return Err(Error::semantic("need at least one iteration to benchmark"));
+    }
+    let parse = measure(iterations, || S::parse(input).map(drop))?;
+    let parsed = S::parse(input)?;
+    let part1 = measure(iterations, || S::part1(&parsed).map(drop))?;
+    let part2 = measure(iterations, || S::part2(&parsed).map(drop))?;
+
+    Ok(BenchResult {
+        day: S::DAY,
+        iterations,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+fn measure<F>(iterations: usize, mut f: F) -> Result<Stats, Error>
+where
+    F: FnMut() -> Result<(), Error>,
+{
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        f()?;
+        samples.push(start.elapsed());
+    }
+    Ok(Stats::from_samples(samples))
+}
+
+/// Benchmark results stored on disk, to compare later runs against.
+///
+/// The file has one line per day and phase: `<day> <phase> <min> <median> <p95>`, with the
+/// durations in nanoseconds. Empty lines and lines starting with `#` are ignored.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Baseline {
+    pub entries: BTreeMap<(u8, Phase), Stats>,
+}
+
+impl Baseline {
+    pub fn record(&mut self, result: &BenchResult) {
+        for phase in Phase::ALL {
+            self.entries
+                .insert((result.day, phase), *result.stats(phase));
+        }
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<&Stats> {
+        self.entries.get(&(day, phase))
+    }
+
+    pub fn parse(input: &str) -> Result<Baseline, Error> {
+        let mut baseline = Baseline::default();
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let ((day, phase), stats) = parse_entry(line).map_err(|e| e.at_line(i + 1))?;
+            baseline.entries.insert((day, phase), stats);
+        }
+        Ok(baseline)
+    }
+
+    /// Load the baseline from `path`. A missing file is an empty baseline.
+    pub fn load(path: &Path) -> Result<Baseline, Error> {
+        match read_to_string(path) {
+            Ok(content) => Baseline::parse(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        Ok(write(path, self.to_string())?)
+    }
+}
+
+fn parse_entry(line: &str) -> Result<((u8, Phase), Stats), Error> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [day, phase, min, median, p95] = fields[..] else {
+        return Err(Error::syntax(
+            line,
+            line,
+            "expected '<day> <phase> <min> <median> <p95>'",
+        ));
+    };
+    let phase: Phase = phase.parse().map_err(|e: Error| e.with_span(line, phase))?;
+    let nanos = |span: &str| parse_number(line, span).map(Duration::from_nanos);
+    Ok((
+        (parse_number(line, day)?, phase),
+        Stats {
+            min: nanos(min)?,
+            median: nanos(median)?,
+            p95: nanos(p95)?,
+        },
+    ))
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day phase min median p95 (nanoseconds)")?;
+        for ((day, phase), stats) in &self.entries {
+            writeln!(
+                f,
+                "{day} {phase} {} {} {}",
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.p95.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats_from_samples_works() {
+        // given
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+
+        // when
+        let stats = Stats::from_samples(samples);
+
+        // then
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(11));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn baseline_survives_round_trip() {
+        // given
+        let mut baseline = Baseline::default();
+        baseline.record(&BenchResult {
+            day: 16,
+            iterations: 3,
+            parse: Stats::default(),
+            part1: Stats {
+                min: Duration::from_micros(10),
+                median: Duration::from_micros(12),
+                p95: Duration::from_micros(20),
+            },
+            part2: Stats::default(),
+        });
+
+        // when
+        let parsed = Baseline::parse(&baseline.to_string());
+
+        // then
+        assert_eq!(parsed, Ok(baseline));
+    }
+}
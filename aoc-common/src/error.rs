@@ -0,0 +1,271 @@
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum ErrorKind {
+    /// The input could not be read at all.
+    Io,
+    /// The input does not have the expected format.
+    Syntax,
+    /// Something that should be a number isn't one (or is too large).
+    Number,
+    /// The input is well-formed, but does not make sense for the puzzle.
+    Semantic,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ErrorKind::Io => "I/O error",
+            ErrorKind::Syntax => "syntax error",
+            ErrorKind::Number => "invalid number",
+            ErrorKind::Semantic => "semantic error",
+        })
+    }
+}
+
+/// Where in the input an error occurred.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Location {
+    /// 1-based line number, 0 if the line is not known (yet)
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// the whole line the error occurred in
+    pub text: String,
+    /// length of the offending part of the line, in characters
+    pub len: usize,
+}
+
+/// Error type for everything that can go wrong while parsing and solving a puzzle.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Error {
+            kind,
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// A syntax error in `line`, caused by `span`. `span` should be a slice of `line`, otherwise
+    /// we have to search for it (and if we don't find it, the whole line is marked).
+    pub fn syntax(line: &str, span: &str, message: impl Into<String>) -> Self {
+        Error::new(ErrorKind::Syntax, message).with_span(line, span)
+    }
+
+    /// `span` in `line` is not a valid number.
+    pub fn number(line: &str, span: &str, err: impl fmt::Display) -> Self {
+        Error::new(
+            ErrorKind::Number,
+            format!("unable to parse '{span}' as number: {err}"),
+        )
+        .with_span(line, span)
+    }
+
+    pub fn semantic(message: impl Into<String>) -> Self {
+        Error::new(ErrorKind::Semantic, message)
+    }
+
+    /// Mark `span` in `line` as the location of this error.
+    pub fn with_span(mut self, line: &str, span: &str) -> Self {
+        let byte_offset =
+            subslice_offset(line, span).or_else(|| line.find(span).filter(|_| !span.is_empty()));
+        let (column, len) = match byte_offset {
+            Some(offset) => (
+                line[..offset].chars().count() + 1,
+                span.chars().count().max(1),
+            ),
+            None => (1, line.chars().count().max(1)),
+        };
+        self.location = Some(Location {
+            line: 0,
+            column,
+            text: line.to_owned(),
+            len,
+        });
+        self
+    }
+
+    /// Set the line number, if it is not known yet. Parsers for single lines don't know where
+    /// in the input they are, so the line number is filled in by the caller.
+    pub fn at_line(mut self, line: usize) -> Self {
+        if let Some(location) = &mut self.location {
+            if location.line == 0 {
+                location.line = line;
+            }
+        }
+        self
+    }
+
+    /// Shift the line number by `offset`, for parsers that only see a part of the input.
+    pub fn offset_lines(mut self, offset: usize) -> Self {
+        if let Some(location) = &mut self.location {
+            if location.line != 0 {
+                location.line += offset;
+            }
+        }
+        self
+    }
+}
+
+// byte offset of `part` in `whole`, if `part` is actually a slice of `whole`
+fn subslice_offset(whole: &str, part: &str) -> Option<usize> {
+    let start = whole.as_ptr() as usize;
+    let part_start = part.as_ptr() as usize;
+    if part_start >= start && part_start + part.len() <= start + whole.len() {
+        Some(part_start - start)
+    } else {
+        None
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = match &self.location {
+            Some(location) => location,
+            None => return write!(f, "{}: {}", self.kind, self.message),
+        };
+        if location.line != 0 {
+            writeln!(
+                f,
+                "{} in line {}, column {}: {}",
+                self.kind, location.line, location.column, self.message
+            )?;
+        } else {
+            writeln!(
+                f,
+                "{} in column {}: {}",
+                self.kind, location.column, self.message
+            )?;
+        }
+        let line_no = if location.line != 0 {
+            location.line.to_string()
+        } else {
+            String::new()
+        };
+        let gutter = " ".repeat(line_no.len());
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line_no} | {}", location.text)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(location.column - 1),
+            "^".repeat(location.len)
+        )
+    }
+}
+
+// Debug is what you see when main returns an error, so make it readable
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::new(ErrorKind::Io, e.to_string())
+    }
+}
+
+/// Parse `span` (a part of `line`) as a number.
+pub fn parse_number<T>(line: &str, span: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    span.parse::<T>().map_err(|e| Error::number(line, span, e))
+}
+
+/// Parse each line of the input, adding line numbers to the errors.
+pub fn parse_lines<'a, T, C, F>(input: &'a str, mut parse_line: F) -> Result<C, Error>
+where
+    F: FnMut(&'a str) -> Result<T, Error>,
+    C: FromIterator<T>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_points_at_offending_text() {
+        // given
+        let line = "move 1 frm 2 to 1";
+        let error = Error::syntax(line, &line[7..10], "expected 'from'").at_line(12);
+
+        // when
+        let displayed = error.to_string();
+
+        // then
+        assert_eq!(
+            displayed,
+            r#"syntax error in line 12, column 8: expected 'from'
+   |
+12 | move 1 frm 2 to 1
+   |        ^^^"#
+        );
+    }
+
+    #[test]
+    fn display_works_without_location() {
+        // given
+        let error = Error::semantic("it appears we have lost an elf somewhere in the jungle");
+
+        // when
+        let displayed = error.to_string();
+
+        // then
+        assert_eq!(
+            displayed,
+            "semantic error: it appears we have lost an elf somewhere in the jungle"
+        );
+    }
+
+    #[test]
+    fn parse_lines_adds_line_numbers() {
+        // when
+        let result: Result<Vec<u32>, Error> =
+            parse_lines("1\n2\nthree\n4", |line| parse_number(line, line));
+
+        // then
+        let error = result.expect_err("expected parsing to fail");
+        assert_eq!(error.kind, ErrorKind::Number);
+        assert_eq!(
+            error.location,
+            Some(Location {
+                line: 3,
+                column: 1,
+                text: "three".to_owned(),
+                len: 5
+            })
+        );
+    }
+
+    #[test]
+    fn with_span_finds_span_that_is_not_a_subslice() {
+        // when
+        let error = Error::syntax("a -> b", &String::from("->"), "arrows are not allowed");
+
+        // then
+        let location = error.location.expect("expected location");
+        assert_eq!(location.column, 3);
+        assert_eq!(location.len, 2);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::Rng;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+// characters puzzle inputs are made of, plus some that never appear in them
+const ALPHABET: &[char] = &[
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '-', '+', '=', ' ', '\n', '\n', '\r', '\t',
+    ',', ':', ';', '.', '[', ']', '(', ')', '#', '<', '>', '^', 'v', '/', '*', 'a', 'b', 'x', 'y',
+    'z', 'A', 'B', 'S', 'E', 'L', 'R', 'X', 'é', '→', '\0',
+];
+
+// fragments that break parsers in more interesting ways than single characters
+const TOKENS: &[&str] = &[
+    "\n\n",
+    "99999999999999999999",
+    "-1",
+    "0",
+    "[[]]",
+    "    ",
+    "move 1 from 1 to 2",
+    "$ cd ..",
+    "x=-3, y=4",
+    "old * old",
+    "Valve AA",
+    "->",
+];
+
+/// The input that made a parser panic, shrunk as far as possible, and the panic message.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Panic {
+    pub input: String,
+    pub message: String,
+}
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "panicked on input {:?}: {}", self.input, self.message)
+    }
+}
+
+/// Feed `parse` with `runs` random inputs and mutations of the inputs in `corpus`. A parser is
+/// expected to return an error for bad input, so any panic is reported as failure.
+pub fn fuzz(seed: u64, runs: usize, corpus: &[&str], parse: impl Fn(&str)) -> Result<(), Panic> {
+    let mut rng = Rng::new(seed);
+    for _ in 0..runs {
+        let input = if corpus.is_empty() || rng.chance(0.2) {
+            arbitrary_input(&mut rng, 64)
+        } else {
+            let mut input = rng.choose(corpus).to_string();
+            for _ in 0..=rng.below(4) {
+                input = mutate(&mut rng, &input);
+            }
+            input
+        };
+        if let Err(message) = catch_panic(&parse, &input) {
+            return Err(shrink(&parse, input, message));
+        }
+    }
+    Ok(())
+}
+
+/// Up to `max_len` random characters, mostly those that appear in puzzle inputs.
+pub fn arbitrary_input(rng: &mut Rng, max_len: usize) -> String {
+    let len = rng.below(max_len + 1);
+    if rng.chance(0.1) {
+        // raw bytes, as they would come from a broken file
+        let bytes: Vec<u8> = (0..len).map(|_| rng.below(256) as u8).collect();
+        return String::from_utf8_lossy(&bytes).into_owned();
+    }
+    (0..len).map(|_| *rng.choose(ALPHABET)).collect()
+}
+
+/// A random small change of `input`: characters removed, inserted, duplicated or replaced.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    if chars.is_empty() {
+        return rng.choose(TOKENS).to_string();
+    }
+    let start = rng.below(chars.len());
+    let end = (start + 1 + rng.below(8)).min(chars.len());
+    match rng.below(6) {
+        0 => {
+            chars.drain(start..end);
+        }
+        1 => chars.insert(start, *rng.choose(ALPHABET)),
+        2 => {
+            let token: Vec<char> = rng.choose(TOKENS).chars().collect();
+            chars.splice(start..start, token);
+        }
+        3 => {
+            let copy: Vec<char> = chars[start..end].to_vec();
+            chars.splice(start..start, copy);
+        }
+        4 => chars[start] = *rng.choose(ALPHABET),
+        _ => chars.truncate(start),
+    }
+    chars.into_iter().collect()
+}
+
+fn catch_panic(parse: &impl Fn(&str), input: &str) -> Result<(), String> {
+    panic::catch_unwind(AssertUnwindSafe(|| parse(input))).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned())
+    })
+}
+
+// remove ever smaller chunks of the input as long as the parser still panics
+fn shrink(parse: &impl Fn(&str), input: String, message: String) -> Panic {
+    // the default hook would print every panic while shrinking
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut smallest: Vec<char> = input.chars().collect();
+    let mut message = message;
+    // start over as long as something could be removed, a smaller input may allow more of it
+    let mut shrunk = true;
+    while shrunk {
+        shrunk = false;
+        let mut chunk = smallest.len() / 2;
+        while chunk > 0 {
+            let mut start = 0;
+            while start + chunk <= smallest.len() {
+                let mut candidate = smallest.clone();
+                candidate.drain(start..start + chunk);
+                let candidate: String = candidate.into_iter().collect();
+                match catch_panic(parse, &candidate) {
+                    Err(m) => {
+                        smallest = candidate.chars().collect();
+                        message = m;
+                        shrunk = true;
+                    }
+                    Ok(()) => start += chunk,
+                }
+            }
+            chunk /= 2;
+        }
+    }
+    panic::set_hook(hook);
+    Panic {
+        input: smallest.into_iter().collect(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fuzz_finds_and_shrinks_panics() {
+        // given
+        let careless = |input: &str| {
+            // panics for a line without comma
+            for line in input.lines() {
+                let (_, _) = line.split_once(',').unwrap();
+            }
+        };
+        let careful = |input: &str| {
+            let _: Vec<_> = input.lines().map(|line| line.split_once(',')).collect();
+        };
+
+        // when
+        let careless_result = fuzz(1, 1000, &["1,2\n3,4\n"], careless);
+        let careful_result = fuzz(1, 1000, &["1,2\n3,4\n"], careful);
+
+        // then
+        let panic = careless_result.expect_err("expected a panic");
+        assert_eq!(panic.input.lines().count(), 1);
+        assert!(!panic.input.contains(','));
+        assert_eq!(careful_result, Ok(()));
+    }
+}
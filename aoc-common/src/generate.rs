@@ -0,0 +1,129 @@
+use crate::Error;
+use std::str::FromStr;
+
+/// How hard a generated puzzle should be. What that means is up to each day, e.g. more valves
+/// with a flow rate on day 16 or a more even flow of numbers on day 20.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl FromStr for Difficulty {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(Error::semantic(format!(
+                "unknown difficulty '{s}', expected 'easy', 'normal' or 'hard'"
+            ))),
+        }
+    }
+}
+
+/// The knobs for generating a puzzle input.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct GenerateOptions {
+    /// The size of the input, in whatever unit fits the day best (lines, width of a grid, number
+    /// of valves, …). Without size, each day uses the size of a real puzzle input.
+    pub size: Option<usize>,
+    pub seed: u64,
+    pub difficulty: Difficulty,
+}
+
+impl GenerateOptions {
+    pub fn size_or(&self, default: usize) -> usize {
+        self.size.unwrap_or(default)
+    }
+
+    /// A random number generator seeded with the seed of these options.
+    pub fn rng(&self) -> Rng {
+        Rng::new(self.seed)
+    }
+
+    /// Pick the value for the difficulty of these options.
+    pub fn pick<T>(&self, easy: T, normal: T, hard: T) -> T {
+        match self.difficulty {
+            Difficulty::Easy => easy,
+            Difficulty::Normal => normal,
+            Difficulty::Hard => hard,
+        }
+    }
+}
+
+/// A small pseudo random number generator (SplitMix64). Good enough for puzzle inputs, and the
+/// same seed always gives the same input.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` to `n - 1`. Panics if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "can't pick a number below 0");
+        // multiply and keep the high bits, which is fair enough without a division
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+
+    /// A number from `low` to `high`, both included. Panics if `low > high`.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range {low}..={high}");
+        let span = high.abs_diff(low) as u128 + 1;
+        low.wrapping_add(((u128::from(self.next_u64()) * span) >> 64) as i64)
+    }
+
+    /// `true` with the given probability.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// A random element of `items`. Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rng_stays_in_range_and_is_reproducible() {
+        // given
+        let mut rng = Rng::new(42);
+        let mut other = Rng::new(42);
+
+        // when
+        let numbers: Vec<i64> = (0..1000).map(|_| rng.between(-3, 3)).collect();
+        let other_numbers: Vec<i64> = (0..1000).map(|_| other.between(-3, 3)).collect();
+
+        // then
+        assert_eq!(numbers, other_numbers);
+        assert!(numbers.iter().all(|n| (-3..=3).contains(n)));
+        assert!(numbers.contains(&-3) && numbers.contains(&3));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Error, ErrorKind};
+use std::fmt;
+use std::fs::{read_to_string, File};
+use std::io::{stdin, BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+/// Where a puzzle input comes from.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    /// `-` means stdin, like for most command line tools.
+    pub fn from_arg(arg: &str) -> Input {
+        if arg == "-" {
+            Input::Stdin
+        } else {
+            Input::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> Result<String, Error> {
+        match self {
+            Input::Stdin => {
+                let mut content = String::new();
+                stdin().read_to_string(&mut content)?;
+                Ok(content)
+            }
+            Input::File(path) => read_to_string(path).map_err(|e| {
+                Error::new(
+                    ErrorKind::Io,
+                    format!("Unable to read '{}': {e}", path.display()),
+                )
+            }),
+        }
+    }
+
+    /// Open the input to read it bit by bit, for inputs too large to keep in memory.
+    pub fn open(&self) -> Result<Box<dyn BufRead>, Error> {
+        match self {
+            Input::Stdin => Ok(Box::new(stdin().lock())),
+            Input::File(path) => File::open(path)
+                .map(|f| Box::new(BufReader::new(f)) as _)
+                .map_err(|e| {
+                    Error::new(
+                        ErrorKind::Io,
+                        format!("Unable to read '{}': {e}", path.display()),
+                    )
+                }),
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Stdin => f.write_str("<stdin>"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The inputs named by the positional command line arguments. Without any, the input is read
+/// from stdin.
+pub fn inputs_from_args(positional: &[String]) -> Vec<Input> {
+    if positional.is_empty() {
+        vec![Input::Stdin]
+    } else {
+        positional.iter().map(|arg| Input::from_arg(arg)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inputs_from_args_defaults_to_stdin() {
+        // given
+        let args = ["day-01.txt", "-"].map(String::from);
+
+        // when
+        let inputs = inputs_from_args(&args);
+        let default = inputs_from_args(&[]);
+
+        // then
+        assert_eq!(
+            inputs,
+            [Input::File(PathBuf::from("day-01.txt")), Input::Stdin]
+        );
+        assert_eq!(default, [Input::Stdin]);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Answer, DayResult, Error, Timings};
+use std::fmt::Write;
+use std::time::Duration;
+
+impl DayResult {
+    /// The result as a single JSON object, e.g.
+    /// `{"day":7,"part1":1432936,"part2":272298,"timings":{"parse_us":95,…}}`.
+    /// Failed parts are reported as `{"error":"…"}` instead of the answer.
+    pub fn to_json(&self) -> String {
+        self.to_labelled_json(None)
+    }
+
+    /// Like [`DayResult::to_json`], with an additional `"input"` field naming the input, if given.
+    pub fn to_labelled_json(&self, input: Option<&str>) -> String {
+        let mut json = String::with_capacity(256);
+        write!(json, "{{\"day\":{},", self.day).unwrap();
+        write_input(&mut json, input);
+        json.push_str("\"part1\":");
+        write_part(&mut json, &self.part1);
+        json.push_str(",\"part2\":");
+        write_part(&mut json, &self.part2);
+        json.push_str(",\"timings\":");
+        write_timings(&mut json, &self.timings);
+        json.push('}');
+        json
+    }
+}
+
+/// A JSON object for a day whose input could not even be parsed.
+pub fn error_json(day: u8, input: Option<&str>, error: &Error) -> String {
+    let mut json = format!("{{\"day\":{day},");
+    write_input(&mut json, input);
+    json.push_str("\"error\":");
+    write_string(&mut json, &error.to_string());
+    json.push('}');
+    json
+}
+
+fn write_input(json: &mut String, input: Option<&str>) {
+    if let Some(input) = input {
+        json.push_str("\"input\":");
+        write_string(json, input);
+        json.push(',');
+    }
+}
+
+fn write_part(json: &mut String, part: &Result<Answer, Error>) {
+    match part {
+        Ok(Answer::Int(v)) => write!(json, "{v}").unwrap(),
+        Ok(Answer::Text(s)) => write_string(json, s),
+        Ok(Answer::Empty) => json.push_str("null"),
+        Err(e) => {
+            json.push_str("{\"error\":");
+            write_string(json, &e.to_string());
+            json.push('}');
+        }
+    }
+}
+
+fn write_timings(json: &mut String, timings: &Timings) {
+    let us = |d: Duration| d.as_micros();
+    write!(
+        json,
+        "{{\"parse_us\":{},\"part1_us\":{},\"part2_us\":{},\"total_us\":{}}}",
+        us(timings.parse),
+        us(timings.part1),
+        us(timings.part2),
+        us(timings.total())
+    )
+    .unwrap();
+}
+
+/// Append `s` as a quoted JSON string.
+pub fn write_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_json_works_for_all_kinds_of_answers() {
+        // given
+        let result = DayResult {
+            day: 10,
+            part1: Ok(Answer::Int(13140)),
+            part2: Ok(Answer::Text("█░\n░█\n".to_owned())),
+            timings: Timings {
+                parse: Duration::from_micros(3),
+                part1: Duration::from_micros(20),
+                part2: Duration::from_micros(100),
+            },
+        };
+
+        // when
+        let json = result.to_json();
+
+        // then
+        assert_eq!(
+            json,
+            r#"{"day":10,"part1":13140,"part2":"█░\n░█\n","timings":{"parse_us":3,"part1_us":20,"part2_us":100,"total_us":123}}"#
+        );
+    }
+
+    #[test]
+    fn to_json_reports_errors_and_missing_parts() {
+        // given
+        let result = DayResult {
+            day: 25,
+            part1: Err(Error::semantic("no \"fuel\" today")),
+            part2: Ok(Answer::Empty),
+            timings: Timings::default(),
+        };
+
+        // when
+        let json = result.to_json();
+
+        // then
+        assert_eq!(
+            json,
+            r#"{"day":25,"part1":{"error":"semantic error: no \"fuel\" today"},"part2":null,"timings":{"parse_us":0,"part1_us":0,"part2_us":0,"total_us":0}}"#
+        );
+    }
+}
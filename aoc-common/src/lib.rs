@@ -0,0 +1,383 @@
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+mod answers;
+mod bench;
+mod error;
+mod fuzz;
+mod generate;
+mod input;
+mod json;
+
+pub use answers::{ExpectedAnswers, Verdict};
+pub use bench::{bench, Baseline, BenchResult, Phase, Stats};
+pub use error::{parse_lines, parse_number, Error, ErrorKind, Location};
+pub use fuzz::{arbitrary_input, fuzz, mutate, Panic};
+pub use generate::{Difficulty, GenerateOptions, Rng};
+pub use input::{inputs_from_args, Input};
+pub use json::{error_json, write_string};
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    // for puzzles that don't have a second part (looking at you, day 25)
+    Empty,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{v}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Empty => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    Answer::Int(v as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u8, u32, u64, u128, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+/// Common interface for the solutions of each day, so they can be run from one place.
+pub trait Solution {
+    const DAY: u8;
+
+    /// The parsed puzzle input. Some days borrow from the raw input, hence the lifetime.
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error>;
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, Error>;
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, Error>;
+
+    /// A random puzzle input that [`Solution::parse`] accepts and that meets the assumptions the
+    /// puzzle makes without telling.
+    fn generate(options: &GenerateOptions) -> String;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DayResult {
+    pub day: u8,
+    pub part1: Result<Answer, Error>,
+    pub part2: Result<Answer, Error>,
+    pub timings: Timings,
+}
+
+/// How the answers are printed.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Format {
+    /// Prose for humans.
+    #[default]
+    Text,
+    /// One JSON object per day, for machines.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(Error::semantic(format!(
+                "unknown output format '{s}', expected 'text' or 'json'"
+            ))),
+        }
+    }
+}
+
+/// Split the command line arguments into positional arguments and the output format given with
+/// `--format <format>` or `--format=<format>`.
+pub fn split_args<I: IntoIterator<Item = String>>(args: I) -> Result<(Vec<String>, Format), Error> {
+    let mut positional = Vec::new();
+    let mut format = Format::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            let value = args
+                .next()
+                .ok_or_else(|| Error::semantic("--format needs a value"))?;
+            format = value.parse()?;
+        } else if let Some(value) = arg.strip_prefix("--format=") {
+            format = value.parse()?;
+        } else {
+            positional.push(arg);
+        }
+    }
+    Ok((positional, format))
+}
+
+/// Like [`split_args`], for the modes of a day that only print prose: asking for JSON there is an
+/// error instead of being ignored.
+pub fn split_text_args<I: IntoIterator<Item = String>>(args: I) -> Result<Vec<String>, Error> {
+    match split_args(args)? {
+        (positional, Format::Text) => Ok(positional),
+        (_, Format::Json) => Err(Error::semantic(
+            "only the answers can be printed as JSON, leave out --format json",
+        )),
+    }
+}
+
+/// Remove `<name> <value>` from the arguments and return the value, if `name` is among them.
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, Error> {
+    let Some(i) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if i + 1 >= args.len() {
+        return Err(Error::semantic(format!("{name} needs a value")));
+    }
+    let value = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
+
+/// The output format requested on the command line, see [`split_args`].
+pub fn format_from_args() -> Result<Format, Error> {
+    Ok(split_args(env::args().skip(1))?.1)
+}
+
+/// Read the puzzle input from the first input given on the command line, see
+/// [`inputs_from_args`].
+pub fn read_input_from_args() -> Result<String, Error> {
+    let (positional, _) = split_args(env::args().skip(1))?;
+    inputs_from_args(&positional)[0].read()
+}
+
+/// The `main` of every day: solve each input given on the command line (stdin if there is none)
+/// one after another, either printing the answers as prose with `print_answers`, or as JSON.
+/// With several inputs, the output for each is labelled with its file name and a failing input
+/// doesn't stop the others.
+pub fn run_main<S: Solution>(print_answers: fn(&str) -> Result<(), Error>) -> Result<(), Error> {
+    run_args::<S>(env::args().skip(1), print_answers)
+}
+
+/// Like [`run_main`], but with `--render` on the command line the prose is replaced by whatever
+/// `render` draws, e.g. the path that was found.
+pub fn run_main_with_render<S: Solution>(
+    print_answers: fn(&str) -> Result<(), Error>,
+    render: fn(&str) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let (rest, flags): (Vec<String>, Vec<String>) =
+        env::args().skip(1).partition(|arg| arg != "--render");
+    let print = if flags.is_empty() {
+        print_answers
+    } else {
+        render
+    };
+    run_args::<S>(rest, print)
+}
+
+fn run_args<S: Solution>(
+    args: impl IntoIterator<Item = String>,
+    print_answers: fn(&str) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let (positional, format) = split_args(args)?;
+    let inputs = inputs_from_args(&positional);
+    if let [input] = &inputs[..] {
+        let content = input.read()?;
+        return match format {
+            Format::Text => print_answers(&content),
+            Format::Json => print_json::<S>(&content, None),
+        };
+    }
+
+    let mut failed = 0;
+    for (i, input) in inputs.iter().enumerate() {
+        let label = input.to_string();
+        if format == Format::Text {
+            if i > 0 {
+                println!();
+            }
+            println!("==> {label} <==");
+        }
+        let result = match (input.read(), format) {
+            (Ok(content), Format::Text) => print_answers(&content),
+            (Ok(content), Format::Json) => print_json::<S>(&content, Some(&label)),
+            (Err(e), Format::Text) => Err(e),
+            (Err(e), Format::Json) => {
+                println!("{}", error_json(S::DAY, Some(&label), &e));
+                Err(e)
+            }
+        };
+        if let Err(e) = result {
+            eprintln!("{label}: {e}");
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        Err(Error::semantic(format!(
+            "{failed} of {} inputs failed",
+            inputs.len()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Solve the puzzle with `S` and print the result as JSON, labelled with the name of the input if
+/// given. If the input can't be parsed, the error is printed as JSON as well, and returned.
+pub fn print_json<S: Solution>(content: &str, input: Option<&str>) -> Result<(), Error> {
+    match solve::<S>(content) {
+        Ok(result) => {
+            println!("{}", result.to_labelled_json(input));
+            Ok(())
+        }
+        Err(e) => {
+            println!("{}", error_json(S::DAY, input, &e));
+            Err(e)
+        }
+    }
+}
+
+/// Parse the input and solve both parts, measuring the time each step takes.
+/// If the input can't be parsed, there is nothing to solve, so this is the only error returned
+/// directly. Errors in the parts are part of the result.
+pub fn solve<S: Solution>(input: &str) -> Result<DayResult, Error> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let part1 = S::part1(&parsed);
+    let part1_time = start.elapsed();
+
+    let start = Instant::now();
+    let part2 = S::part2(&parsed);
+    let part2_time = start.elapsed();
+
+    Ok(DayResult {
+        day: S::DAY,
+        part1,
+        part2,
+        timings: Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        const DAY: u8 = 42;
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+            if input.is_empty() {
+                Err(Error::semantic("empty input"))
+            } else {
+                Ok(input.lines().collect())
+            }
+        }
+        fn part1(input: &Self::Input<'_>) -> Result<Answer, Error> {
+            Ok(input.len().into())
+        }
+        fn part2(input: &Self::Input<'_>) -> Result<Answer, Error> {
+            input
+                .iter()
+                .max_by_key(|line| line.len())
+                .map(|line| Answer::from(*line))
+                .ok_or_else(|| Error::semantic("no lines"))
+        }
+        fn generate(options: &GenerateOptions) -> String {
+            "x\n".repeat(options.size_or(3))
+        }
+    }
+
+    #[test]
+    fn solve_runs_all_parts() {
+        // when
+        let result = solve::<Example>("a\nbcd\nef\n");
+
+        // then
+        let result = result.expect("expected successful run");
+        assert_eq!(result.day, 42);
+        assert_eq!(result.part1, Ok(Answer::Int(3)));
+        assert_eq!(result.part2, Ok(Answer::Text("bcd".to_owned())));
+    }
+
+    #[test]
+    fn solve_fails_for_unparseable_input() {
+        // when
+        let result = solve::<Example>("");
+
+        // then
+        assert_eq!(result, Err(Error::semantic("empty input")));
+    }
+
+    #[test]
+    fn split_args_extracts_format() {
+        // given
+        let args = ["input.txt", "--format", "json"].map(String::from);
+
+        // when
+        let result = split_args(args);
+
+        // then
+        assert_eq!(result, Ok((vec!["input.txt".to_owned()], Format::Json)));
+        assert!(split_args(["--format=yaml".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn take_option_removes_name_and_value() {
+        // given
+        let mut args = ["--top", "10", "input.txt", "--sep"]
+            .map(String::from)
+            .to_vec();
+
+        // when
+        let top = take_option(&mut args, "--top");
+        let missing = take_option(&mut args, "--comment");
+
+        // then
+        assert_eq!(top, Ok(Some("10".to_owned())));
+        assert_eq!(missing, Ok(None));
+        assert_eq!(args, ["input.txt", "--sep"]);
+        assert!(take_option(&mut args, "--sep").is_err());
+        assert!(split_text_args(["--format=json".to_owned()]).is_err());
+    }
+}
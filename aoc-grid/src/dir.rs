@@ -0,0 +1,128 @@
+use crate::Point;
+
+/// The four directions on a grid, in clockwise order.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// The step to take in this direction. Up is towards smaller `y`.
+    pub const fn offset(self) -> Point {
+        match self {
+            Dir4::Up => Point::new(0, -1),
+            Dir4::Right => Point::new(1, 0),
+            Dir4::Down => Point::new(0, 1),
+            Dir4::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Dir4 {
+        self.rot_clockwise(1)
+    }
+
+    pub fn turn_left(self) -> Dir4 {
+        self.rot_clockwise(3)
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        self.rot_clockwise(2)
+    }
+
+    /// Rotate by `r` times 90° clockwise.
+    pub fn rot_clockwise(self, r: u8) -> Dir4 {
+        Dir4::ALL[(self as usize + r as usize) % 4]
+    }
+
+    /// The direction drawn as an arrow: `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Dir4> {
+        match c {
+            '^' => Some(Dir4::Up),
+            '>' => Some(Dir4::Right),
+            'v' => Some(Dir4::Down),
+            '<' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+}
+
+/// The eight directions including diagonals, in clockwise order starting north (up).
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub const fn offset(self) -> Point {
+        match self {
+            Dir8::N => Point::new(0, -1),
+            Dir8::NE => Point::new(1, -1),
+            Dir8::E => Point::new(1, 0),
+            Dir8::SE => Point::new(1, 1),
+            Dir8::S => Point::new(0, 1),
+            Dir8::SW => Point::new(-1, 1),
+            Dir8::W => Point::new(-1, 0),
+            Dir8::NW => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::N,
+            Dir4::Right => Dir8::E,
+            Dir4::Down => Dir8::S,
+            Dir4::Left => Dir8::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turning_works_in_both_directions() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.offset(), -dir.reverse().offset());
+            assert_eq!(Dir4::from_arrow(dir.arrow()), Some(dir));
+        }
+        assert_eq!(Dir4::Left.turn_right(), Dir4::Up);
+        assert_eq!(Dir4::Up.rot_clockwise(7), Dir4::Left);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{Dir4, Dir8, Point};
+use aoc_common::Error;
+use std::ops::{Index, IndexMut};
+
+/// A dense, rectangular 2D grid, stored row by row. Positions outside of the grid are simply not
+/// there, so [`Grid::get`] and the neighbour iterators never have to worry about bounds.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid without any cells, usable in constants.
+    pub const fn empty() -> Grid<T> {
+        Grid {
+            cells: Vec::new(),
+            width: 0,
+        }
+    }
+
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+        }
+    }
+
+    /// Will panic if the number of cells is not a multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width) || width == 0 && cells.is_empty(),
+            "{} cells do not fit into rows of width {width}",
+            cells.len()
+        );
+        Grid { cells, width }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x as i64, y as i64)))
+            .map(&mut f)
+            .collect();
+        Grid { cells, width }
+    }
+
+    /// Parse text art, one character per cell. `cell` returns `None` for characters that are not
+    /// allowed. Empty lines at the end are ignored, all other lines must have the same length.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, Error> {
+        let mut lines: Vec<&str> = input.lines().collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let width = lines
+            .first()
+            .ok_or_else(|| Error::semantic("grid is empty"))?
+            .chars()
+            .count();
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(Error::syntax(
+                    line,
+                    line,
+                    format!("assumed row length {width}, but this row has a different length"),
+                )
+                .at_line(i + 1));
+            }
+            for (j, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    Error::syntax(
+                        line,
+                        &line[j..j + c.len_utf8()],
+                        format!("unexpected character '{c}'"),
+                    )
+                    .at_line(i + 1)
+                })?;
+                cells.push(value);
+            }
+        }
+        Ok(Grid { cells, width })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// All cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height()
+    }
+
+    fn index_of(&self, p: Point) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.x as usize + p.y as usize * self.width)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new((i % width) as i64, (i / width) as i64))
+    }
+
+    /// All positions with their cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, whose cell matches.
+    pub fn position(&self, mut f: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| f(cell)).map(|(p, _)| p)
+    }
+
+    /// The neighbours of `p` sharing an edge with it that are inside the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .map(move |dir| p + dir.offset())
+            .filter(|n| self.contains(*n))
+    }
+
+    /// The neighbours of `p` including diagonals that are inside the grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .map(move |dir| p + dir.offset())
+            .filter(|n| self.contains(*n))
+    }
+
+    /// The cells from `p` (exclusive) to the edge of the grid in the given direction.
+    pub fn ray(&self, p: Point, dir: Dir4) -> impl Iterator<Item = &T> + '_ {
+        let mut current = p;
+        std::iter::from_fn(move || {
+            current = current.step(dir);
+            self.get(current)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+
+    /// Draw the grid as text art, one line per row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity(self.cells.len() + self.height());
+        for row in self.cells.chunks(self.width.max(1)) {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// Will panic if `p` is outside of the grid.
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(cell) => cell,
+            None => panic!(
+                "{p} is outside of the {}x{} grid",
+                self.width,
+                self.height()
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height());
+        match self.get_mut(p) {
+            Some(cell) => cell,
+            None => panic!("{p} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_render_round_trip() {
+        // given
+        let input = "#.#\n..#\n";
+
+        // when
+        let grid = Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        });
+
+        // then
+        let grid = grid.expect("expected successful parsing");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Point::new(2, 1)]);
+        assert_eq!(grid.get(Point::new(3, 1)), None);
+        assert_eq!(grid.neighbours4(Point::ORIGIN).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+        assert_eq!(grid.render(|b| if *b { '#' } else { '.' }), input);
+    }
+
+    #[test]
+    fn parse_points_at_bad_characters_and_rows() {
+        // given
+        let bad_char = "..\n.x\n";
+        let bad_row = "..\n...\n";
+
+        // when
+        let bad_char = Grid::parse(bad_char, |c| (c == '.').then_some(()));
+        let bad_row = Grid::parse(bad_row, |c| (c == '.').then_some(()));
+
+        // then
+        let e = bad_char.expect_err("expected an error for 'x'");
+        assert_eq!(e.location.map(|l| (l.line, l.column)), Some((2, 2)));
+        let e = bad_row.expect_err("expected an error for the longer row");
+        assert_eq!(e.location.map(|l| l.line), Some(2));
+    }
+}
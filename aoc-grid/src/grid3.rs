@@ -0,0 +1,96 @@
+use crate::{Bounds, Point3};
+use std::ops::{Index, IndexMut};
+
+/// A dense 3D grid covering a box of space, which does not have to start at the origin.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid3<T> {
+    cells: Vec<T>,
+    bounds: Bounds<Point3>,
+}
+
+impl<T> Grid3<T> {
+    pub fn new(bounds: Bounds<Point3>, value: T) -> Grid3<T>
+    where
+        T: Clone,
+    {
+        let size = bounds.size();
+        let len = (size.x.max(0) * size.y.max(0) * size.z.max(0)) as usize;
+        Grid3 {
+            cells: vec![value; len],
+            bounds,
+        }
+    }
+
+    pub fn bounds(&self) -> Bounds<Point3> {
+        self.bounds
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        self.bounds.contains(p)
+    }
+
+    fn index_of(&self, p: Point3) -> Option<usize> {
+        if self.contains(p) {
+            let size = self.bounds.size();
+            let Point3 { x, y, z } = p - self.bounds.min;
+            Some((x + y * size.x + z * size.x * size.y) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, p: Point3) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point3) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// The neighbours of `p` sharing a face with it that are inside the grid.
+    pub fn neighbours6(&self, p: Point3) -> impl Iterator<Item = Point3> + '_ {
+        p.neighbours6().into_iter().filter(|n| self.contains(*n))
+    }
+}
+
+impl<T> Index<Point3> for Grid3<T> {
+    type Output = T;
+
+    /// Will panic if `p` is outside of the grid.
+    fn index(&self, p: Point3) -> &T {
+        match self.get(p) {
+            Some(cell) => cell,
+            None => panic!("{p} is outside of the grid"),
+        }
+    }
+}
+
+impl<T> IndexMut<Point3> for Grid3<T> {
+    fn index_mut(&mut self, p: Point3) -> &mut T {
+        match self.get_mut(p) {
+            Some(cell) => cell,
+            None => panic!("{p} is outside of the grid"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid_covers_bounds_not_starting_at_origin() {
+        // given
+        let bounds = Bounds::new(Point3::new(-1, -1, -1), Point3::new(1, 1, 1));
+        let mut grid = Grid3::new(bounds, 0);
+
+        // when
+        grid[Point3::new(1, -1, 0)] = 7;
+
+        // then
+        assert_eq!(grid.get(Point3::new(1, -1, 0)), Some(&7));
+        assert_eq!(grid.get(Point3::new(2, 0, 0)), None);
+        assert_eq!(grid.neighbours6(Point3::ORIGIN).count(), 6);
+        assert_eq!(grid.neighbours6(bounds.min).count(), 3);
+    }
+}
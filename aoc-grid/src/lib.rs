@@ -0,0 +1,13 @@
+//! Grids, points and directions shared by the grid-based days.
+
+mod dir;
+mod grid;
+mod grid3;
+mod point;
+mod sparse;
+
+pub use dir::{Dir4, Dir8};
+pub use grid::Grid;
+pub use grid3::Grid3;
+pub use point::{Bounds, Point, Point3};
+pub use sparse::SparseGrid;
@@ -0,0 +1,261 @@
+use crate::{Dir4, Dir8};
+use std::fmt;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// A position on a 2D grid. `y` grows downwards, like the lines of the puzzle input.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// The neighbouring point in the given direction.
+    pub fn step(self, dir: Dir4) -> Point {
+        self + dir.offset()
+    }
+
+    /// The four points sharing an edge with this one, clockwise starting at the top.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Dir4::ALL.into_iter().map(move |dir| self + dir.offset())
+    }
+
+    /// The eight points surrounding this one, clockwise starting at the top.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir.offset())
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Component-wise minimum.
+    pub fn min(self, other: Point) -> Point {
+        Point::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, other: Point) -> Point {
+        Point::new(self.x.max(other.x), self.y.max(other.y))
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// A position in 3D space.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    /// The six points sharing a face with this one.
+    pub fn neighbours6(self) -> [Point3; 6] {
+        let Point3 { x, y, z } = self;
+        [
+            Point3::new(x - 1, y, z),
+            Point3::new(x + 1, y, z),
+            Point3::new(x, y - 1, z),
+            Point3::new(x, y + 1, z),
+            Point3::new(x, y, z - 1),
+            Point3::new(x, y, z + 1),
+        ]
+    }
+
+    pub fn manhattan(self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Component-wise minimum.
+    pub fn min(self, other: Point3) -> Point3 {
+        Point3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, other: Point3) -> Point3 {
+        Point3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+// both point types are just vectors, so implement the arithmetic for both in one go
+macro_rules! impl_ops {
+    ($p:ident { $($field:ident),+ }) => {
+        impl Add for $p {
+            type Output = $p;
+            fn add(self, rhs: $p) -> $p {
+                $p { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+        impl Sub for $p {
+            type Output = $p;
+            fn sub(self, rhs: $p) -> $p {
+                $p { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+        impl Neg for $p {
+            type Output = $p;
+            fn neg(self) -> $p {
+                $p { $($field: -self.$field),+ }
+            }
+        }
+        impl AddAssign for $p {
+            fn add_assign(&mut self, rhs: $p) {
+                *self = *self + rhs;
+            }
+        }
+        impl SubAssign for $p {
+            fn sub_assign(&mut self, rhs: $p) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_ops!(Point { x, y });
+impl_ops!(Point3 { x, y, z });
+
+/// The smallest box containing a set of points, both corners inclusive.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Bounds<P> {
+    pub min: P,
+    pub max: P,
+}
+
+impl Bounds<Point> {
+    /// `None` if there are no points.
+    pub fn of(points: impl IntoIterator<Item = Point>) -> Option<Bounds<Point>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds::new(first, first), |b, p| {
+            Bounds::new(b.min.min(p), b.max.max(p))
+        }))
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// The same box with `n` more tiles on each side.
+    pub fn grow(&self, n: i64) -> Bounds<Point> {
+        Bounds::new(self.min - Point::new(n, n), self.max + Point::new(n, n))
+    }
+
+    /// All points inside the box, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Bounds { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+impl Bounds<Point3> {
+    /// `None` if there are no points.
+    pub fn of(points: impl IntoIterator<Item = Point3>) -> Option<Bounds<Point3>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds::new(first, first), |b, p| {
+            Bounds::new(b.min.min(p), b.max.max(p))
+        }))
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    /// The same box with `n` more cubes on each side.
+    pub fn grow(&self, n: i64) -> Bounds<Point3> {
+        Bounds::new(
+            self.min - Point3::new(n, n, n),
+            self.max + Point3::new(n, n, n),
+        )
+    }
+
+    /// Number of cubes along each axis.
+    pub fn size(&self) -> Point3 {
+        self.max - self.min + Point3::new(1, 1, 1)
+    }
+}
+
+impl<P> Bounds<P> {
+    pub const fn new(min: P, max: P) -> Bounds<P> {
+        Bounds { min, max }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounds_contain_all_points() {
+        // given
+        let points = [Point::new(2, -1), Point::new(-3, 4), Point::new(0, 0)];
+
+        // when
+        let bounds = Bounds::<Point>::of(points);
+
+        // then
+        let bounds = bounds.expect("expected bounds for non-empty points");
+        assert_eq!(bounds, Bounds::new(Point::new(-3, -1), Point::new(2, 4)));
+        assert_eq!(bounds.area(), 36);
+        assert_eq!(bounds.points().count(), 36);
+        assert!(points.iter().all(|p| bounds.contains(*p)));
+    }
+}
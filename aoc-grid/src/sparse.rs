@@ -0,0 +1,122 @@
+use crate::{Bounds, Point};
+use std::collections::HashMap;
+
+/// A 2D grid that only stores occupied positions, for grids without fixed bounds or with only a
+/// few interesting tiles.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Parse text art, one character per tile. Characters for which `cell` returns `None` are
+    /// empty tiles. The top left character is at the origin.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> SparseGrid<T> {
+        let mut grid = SparseGrid::with_capacity(input.len());
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(value) = cell(c) {
+                    grid.insert(Point::new(x as i64, y as i64), value);
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    /// Returns the previous value of the tile, if there was one.
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    /// The occupied tiles in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(p, v)| (*p, v))
+    }
+
+    /// The occupied positions in no particular order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The smallest rectangle containing all occupied tiles.
+    pub fn bounds(&self) -> Option<Bounds<Point>> {
+        Bounds::<Point>::of(self.points())
+    }
+
+    /// Draw the tiles within `bounds` as text art, `empty` for unoccupied tiles.
+    pub fn render(
+        &self,
+        bounds: Bounds<Point>,
+        empty: char,
+        mut f: impl FnMut(&T) -> char,
+    ) -> String {
+        let mut s = String::with_capacity((bounds.area().max(0) + bounds.height().max(0)) as usize);
+        for p in bounds.points() {
+            s.push(self.get(p).map(&mut f).unwrap_or(empty));
+            if p.x == bounds.max.x {
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_render_round_trip() {
+        // given
+        let input = "..#\n#..\n";
+
+        // when
+        let grid = SparseGrid::parse(input, |c| (c == '#').then_some(()));
+
+        // then
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(Point::new(2, 0)));
+        let bounds = grid.bounds().expect("expected bounds for non-empty grid");
+        assert_eq!(grid.render(bounds, '.', |_| '#'), input);
+    }
+}
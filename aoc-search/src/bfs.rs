@@ -0,0 +1,116 @@
+use crate::{trace_path, Found, Graph};
+use std::collections::{HashSet, VecDeque};
+
+/// Breadth-first search from any of the `starts` to the nearest goal, counting steps.
+pub fn bfs<G: Graph>(graph: &G, starts: impl IntoIterator<Item = G::Node>) -> Option<Found<G::Node>>
+where
+    G::Node: Clone,
+{
+    // the queue holds indices into the arena of found nodes
+    let mut arena: Vec<(G::Node, Option<usize>)> = Vec::with_capacity(1024);
+    let mut queue: VecDeque<(usize, u64)> = VecDeque::with_capacity(1024);
+    let mut visited: HashSet<G::Key> = HashSet::with_capacity(1024);
+    for start in starts {
+        if visited.insert(graph.key(&start)) {
+            queue.push_back((arena.len(), 0));
+            arena.push((start, None));
+        }
+    }
+
+    while let Some((i, steps)) = queue.pop_front() {
+        let node = &arena[i].0;
+        if graph.is_goal(node) {
+            return Some(Found {
+                node: node.clone(),
+                cost: steps,
+                path: trace_path(&arena, i),
+            });
+        }
+        if graph.prune(node) {
+            continue;
+        }
+        let successors: Vec<G::Node> = graph
+            .successors(node)
+            .map(|(next, _)| next)
+            .filter(|next| visited.insert(graph.key(next)))
+            .collect();
+        for next in successors {
+            queue.push_back((arena.len(), steps + 1));
+            arena.push((next, Some(i)));
+        }
+    }
+    None
+}
+
+/// All nodes reachable from `start`, in breadth-first order. Goals are not treated specially.
+pub fn reachable<G: Graph>(graph: &G, start: G::Node) -> Vec<G::Node> {
+    let mut nodes: Vec<G::Node> = Vec::with_capacity(1024);
+    let mut visited: HashSet<G::Key> = HashSet::with_capacity(1024);
+    visited.insert(graph.key(&start));
+    nodes.push(start);
+
+    // the found nodes double as queue
+    let mut i = 0;
+    while i < nodes.len() {
+        if !graph.prune(&nodes[i]) {
+            let successors: Vec<G::Node> = graph
+                .successors(&nodes[i])
+                .map(|(next, _)| next)
+                .filter(|next| visited.insert(graph.key(next)))
+                .collect();
+            nodes.extend(successors);
+        }
+        i += 1;
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // numbers, where each number n leads to n + 1 and 2 * n
+    struct Doubling {
+        goal: u64,
+    }
+
+    impl Graph for Doubling {
+        type Node = u64;
+        type Key = u64;
+
+        fn key(&self, node: &u64) -> u64 {
+            *node
+        }
+        fn successors(&self, node: &u64) -> impl Iterator<Item = (u64, u64)> {
+            [(node + 1, 1), (node * 2, 1)].into_iter()
+        }
+        fn is_goal(&self, node: &u64) -> bool {
+            *node == self.goal
+        }
+        fn prune(&self, node: &u64) -> bool {
+            *node > self.goal
+        }
+    }
+
+    #[test]
+    fn bfs_finds_fewest_steps() {
+        // given
+        let graph = Doubling { goal: 10 };
+
+        // when
+        let found = bfs(&graph, [1]);
+        let reached = reachable(&graph, 1);
+
+        // then
+        assert_eq!(
+            found,
+            Some(Found {
+                node: 10,
+                cost: 4,
+                path: vec![1, 2, 4, 5, 10]
+            })
+        );
+        // everything up to the goal, plus the pruned numbers 11, 12, 14, 16, 18 and 20
+        assert_eq!(reached.len(), 16);
+    }
+}
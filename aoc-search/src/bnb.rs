@@ -0,0 +1,132 @@
+use crate::{Best, Optimisation};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+// the entry with the highest bound is the most promising one, so it comes first
+struct QueueEntry<N> {
+    bound: u64,
+    node: N,
+}
+
+impl<N> Ord for QueueEntry<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound.cmp(&other.bound)
+    }
+}
+
+impl<N> PartialOrd for QueueEntry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> PartialEq for QueueEntry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<N> Eq for QueueEntry<N> {}
+
+/// Best-first branch and bound: find the node with the highest value reachable from `start`.
+/// Nodes whose bound is not better than the best value found so far are never expanded.
+pub fn branch_and_bound<O: Optimisation>(problem: &O, start: O::Node) -> Best<O::Node>
+where
+    O::Node: Clone,
+{
+    let mut best = Best {
+        value: problem.value(&start),
+        node: start.clone(),
+    };
+    let mut queue: BinaryHeap<QueueEntry<O::Node>> = BinaryHeap::with_capacity(4096);
+    let mut seen: HashMap<O::Key, u64> = HashMap::with_capacity(4096);
+    queue.push(QueueEntry {
+        bound: problem.bound(&start),
+        node: start,
+    });
+
+    while let Some(QueueEntry { bound, node }) = queue.pop() {
+        // the queue is ordered by bound, so nothing left in it can beat the best node
+        if bound <= best.value {
+            break;
+        }
+        let value = problem.value(&node);
+        if seen
+            .get(&problem.key(&node))
+            .is_some_and(|seen_value| *seen_value >= value)
+        {
+            continue;
+        }
+        seen.insert(problem.key(&node), value);
+        if value > best.value {
+            best = Best {
+                node: node.clone(),
+                value,
+            };
+        }
+        if problem.prune(&node) {
+            continue;
+        }
+        for next in problem.successors(&node) {
+            let bound = problem.bound(&next);
+            if bound > best.value {
+                queue.push(QueueEntry { bound, node: next });
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // pack items with (weight, value) into a knapsack with limited capacity
+    struct Knapsack {
+        items: Vec<(u64, u64)>,
+        capacity: u64,
+    }
+
+    // (next item to decide on, weight so far, value so far)
+    type Packing = (usize, u64, u64);
+
+    impl Optimisation for Knapsack {
+        type Node = Packing;
+        type Key = Packing;
+
+        fn key(&self, node: &Packing) -> Packing {
+            *node
+        }
+        fn successors(&self, &(i, weight, value): &Packing) -> impl Iterator<Item = Packing> {
+            let take = self
+                .items
+                .get(i)
+                .filter(|(w, _)| weight + w <= self.capacity)
+                .map(|(w, v)| (i + 1, weight + w, value + v));
+            let skip = (i < self.items.len()).then_some((i + 1, weight, value));
+            take.into_iter().chain(skip)
+        }
+        fn value(&self, node: &Packing) -> u64 {
+            node.2
+        }
+        fn bound(&self, &(i, _, value): &Packing) -> u64 {
+            value + self.items[i..].iter().map(|(_, v)| v).sum::<u64>()
+        }
+    }
+
+    #[test]
+    fn branch_and_bound_finds_best_packing() {
+        // given
+        let knapsack = Knapsack {
+            items: vec![(5, 10), (4, 40), (6, 30), (3, 50)],
+            capacity: 10,
+        };
+
+        // when
+        let best = branch_and_bound(&knapsack, (0, 0, 0));
+
+        // then
+        assert_eq!(best.value, 90);
+        assert_eq!(best.node.1, 7);
+    }
+}
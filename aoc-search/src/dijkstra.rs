@@ -0,0 +1,222 @@
+use crate::{trace_path, Found, Graph};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// BinaryHeap is a max-heap, so the ordering is reversed to get the cheapest entry first
+struct QueueEntry {
+    priority: u64,
+    cost: u64,
+    // index into the arena of queued nodes
+    index: usize,
+}
+
+impl Ord for QueueEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            // prefer the entry that got further, so ties are resolved depth-first
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl PartialOrd for QueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueueEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for QueueEntry {}
+
+/// Cheapest path from any of the `starts` to a goal.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+) -> Option<Found<G::Node>>
+where
+    G::Node: Clone,
+{
+    search(graph, starts, true, |_| 0, |_, _| {})
+}
+
+/// Like [`dijkstra`], but guided by [`Graph::heuristic`], which has to be consistent for the path
+/// to be the cheapest.
+pub fn astar<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+) -> Option<Found<G::Node>>
+where
+    G::Node: Clone,
+{
+    search(graph, starts, true, |node| graph.heuristic(node), |_, _| {})
+}
+
+/// Costs of the cheapest paths from `start` to every reachable node, by key. Goals are not
+/// treated specially.
+pub fn distances<G: Graph>(graph: &G, start: G::Node) -> HashMap<G::Key, u64>
+where
+    G::Node: Clone,
+{
+    let mut costs: HashMap<G::Key, u64> = HashMap::new();
+    search(
+        graph,
+        [start],
+        false,
+        |_| 0,
+        |key, cost| {
+            costs.insert(key, cost);
+        },
+    );
+    costs
+}
+
+// every node is reported to `settled` once its cheapest cost is known
+fn search<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+    until_goal: bool,
+    heuristic: impl Fn(&G::Node) -> u64,
+    mut settled: impl FnMut(G::Key, u64),
+) -> Option<Found<G::Node>>
+where
+    G::Node: Clone,
+{
+    let mut arena: Vec<(G::Node, Option<usize>)> = Vec::with_capacity(1024);
+    let mut queue: BinaryHeap<QueueEntry> = BinaryHeap::with_capacity(1024);
+    let mut best: HashMap<G::Key, u64> = HashMap::with_capacity(1024);
+    let mut done: HashSet<G::Key> = HashSet::with_capacity(1024);
+    for node in starts {
+        best.insert(graph.key(&node), 0);
+        queue.push(QueueEntry {
+            priority: heuristic(&node),
+            cost: 0,
+            index: arena.len(),
+        });
+        arena.push((node, None));
+    }
+
+    while let Some(QueueEntry { cost, index, .. }) = queue.pop() {
+        let node = &arena[index].0;
+        // a node can be queued several times with decreasing costs, only the first one counts
+        if !done.insert(graph.key(node)) {
+            continue;
+        }
+        settled(graph.key(node), cost);
+        if until_goal && graph.is_goal(node) {
+            return Some(Found {
+                node: node.clone(),
+                cost,
+                path: trace_path(&arena, index),
+            });
+        }
+        if graph.prune(node) {
+            continue;
+        }
+        let mut successors = Vec::new();
+        for (next, step) in graph.successors(node) {
+            let next_cost = cost + step;
+            match best.entry(graph.key(&next)) {
+                Entry::Occupied(e) if *e.get() <= next_cost => continue,
+                Entry::Occupied(mut e) => {
+                    e.insert(next_cost);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(next_cost);
+                }
+            }
+            successors.push((next, next_cost));
+        }
+        for (next, next_cost) in successors {
+            queue.push(QueueEntry {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                index: arena.len(),
+            });
+            arena.push((next, Some(index)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a weighted graph given as adjacency list, with positions on a line for the heuristic
+    struct Weighted {
+        edges: Vec<Vec<(usize, u64)>>,
+        positions: Vec<u64>,
+        goal: usize,
+    }
+
+    impl Graph for Weighted {
+        type Node = usize;
+        type Key = usize;
+
+        fn key(&self, node: &usize) -> usize {
+            *node
+        }
+        fn successors(&self, node: &usize) -> impl Iterator<Item = (usize, u64)> {
+            self.edges[*node].iter().copied()
+        }
+        fn is_goal(&self, node: &usize) -> bool {
+            *node == self.goal
+        }
+        fn heuristic(&self, node: &usize) -> u64 {
+            self.positions[*node].abs_diff(self.positions[self.goal])
+        }
+    }
+
+    fn example() -> Weighted {
+        Weighted {
+            edges: vec![
+                vec![(1, 1), (2, 5)],
+                vec![(2, 1), (3, 7)],
+                vec![(3, 2)],
+                vec![],
+            ],
+            positions: vec![0, 1, 2, 4],
+            goal: 3,
+        }
+    }
+
+    #[test]
+    fn dijkstra_and_astar_find_cheapest_path() {
+        // given
+        let graph = example();
+
+        // when
+        let found_dijkstra = dijkstra(&graph, [0]);
+        let found_astar = astar(&graph, [0]);
+
+        // then
+        assert_eq!(
+            found_dijkstra,
+            Some(Found {
+                node: 3,
+                cost: 4,
+                path: vec![0, 1, 2, 3]
+            })
+        );
+        assert_eq!(found_astar, found_dijkstra);
+    }
+
+    #[test]
+    fn distances_cover_all_reachable_nodes() {
+        // given
+        let graph = example();
+
+        // when
+        let costs = distances(&graph, 0);
+
+        // then
+        assert_eq!(costs, HashMap::from([(0, 0), (1, 1), (2, 2), (3, 4)]));
+    }
+}
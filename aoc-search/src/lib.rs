@@ -0,0 +1,94 @@
+//! Graph searches shared by the days that look for shortest paths or best strategies.
+//!
+//! A day only describes its search space by implementing [`Graph`] (for shortest paths) or
+//! [`Optimisation`] (for maximising a value), the queues and visited sets live here.
+
+use std::hash::Hash;
+
+mod bfs;
+mod bnb;
+mod dijkstra;
+
+pub use bfs::{bfs, reachable};
+pub use bnb::branch_and_bound;
+pub use dijkstra::{astar, dijkstra, distances};
+
+/// A search space for shortest paths.
+pub trait Graph {
+    type Node;
+    /// Nodes with the same key are the same for the search and only visited once, e.g. positions
+    /// with the time modulo the cycle length of the blizzards.
+    type Key: Hash + Eq;
+
+    fn key(&self, node: &Self::Node) -> Self::Key;
+
+    /// The nodes reachable in one step, with the cost of that step. [`bfs`] ignores the costs.
+    fn successors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, u64)>;
+
+    fn is_goal(&self, _node: &Self::Node) -> bool {
+        false
+    }
+
+    /// Estimated cost from `node` to the nearest goal, for [`astar`]. It must be consistent: never
+    /// more than the cost of a step plus the estimate for the node the step leads to, and 0 at
+    /// goals. Nodes are never expanded twice, so never overestimating alone is not enough.
+    fn heuristic(&self, _node: &Self::Node) -> u64 {
+        0
+    }
+
+    /// Nodes for which this returns `true` are dropped without being expanded.
+    fn prune(&self, _node: &Self::Node) -> bool {
+        false
+    }
+}
+
+/// A search space for the node with the highest value, see [`branch_and_bound`].
+pub trait Optimisation {
+    type Node;
+    /// Of all nodes with the same key only the one with the highest value is expanded.
+    type Key: Hash + Eq;
+
+    fn key(&self, node: &Self::Node) -> Self::Key;
+
+    fn successors(&self, node: &Self::Node) -> impl Iterator<Item = Self::Node>;
+
+    /// The value reached if the search stopped at this node.
+    fn value(&self, node: &Self::Node) -> u64;
+
+    /// An upper bound for the value of `node` and every node reachable from it. The tighter the
+    /// bound, the less of the search space has to be explored.
+    fn bound(&self, node: &Self::Node) -> u64;
+
+    /// Nodes for which this returns `true` are dropped without being expanded.
+    fn prune(&self, _node: &Self::Node) -> bool {
+        false
+    }
+}
+
+/// The goal node found by a search, the total cost to get there and the path that was taken,
+/// from the start to the goal node (both included).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Found<N> {
+    pub node: N,
+    pub cost: u64,
+    pub path: Vec<N>,
+}
+
+// The searches keep every queued node in an arena, together with the index of the node it was
+// reached from, so the path can be traced back once a goal is found.
+fn trace_path<N: Clone>(arena: &[(N, Option<usize>)], mut i: usize) -> Vec<N> {
+    let mut path = vec![arena[i].0.clone()];
+    while let Some(parent) = arena[i].1 {
+        path.push(arena[parent].0.clone());
+        i = parent;
+    }
+    path.reverse();
+    path
+}
+
+/// The best node found by [`branch_and_bound`] and its value.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Best<N> {
+    pub node: N,
+    pub value: u64,
+}
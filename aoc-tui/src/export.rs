@@ -0,0 +1,351 @@
+use crate::gif::GifEncoder;
+use crate::png::encode_png;
+use crate::Simulation;
+use aoc_common::{take_option, Error};
+use std::collections::{HashMap, HashSet};
+use std::fs::{create_dir_all, write, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const DEFAULT_CELL_SIZE: usize = 4;
+// hundredths of a second per frame
+const DEFAULT_DELAY: u16 = 5;
+const BACKGROUND: Rgb = (0, 0, 0);
+// characters without colour, like the default colour of a terminal
+const FOREGROUND: Rgb = (200, 200, 200);
+
+/// A colour as red, green and blue.
+pub type Rgb = (u8, u8, u8);
+
+/// Where the images go.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ImageFormat {
+    /// One animated GIF.
+    Gif(PathBuf),
+    /// A directory with one PNG file per frame.
+    Png(PathBuf),
+}
+
+/// How to turn the steps of a simulation into images.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Export {
+    pub format: ImageFormat,
+    /// Width and height in pixels of one character of the rendered state.
+    pub cell_size: usize,
+    pub palette: Palette,
+    /// Only every n-th step becomes a frame. The first and the last state always do.
+    pub every: usize,
+    /// How long a frame of the GIF is shown, in hundredths of a second.
+    pub delay: u16,
+}
+
+impl Export {
+    /// Take the export options out of the command line arguments, if there is `--gif <file>` or
+    /// `--png <directory>`.
+    pub fn from_args(args: &mut Vec<String>) -> Result<Option<Export>, Error> {
+        let gif = take_option(args, "--gif")?;
+        let png = take_option(args, "--png")?;
+        let cell_size = take_option(args, "--cell-size")?;
+        let palette = take_option(args, "--palette")?;
+        let every = take_option(args, "--every")?;
+        let delay = take_option(args, "--delay")?;
+        let format = match (gif, png) {
+            (Some(gif), None) => ImageFormat::Gif(gif.into()),
+            (None, Some(png)) => ImageFormat::Png(png.into()),
+            (Some(_), Some(_)) => {
+                return Err(Error::semantic(
+                    "export either to --gif or to --png, not both",
+                ))
+            }
+            (None, None) if [&cell_size, &palette, &every].iter().any(|o| o.is_some()) => {
+                return Err(Error::semantic(
+                    "--cell-size, --palette and --every need --gif or --png",
+                ))
+            }
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(Export {
+            format,
+            cell_size: parse_option(cell_size, "cell size", DEFAULT_CELL_SIZE)?,
+            palette: palette.map(|p| p.parse()).transpose()?.unwrap_or_default(),
+            every: parse_option(every, "number of steps per frame", 1)?,
+            delay: parse_option(delay, "delay", DEFAULT_DELAY)?,
+        }))
+    }
+}
+
+fn parse_option<T: FromStr + PartialEq + Default>(
+    value: Option<String>,
+    name: &str,
+    default: T,
+) -> Result<T, Error> {
+    match value {
+        None => Ok(default),
+        Some(v) => match v.parse() {
+            Ok(n) if n != T::default() => Ok(n),
+            _ => Err(Error::semantic(format!("'{v}' is not a valid {name}"))),
+        },
+    }
+}
+
+/// Colours that replace those of the simulation, given as `background=000000,#=ff8800,o=ffff00`.
+/// The background also fills the image around smaller frames.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Palette {
+    pub background: Option<Rgb>,
+    pub colours: HashMap<char, Rgb>,
+}
+
+impl Palette {
+    /// The colour of a character: from the palette, else from the simulation. Spaces and dots
+    /// without colour are background.
+    pub fn colour<S: Simulation>(&self, simulation: &S, c: char) -> Rgb {
+        self.colours
+            .get(&c)
+            .copied()
+            .or_else(|| simulation.colour(c))
+            .unwrap_or(match c {
+                ' ' | '.' => self.background(),
+                _ => FOREGROUND,
+            })
+    }
+
+    pub fn background(&self) -> Rgb {
+        self.background.unwrap_or(BACKGROUND)
+    }
+}
+
+impl FromStr for Palette {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut palette = Palette::default();
+        for entry in s.split(',').filter(|e| !e.is_empty()) {
+            let invalid = || {
+                Error::semantic(format!(
+                    "invalid palette entry '{entry}', expected e.g. '#=ff8800'"
+                ))
+            };
+            let (name, colour) = entry.rsplit_once('=').ok_or_else(invalid)?;
+            let colour = colour.strip_prefix('#').unwrap_or(colour);
+            if colour.len() != 6 || !colour.is_ascii() {
+                return Err(invalid());
+            }
+            let channel = |i: usize| u8::from_str_radix(&colour[i..i + 2], 16);
+            let rgb = (channel(0), channel(2), channel(4));
+            let (Ok(r), Ok(g), Ok(b)) = rgb else {
+                return Err(invalid());
+            };
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                _ if name == "background" => palette.background = Some((r, g, b)),
+                (Some(c), None) => {
+                    palette.colours.insert(c, (r, g, b));
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(palette)
+    }
+}
+
+/// Write the frames of the simulation to the images given by `export`. Returns the number of
+/// frames.
+pub fn export<S: Simulation>(simulation: S, export: &Export) -> Result<usize, Error> {
+    match &export.format {
+        ImageFormat::Gif(path) => {
+            let file = File::create(path).map_err(|e| {
+                Error::semantic(format!("Unable to create '{}': {e}", path.display()))
+            })?;
+            write_gif(simulation, export, BufWriter::new(file))
+        }
+        ImageFormat::Png(dir) => {
+            create_dir_all(dir)?;
+            let canvas = Canvas::measure(simulation.clone(), export)?;
+            for_each_frame(simulation, export.every, |i, simulation| {
+                let (width, height, pixels) = canvas.draw(simulation, export);
+                let png = encode_png(width, height, &canvas.colours, &pixels);
+                write(dir.join(format!("frame-{i:05}.png")), png)?;
+                Ok(())
+            })
+        }
+    }
+}
+
+/// Write the frames of the simulation as animated GIF. Returns the number of frames.
+pub fn write_gif<S: Simulation, W: Write>(
+    simulation: S,
+    export: &Export,
+    out: W,
+) -> Result<usize, Error> {
+    let canvas = Canvas::measure(simulation.clone(), export)?;
+    let (width, height) = canvas.size(export);
+    let mut gif = GifEncoder::new(out, width, height, &canvas.colours)?;
+    let frames = for_each_frame(simulation, export.every, |_, simulation| {
+        let (_, _, pixels) = canvas.draw(simulation, export);
+        Ok(gif.frame(&pixels, export.delay)?)
+    })?;
+    gif.finish()?;
+    Ok(frames)
+}
+
+// call `f` with the number and the state of every frame
+fn for_each_frame<S: Simulation>(
+    mut simulation: S,
+    every: usize,
+    mut f: impl FnMut(usize, &S) -> Result<(), Error>,
+) -> Result<usize, Error> {
+    for i in 0.. {
+        f(i, &simulation)?;
+        if (0..every).take_while(|_| simulation.step()).count() == 0 {
+            return Ok(i + 1);
+        }
+    }
+    unreachable!()
+}
+
+// The size in characters of the largest frame, and all colours of all frames. Frames are centred
+// horizontally and stand on the bottom of the image, as sand and rocks pile up on the floor.
+struct Canvas {
+    cols: usize,
+    rows: usize,
+    // the background comes first
+    colours: Vec<Rgb>,
+}
+
+impl Canvas {
+    fn measure<S: Simulation>(simulation: S, export: &Export) -> Result<Canvas, Error> {
+        let mut canvas = Canvas {
+            cols: 1,
+            rows: 1,
+            colours: vec![export.palette.background()],
+        };
+        for_each_frame(simulation, export.every, |_, simulation| {
+            let rendered = simulation.render();
+            let mut chars = HashSet::new();
+            for line in rendered.lines() {
+                canvas.cols = canvas.cols.max(line.chars().count());
+                chars.extend(line.chars());
+            }
+            canvas.rows = canvas.rows.max(rendered.lines().count());
+            for c in chars {
+                let colour = export.palette.colour(simulation, c);
+                if !canvas.colours.contains(&colour) {
+                    canvas.colours.push(colour);
+                }
+            }
+            if canvas.colours.len() > 256 {
+                return Err(Error::semantic(
+                    "the images can't have more than 256 colours",
+                ));
+            }
+            Ok(())
+        })?;
+        Ok(canvas)
+    }
+
+    // in pixels
+    fn size(&self, export: &Export) -> (usize, usize) {
+        (self.cols * export.cell_size, self.rows * export.cell_size)
+    }
+
+    // width, height and colour indices of the pixels of a frame
+    fn draw<S: Simulation>(&self, simulation: &S, export: &Export) -> (usize, usize, Vec<u8>) {
+        let (width, height) = self.size(export);
+        let mut pixels = vec![0; width * height];
+        let rendered = simulation.render();
+        let lines: Vec<Vec<char>> = rendered.lines().map(|l| l.chars().collect()).collect();
+        let frame_cols = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+        let top = self.rows - lines.len();
+        let left = (self.cols - frame_cols) / 2;
+        let cell_size = export.cell_size;
+        let mut indices: HashMap<char, u8> = HashMap::new();
+        for (y, line) in lines.iter().enumerate() {
+            // draw the first row of pixels of the line, then copy it
+            let start = (top + y) * cell_size * width;
+            for (x, c) in line.iter().enumerate() {
+                let index = *indices.entry(*c).or_insert_with(|| {
+                    let colour = export.palette.colour(simulation, *c);
+                    self.colours.iter().position(|&c| c == colour).unwrap_or(0) as u8
+                });
+                let px = start + (left + x) * cell_size;
+                pixels[px..px + cell_size].fill(index);
+            }
+            for row in 1..cell_size {
+                pixels.copy_within(start..start + width, start + row * width);
+            }
+        }
+        (width, height, pixels)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a tower of `#` that grows by one line per step
+    #[derive(Clone, Debug)]
+    struct Tower(usize);
+
+    impl Simulation for Tower {
+        fn step(&mut self) -> bool {
+            if self.0 == 5 {
+                return false;
+            }
+            self.0 += 1;
+            true
+        }
+        fn render(&self) -> String {
+            (0..self.0).map(|i| "#".repeat(i + 1) + "\n").collect()
+        }
+        fn status(&self) -> String {
+            format!("{} lines", self.0)
+        }
+        fn colour(&self, c: char) -> Option<Rgb> {
+            (c == '#').then_some((255, 0, 0))
+        }
+    }
+
+    #[test]
+    fn palette_can_be_parsed() {
+        // given
+        let spec = "background=102030,#=#ff8800,==00ff00";
+
+        // when
+        let palette: Result<Palette, Error> = spec.parse();
+        let invalid: Result<Palette, Error> = "#=ff88".parse();
+
+        // then
+        let palette = palette.expect("expected successful parsing");
+        assert_eq!(palette.background, Some((16, 32, 48)));
+        assert_eq!(palette.colours[&'#'], (255, 136, 0));
+        assert_eq!(palette.colours[&'='], (0, 255, 0));
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn write_gif_draws_every_frame() {
+        // given
+        let export = Export {
+            format: ImageFormat::Gif(PathBuf::new()),
+            cell_size: 2,
+            palette: "#=00ff00".parse().expect("expected successful parsing"),
+            every: 2,
+            delay: 10,
+        };
+
+        // when
+        let mut gif = Vec::new();
+        let frames = write_gif(Tower(0), &export, &mut gif);
+        let canvas = Canvas::measure(Tower(0), &export).expect("expected a canvas");
+
+        // then
+        assert_eq!(frames, Ok(4));
+        assert_eq!((canvas.cols, canvas.rows), (5, 5));
+        assert_eq!(canvas.colours, [(0, 0, 0), (0, 255, 0)]);
+        // 10x10 pixels
+        assert!(gif.starts_with(b"GIF89a\x0a\x00\x0a\x00"));
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::Rgb;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+// LZW codes have at most 12 bits
+const MAX_CODES: u16 = 4096;
+
+/// Writes an animated GIF that loops forever, frame by frame. Only the part of a frame that
+/// changed since the frame before is stored.
+pub struct GifEncoder<W: Write> {
+    out: W,
+    width: usize,
+    height: usize,
+    // bits per colour index, at least 2 as required for LZW
+    depth: u8,
+    previous: Option<Vec<u8>>,
+}
+
+impl<W: Write> GifEncoder<W> {
+    /// Write the header with the global colour table, which has at most 256 colours.
+    pub fn new(
+        mut out: W,
+        width: usize,
+        height: usize,
+        palette: &[Rgb],
+    ) -> io::Result<GifEncoder<W>> {
+        if width > u16::MAX as usize || height > u16::MAX as usize || palette.len() > 256 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "a GIF can't be {width}x{height} pixels with {} colours",
+                    palette.len()
+                ),
+            ));
+        }
+        let depth = (2..=8).find(|d| palette.len() <= 1 << d).unwrap_or(8);
+
+        out.write_all(b"GIF89a")?;
+        out.write_all(&(width as u16).to_le_bytes())?;
+        out.write_all(&(height as u16).to_le_bytes())?;
+        // global colour table of 2^depth colours, background colour 0, square pixels
+        out.write_all(&[0x80 | (depth - 1) << 4 | (depth - 1), 0, 0])?;
+        for i in 0..1 << depth {
+            let (r, g, b) = palette.get(i).copied().unwrap_or_default();
+            out.write_all(&[r, g, b])?;
+        }
+        // the application extension that makes the animation loop forever
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        Ok(GifEncoder {
+            out,
+            width,
+            height,
+            depth,
+            previous: None,
+        })
+    }
+
+    /// Add a frame of `width * height` colour indices, shown for `delay` hundredths of a second.
+    pub fn frame(&mut self, pixels: &[u8], delay: u16) -> io::Result<()> {
+        let (left, top, right, bottom) = match &self.previous {
+            None => (0, 0, self.width, self.height),
+            // a frame without changes still needs a pixel for its delay
+            Some(previous) => changed_area(previous, pixels, self.width).unwrap_or((0, 0, 1, 1)),
+        };
+
+        // graphic control extension: keep the previous frame below, wait `delay`
+        self.out.write_all(&[0x21, 0xf9, 0x04, 1 << 2])?;
+        self.out.write_all(&delay.to_le_bytes())?;
+        self.out.write_all(&[0, 0])?;
+
+        // image descriptor without local colour table
+        self.out.write_all(&[0x2c])?;
+        for value in [left, top, right - left, bottom - top] {
+            self.out.write_all(&(value as u16).to_le_bytes())?;
+        }
+        self.out.write_all(&[0])?;
+
+        let area: Vec<u8> = (top..bottom)
+            .flat_map(|y| &pixels[y * self.width + left..y * self.width + right])
+            .copied()
+            .collect();
+        self.out.write_all(&[self.depth])?;
+        for block in lzw(self.depth, &area).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+        self.out.write_all(&[0])?;
+
+        self.previous = Some(pixels.to_vec());
+        Ok(())
+    }
+
+    /// Write the trailer and return the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.write_all(&[0x3b])?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+// left, top, right and bottom (exclusive) of the pixels that differ
+fn changed_area(a: &[u8], b: &[u8], width: usize) -> Option<(usize, usize, usize, usize)> {
+    let mut area: Option<(usize, usize, usize, usize)> = None;
+    // comparing whole rows first is much faster, most of them stay the same
+    for (y, (row_a, row_b)) in a.chunks(width).zip(b.chunks(width)).enumerate() {
+        if row_a == row_b {
+            continue;
+        }
+        let left = (0..width).find(|&x| row_a[x] != row_b[x]).unwrap_or(0);
+        let right = (0..width).rfind(|&x| row_a[x] != row_b[x]).unwrap_or(0) + 1;
+        area = Some(match area {
+            None => (left, y, right, y + 1),
+            Some((l, t, r, _)) => (l.min(left), t, r.max(right), y + 1),
+        });
+    }
+    area
+}
+
+// the colour indices compressed with variable-length codes, packed least significant bit first
+fn lzw(min_code_size: u8, indices: &[u8]) -> Vec<u8> {
+    let clear: u16 = 1 << min_code_size;
+    let end = clear + 1;
+    let mut out = Vec::new();
+    let (mut bits, mut count) = (0u32, 0u8);
+    let mut write = |code: u16, size: u8| {
+        bits |= (code as u32) << count;
+        count += size;
+        while count >= 8 {
+            out.push(bits as u8);
+            bits >>= 8;
+            count -= 8;
+        }
+    };
+
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut size = min_code_size + 1;
+    let mut next = end + 1;
+    write(clear, size);
+    let mut indices = indices.iter().copied();
+    if let Some(first) = indices.next() {
+        let mut prefix = first as u16;
+        for index in indices {
+            if let Some(&code) = codes.get(&(prefix, index)) {
+                prefix = code;
+                continue;
+            }
+            write(prefix, size);
+            if next < MAX_CODES {
+                codes.insert((prefix, index), next);
+                next += 1;
+                if next > 1 << size && size < 12 {
+                    size += 1;
+                }
+            } else {
+                // the table is full, start over
+                write(clear, size);
+                codes.clear();
+                size = min_code_size + 1;
+                next = end + 1;
+            }
+            prefix = index as u16;
+        }
+        write(prefix, size);
+        // the decoder adds a code for the last prefix, which may need another bit
+        if next == 1 << size && size < 12 {
+            size += 1;
+        }
+    }
+    write(end, size);
+    if count > 0 {
+        out.push(bits as u8);
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // the reverse of `lzw`, as a GIF decoder does it
+    fn unlzw(min_code_size: u8, data: &[u8]) -> Vec<u8> {
+        let clear: u16 = 1 << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut out = Vec::new();
+        let (mut bits, mut count, mut bytes) = (0u32, 0u8, data.iter());
+        loop {
+            while count < size {
+                bits |= (*bytes.next().expect("missing end code") as u32) << count;
+                count += 8;
+            }
+            let code = (bits & ((1 << size) - 1)) as u16;
+            bits >>= size;
+            count -= size;
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(p)) => [p.clone(), vec![p[0]]].concat(),
+                (None, None) => panic!("unknown code {code}"),
+            };
+            if let Some(p) = previous {
+                if table.len() < MAX_CODES as usize {
+                    table.push([p, vec![entry[0]]].concat());
+                }
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+            out.extend(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_codes_can_be_decoded() {
+        // given
+        let mut seed = 7u32;
+        // long enough to fill the table a few times
+        let indices: Vec<u8> = (0..50_000)
+            .map(|i| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                if i % 3 == 0 {
+                    (seed >> 16) as u8 % 5
+                } else {
+                    0
+                }
+            })
+            .collect();
+
+        // when
+        let decoded: Vec<Vec<u8>> = [&indices[..], &indices[..1], &indices[..7], &[]]
+            .iter()
+            .map(|part| unlzw(3, &lzw(3, part)))
+            .collect();
+
+        // then
+        assert_eq!(decoded[0], indices);
+        assert_eq!(decoded[1], &indices[..1]);
+        assert_eq!(decoded[2], &indices[..7]);
+        assert!(decoded[3].is_empty());
+    }
+}
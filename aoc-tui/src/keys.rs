@@ -0,0 +1,67 @@
+/// A key pressed in the terminal.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Escape,
+}
+
+/// The keys in the bytes read from a terminal in raw mode. Arrow keys arrive as escape sequences,
+/// unknown sequences are dropped.
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut chars = text.chars().peekable();
+    let mut keys = Vec::with_capacity(bytes.len());
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            keys.push(Key::Char(c));
+            continue;
+        }
+        if chars.next_if_eq(&'[').is_none() {
+            keys.push(Key::Escape);
+            continue;
+        }
+        // the parameters of the sequence end at its final letter
+        let mut last = None;
+        for c in chars.by_ref() {
+            if c.is_ascii_alphabetic() || c == '~' {
+                last = Some(c);
+                break;
+            }
+        }
+        match last {
+            Some('A') => keys.push(Key::Up),
+            Some('B') => keys.push(Key::Down),
+            Some('C') => keys.push(Key::Right),
+            Some('D') => keys.push(Key::Left),
+            _ => {}
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_keys_understands_arrows_and_escape() {
+        // when
+        let keys = parse_keys(b"q\x1b[A\x1b[1;5C\x1b[3~ \x1b");
+
+        // then
+        assert_eq!(
+            keys,
+            vec![
+                Key::Char('q'),
+                Key::Up,
+                Key::Right,
+                Key::Char(' '),
+                Key::Escape
+            ]
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! A terminal UI for the days that simulate something step by step, to watch the simulation play,
+//! pause it, step through it and rewind it, and an exporter that turns the steps into an animated
+//! GIF or PNG images.
+//!
+//! A day only describes its simulation by implementing [`Simulation`], the history of steps, the
+//! terminal handling and the image encoders live here. The terminal is driven by ANSI escape codes
+//! and `stty`, so this works in any Unix terminal without a terminal library.
+
+use aoc_common::{inputs_from_args, split_text_args, Error};
+use std::env;
+
+mod export;
+mod gif;
+mod keys;
+mod player;
+mod png;
+mod terminal;
+
+pub use export::{export, write_gif, Export, ImageFormat, Palette, Rgb};
+pub use gif::GifEncoder;
+pub use keys::{parse_keys, Key};
+pub use player::Player;
+pub use terminal::run;
+
+/// A simulation that can be shown in the terminal UI. It is cloned to remember earlier steps for
+/// rewinding, so large parts that never change are best borrowed.
+pub trait Simulation: Clone {
+    /// Advance by one step. Returns `false` if the simulation is over, without changing anything.
+    fn step(&mut self) -> bool;
+
+    /// The current state as text art.
+    fn render(&self) -> String;
+
+    /// A short description of the current state, e.g. the round or the number of rocks.
+    fn status(&self) -> String;
+
+    /// The line and column of the rendered state that the view follows, e.g. the head of a rope.
+    fn focus(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// The colour of a character of the rendered state, as RGB. Characters without colour are
+    /// drawn in the colour of the terminal.
+    fn colour(&self, _c: char) -> Option<Rgb> {
+        None
+    }
+}
+
+/// How to show a simulation.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Output {
+    /// Play it in the terminal UI.
+    Terminal,
+    /// Write its steps to images.
+    Images(Export),
+}
+
+impl Output {
+    pub fn show<S: Simulation>(&self, simulation: S) -> Result<(), Error> {
+        match self {
+            Output::Terminal => run(simulation),
+            Output::Images(options) => {
+                let frames = export(simulation, options)?;
+                eprintln!("wrote {frames} frames");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The puzzle input of a simulation and how to show it, if `--tui` or the options of [`Export`]
+/// are among the command line arguments. The input is read like for solving the puzzle, but
+/// there has to be exactly one.
+pub fn simulation_input() -> Result<Option<(String, Output)>, Error> {
+    let (mut rest, tui): (Vec<String>, Vec<String>) =
+        env::args().skip(1).partition(|arg| arg != "--tui");
+    let output = match (tui.is_empty(), Export::from_args(&mut rest)?) {
+        (true, None) => return Ok(None),
+        (true, Some(export)) => Output::Images(export),
+        (false, None) => Output::Terminal,
+        (false, Some(_)) => {
+            return Err(Error::semantic(
+                "either show the simulation with --tui or export it, not both",
+            ))
+        }
+    };
+    let positional = split_text_args(rest)?;
+    match &inputs_from_args(&positional)[..] {
+        [input] => Ok(Some((input.read()?, output))),
+        _ => Err(Error::semantic(
+            "a simulation shows only one input at a time",
+        )),
+    }
+}
@@ -0,0 +1,136 @@
+use crate::Simulation;
+
+// once there are more checkpoints, every other one is dropped
+const MAX_CHECKPOINTS: usize = 64;
+
+/// Steps a simulation forward and back. Going back replays the simulation from the last
+/// checkpoint before the wanted step. The longer the simulation runs, the further apart the
+/// checkpoints are, so their number stays bounded.
+#[derive(Clone, Debug)]
+pub struct Player<S> {
+    current: S,
+    step: usize,
+    // checkpoints[i] is the state after i * interval steps
+    checkpoints: Vec<S>,
+    interval: usize,
+    // the number of steps the simulation takes, once the end was reached
+    end: Option<usize>,
+}
+
+impl<S: Simulation> Player<S> {
+    pub fn new(simulation: S) -> Player<S> {
+        Player {
+            checkpoints: vec![simulation.clone()],
+            current: simulation,
+            step: 0,
+            interval: 1,
+            end: None,
+        }
+    }
+
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// The number of steps taken to get to the current state.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.end == Some(self.step)
+    }
+
+    /// Take one step. Returns `false` if the simulation is over.
+    pub fn forward(&mut self) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        if !self.current.step() {
+            self.end = Some(self.step);
+            return false;
+        }
+        self.step += 1;
+        if self.step.is_multiple_of(self.interval)
+            && self.step / self.interval == self.checkpoints.len()
+        {
+            self.checkpoints.push(self.current.clone());
+            if self.checkpoints.len() > MAX_CHECKPOINTS {
+                self.checkpoints = self.checkpoints.drain(..).step_by(2).collect();
+                self.interval *= 2;
+            }
+        }
+        true
+    }
+
+    /// Undo one step. Returns `false` at the start of the simulation.
+    pub fn back(&mut self) -> bool {
+        if self.step == 0 {
+            return false;
+        }
+        self.seek(self.step - 1);
+        true
+    }
+
+    /// Go to the state after `target` steps, or to the end if the simulation is shorter.
+    pub fn seek(&mut self, target: usize) {
+        if target < self.step {
+            let i = (target / self.interval).min(self.checkpoints.len() - 1);
+            self.current = self.checkpoints[i].clone();
+            self.step = i * self.interval;
+        }
+        while self.step < target && self.forward() {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // counts up to a limit
+    #[derive(Clone, Debug)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                return false;
+            }
+            self.value += 1;
+            true
+        }
+        fn render(&self) -> String {
+            self.value.to_string()
+        }
+        fn status(&self) -> String {
+            format!("{} of {}", self.value, self.limit)
+        }
+    }
+
+    #[test]
+    fn player_steps_forward_and_back() {
+        // given
+        let mut player = Player::new(Counter {
+            value: 0,
+            limit: 1000,
+        });
+
+        // when
+        while player.forward() {}
+        let end = player.step();
+        player.back();
+        let before_end = player.current().value;
+        player.seek(123);
+        let sought = player.current().value;
+
+        // then
+        assert_eq!(end, 1000);
+        assert_eq!(before_end, 999);
+        assert_eq!((player.step(), sought), (123, 123));
+        assert!(player.checkpoints.len() <= MAX_CHECKPOINTS);
+        assert!(!player.is_finished());
+    }
+}
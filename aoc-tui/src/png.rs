@@ -0,0 +1,345 @@
+use crate::Rgb;
+use std::collections::HashMap;
+
+// deflate can refer back this far
+const WINDOW: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// A PNG image with up to 256 colours. `pixels` are indices into `palette`, row by row.
+pub fn encode_png(width: usize, height: usize, palette: &[Rgb], pixels: &[u8]) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bits per pixel, indexed colour, default compression, filter and no interlacing
+    header.extend([8, 3, 0, 0, 0]);
+    chunk(&mut png, b"IHDR", &header);
+
+    let colours: Vec<u8> = palette.iter().flat_map(|&(r, g, b)| [r, g, b]).collect();
+    chunk(&mut png, b"PLTE", &colours);
+
+    // every row starts with its filter type, which is always "none"
+    let mut data = Vec::with_capacity((width + 1) * height);
+    for row in pixels.chunks(width.max(1)).take(height) {
+        data.push(0);
+        data.extend_from_slice(row);
+    }
+    chunk(&mut png, b"IDAT", &zlib(&data));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+pub(crate) fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in bytes.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+// a zlib stream of a single deflate block with the fixed Huffman codes
+fn zlib(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    // deflate with a 32K window, no preset dictionary, fastest compression
+    out.bytes.extend([0x78, 0x01]);
+    // final block, fixed Huffman codes
+    out.write(1, 1);
+    out.write(1, 2);
+
+    // the last position at which three bytes were seen
+    let mut last_seen: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut i = 0;
+    while i < data.len() {
+        let found = data
+            .get(i..i + MIN_MATCH)
+            .and_then(|key| last_seen.get(key))
+            .filter(|&&start| i - start <= WINDOW)
+            .map(|&start| {
+                let len = (0..MAX_MATCH.min(data.len() - i))
+                    .take_while(|&n| data[start + n] == data[i + n])
+                    .count();
+                (i - start, len)
+            });
+        let step = match found {
+            Some((distance, len)) if len >= MIN_MATCH => {
+                out.length(len);
+                out.distance(distance);
+                len
+            }
+            _ => {
+                out.literal(data[i] as u16);
+                1
+            }
+        };
+        for j in i..i + step {
+            if let Some(key) = data.get(j..j + MIN_MATCH) {
+                last_seen.insert([key[0], key[1], key[2]], j);
+            }
+        }
+        i += step;
+    }
+    // end of block
+    out.literal(256);
+
+    let mut bytes = out.finish();
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: u32,
+    count: u8,
+}
+
+impl BitWriter {
+    // the lowest `count` bits of `value`, least significant first
+    fn write(&mut self, value: u32, count: u8) {
+        self.bits |= value << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.bits as u8);
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // Huffman codes are stored most significant bit first
+    fn code(&mut self, code: u32, count: u8) {
+        let reversed = code.reverse_bits() >> (32 - count);
+        self.write(reversed, count);
+    }
+
+    // a literal byte, the end of block or a length code of the fixed literal/length alphabet
+    fn literal(&mut self, symbol: u16) {
+        let symbol = symbol as u32;
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    fn length(&mut self, len: usize) {
+        let i = LENGTH_BASE
+            .iter()
+            .rposition(|&base| base as usize <= len)
+            .unwrap_or(0);
+        self.literal(257 + i as u16);
+        self.write((len - LENGTH_BASE[i] as usize) as u32, LENGTH_EXTRA[i]);
+    }
+
+    fn distance(&mut self, distance: usize) {
+        let i = DISTANCE_BASE
+            .iter()
+            .rposition(|&base| base as usize <= distance)
+            .unwrap_or(0);
+        self.code(i as u32, 5);
+        self.write(
+            (distance - DISTANCE_BASE[i] as usize) as u32,
+            DISTANCE_EXTRA[i],
+        );
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.bits as u8);
+        }
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // reads the bits of a deflate stream, least significant first
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bits(&mut self, count: u8) -> usize {
+            (0..count).fold(0, |value, i| {
+                let bit = (self.bytes[self.pos / 8] >> (self.pos % 8)) & 1;
+                self.pos += 1;
+                value | (bit as usize) << i
+            })
+        }
+
+        // Huffman codes come most significant bit first
+        fn code(&mut self, code: usize, count: u8) -> usize {
+            (0..count).fold(code, |code, _| code << 1 | self.bits(1))
+        }
+
+        fn symbol(&mut self) -> usize {
+            let code = self.code(0, 7);
+            if code <= 0x17 {
+                return 256 + code;
+            }
+            let code = self.code(code, 1);
+            match code {
+                0x30..=0xbf => code - 0x30,
+                0xc0..=0xc7 => 280 + code - 0xc0,
+                _ => 144 + self.code(code, 1) - 0x190,
+            }
+        }
+    }
+
+    // the reverse of `zlib`, for a single block with the fixed Huffman codes
+    fn inflate(zlib: &[u8]) -> Vec<u8> {
+        assert_eq!(zlib[..2], [0x78, 0x01]);
+        let mut bits = BitReader {
+            bytes: &zlib[2..],
+            pos: 0,
+        };
+        assert_eq!(
+            (bits.bits(1), bits.bits(2)),
+            (1, 1),
+            "expected a fixed block"
+        );
+        let mut out: Vec<u8> = Vec::new();
+        loop {
+            let symbol = bits.symbol();
+            match symbol {
+                0..=255 => out.push(symbol as u8),
+                256 => break,
+                _ => {
+                    let i = symbol - 257;
+                    let len = LENGTH_BASE[i] as usize + bits.bits(LENGTH_EXTRA[i]);
+                    let i = bits.code(0, 5);
+                    let distance = DISTANCE_BASE[i] as usize + bits.bits(DISTANCE_EXTRA[i]);
+                    for _ in 0..len {
+                        out.push(out[out.len() - distance]);
+                    }
+                }
+            }
+        }
+        let end = 2 + bits.pos.div_ceil(8);
+        assert_eq!(zlib[end..], adler32(&out).to_be_bytes());
+        out
+    }
+
+    #[test]
+    fn checksums_match_known_values() {
+        // given
+        let data = b"123456789";
+
+        // when
+        let crc = crc32(data);
+        let adler = adler32(data);
+
+        // then
+        assert_eq!(crc, 0xcbf43926);
+        assert_eq!(adler, 0x091e01de);
+    }
+
+    #[test]
+    fn encode_png_writes_all_chunks() {
+        // given
+        let palette = [(0, 0, 0), (255, 255, 255)];
+        let pixels = [0, 1, 1, 0, 1, 0];
+
+        // when
+        let png = encode_png(3, 2, &palette, &pixels);
+
+        // then
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x00\x03"));
+        let kinds: Vec<&[u8]> = png
+            .windows(4)
+            .filter(|w| matches!(*w, b"IHDR" | b"PLTE" | b"IDAT" | b"IEND"))
+            .collect();
+        assert_eq!(kinds, [b"IHDR", b"PLTE", b"IDAT", b"IEND"]);
+        assert!(png.ends_with(&crc32(b"IEND").to_be_bytes()));
+    }
+
+    #[test]
+    fn idat_can_be_inflated() {
+        // given
+        let (width, height) = (300, 200);
+        let palette: Vec<Rgb> = (0..=255).map(|i| (i, i, i)).collect();
+        let mut seed = 14u32;
+        // noise with repeated runs, for literals as well as short and long matches
+        let pixels: Vec<u8> = (0..width * height)
+            .map(|i| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                if (i / 500) % 3 == 0 {
+                    (seed >> 16) as u8
+                } else {
+                    (i % 7 * 40) as u8
+                }
+            })
+            .collect();
+
+        // when
+        let png = encode_png(width, height, &palette, &pixels);
+
+        // then
+        let start = png
+            .windows(4)
+            .position(|w| w == b"IDAT")
+            .expect("expected an IDAT chunk");
+        let len = u32::from_be_bytes([
+            png[start - 4],
+            png[start - 3],
+            png[start - 2],
+            png[start - 1],
+        ]);
+        let idat = &png[start + 4..start + 4 + len as usize];
+        let filtered: Vec<u8> = pixels
+            .chunks(width)
+            .flat_map(|row| [&[0][..], row].concat())
+            .collect();
+        assert_eq!(inflate(idat), filtered);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{parse_keys, Key, Player, Simulation};
+use aoc_common::Error;
+use std::fs::File;
+use std::io::{stdout, Read, Write};
+use std::process::{Command, Stdio};
+
+const HELP: &str = "space play  b rewind  ←/→ step  +/- speed  wasd scroll  g start  q quit";
+const MAX_SPEED: usize = 1024;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Playing {
+    Paused,
+    Forward,
+    Backward,
+}
+
+struct View<S> {
+    player: Player<S>,
+    playing: Playing,
+    // steps per tick of a tenth of a second
+    speed: usize,
+    // lines and columns the user scrolled away from the top left corner or the focus
+    scroll: (i64, i64),
+}
+
+impl<S: Simulation> View<S> {
+    // returns `false` if the user wants to quit
+    fn handle(&mut self, key: Key) -> bool {
+        match key {
+            Key::Char('q') | Key::Char('\u{3}') | Key::Escape => return false,
+            Key::Char(' ') => self.toggle(Playing::Forward),
+            Key::Char('b') => self.toggle(Playing::Backward),
+            Key::Right | Key::Char('l') => {
+                self.playing = Playing::Paused;
+                self.player.forward();
+            }
+            Key::Left | Key::Char('h') => {
+                self.playing = Playing::Paused;
+                self.player.back();
+            }
+            Key::Char('g') => {
+                self.playing = Playing::Paused;
+                self.player.seek(0);
+            }
+            Key::Char('+') | Key::Char('=') => self.speed = (self.speed * 2).min(MAX_SPEED),
+            Key::Char('-') => self.speed = (self.speed / 2).max(1),
+            Key::Up | Key::Char('w') => self.scroll.0 -= 5,
+            Key::Down | Key::Char('s') => self.scroll.0 += 5,
+            Key::Char('a') => self.scroll.1 -= 10,
+            Key::Char('d') => self.scroll.1 += 10,
+            _ => {}
+        }
+        true
+    }
+
+    fn toggle(&mut self, direction: Playing) {
+        self.playing = if self.playing == direction {
+            Playing::Paused
+        } else {
+            direction
+        };
+    }
+
+    fn tick(&mut self) {
+        for _ in 0..self.speed {
+            let moved = match self.playing {
+                Playing::Paused => return,
+                Playing::Forward => self.player.forward(),
+                Playing::Backward => self.player.back(),
+            };
+            if !moved {
+                self.playing = Playing::Paused;
+            }
+        }
+    }
+
+    // the whole screen, with the visible part of the simulation and a status line below it
+    fn frame(&self, rows: usize, cols: usize) -> String {
+        let simulation = self.player.current();
+        let rendered = simulation.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+        let height = rows.saturating_sub(1);
+        let (focus_line, focus_col) = match simulation.focus() {
+            Some((line, col)) => (
+                line as i64 - height as i64 / 2,
+                col as i64 - cols as i64 / 2,
+            ),
+            None => (0, 0),
+        };
+        let top = (focus_line + self.scroll.0).max(0) as usize;
+        let left = (focus_col + self.scroll.1).max(0) as usize;
+
+        let mut s = String::from("\x1b[H");
+        for row in 0..height {
+            let line = lines.get(top + row).copied().unwrap_or("");
+            let mut colour = None;
+            for c in line.chars().skip(left).take(cols) {
+                let c_colour = simulation.colour(c);
+                if c_colour != colour {
+                    s += &match c_colour {
+                        Some((r, g, b)) => format!("\x1b[38;2;{r};{g};{b}m"),
+                        None => "\x1b[39m".to_owned(),
+                    };
+                    colour = c_colour;
+                }
+                s.push(c);
+            }
+            s += "\x1b[0m\x1b[K\r\n";
+        }
+
+        let playing = match self.playing {
+            Playing::Paused => "paused",
+            Playing::Forward => "playing",
+            Playing::Backward => "rewinding",
+        };
+        let end = if self.player.is_finished() {
+            " (end)"
+        } else {
+            ""
+        };
+        let status = format!(
+            " step {}{end} | {} | {playing} at {}x | {HELP}",
+            self.player.step(),
+            simulation.status(),
+            self.speed,
+        );
+        let status: String = status.chars().take(cols).collect();
+        s += &format!("\x1b[7m{status:cols$}\x1b[0m");
+        s
+    }
+}
+
+/// Show the simulation in the terminal until the user quits. Keys are read from the terminal
+/// itself, so the puzzle input may well come from stdin.
+pub fn run<S: Simulation>(simulation: S) -> Result<(), Error> {
+    let mut tty = File::options().read(true).write(true).open("/dev/tty")?;
+    let raw_mode = RawMode::enable(&tty)?;
+    let (rows, cols) = raw_mode.size.unwrap_or((24, 80));
+    let mut view = View {
+        player: Player::new(simulation),
+        playing: Playing::Paused,
+        speed: 1,
+        scroll: (0, 0),
+    };
+    let mut buffer = [0u8; 64];
+    loop {
+        let mut out = stdout().lock();
+        out.write_all(view.frame(rows, cols).as_bytes())?;
+        out.flush()?;
+        // returns after a tenth of a second without any key
+        let n = tty.read(&mut buffer)?;
+        for key in parse_keys(&buffer[..n]) {
+            if !view.handle(key) {
+                return Ok(());
+            }
+        }
+        view.tick();
+    }
+}
+
+// The terminal in raw mode, on the alternate screen and without cursor. Everything is restored
+// when dropped, even if the simulation panics.
+struct RawMode {
+    tty: File,
+    saved: String,
+    // rows and columns
+    size: Option<(usize, usize)>,
+}
+
+impl RawMode {
+    fn enable(tty: &File) -> Result<RawMode, Error> {
+        let saved = stty(tty, &["-g"])?;
+        // no line buffering, no echo, no signals, and reads that give up after a tenth of a second
+        stty(tty, &["-icanon", "-echo", "-isig", "min", "0", "time", "1"])?;
+        let size = stty(tty, &["size"]).ok().and_then(|size| {
+            let (rows, cols) = size.trim().split_once(' ')?;
+            Some((rows.parse().ok()?, cols.parse().ok()?))
+        });
+        let mut tty = tty.try_clone()?;
+        tty.write_all(b"\x1b[?1049h\x1b[?25l\x1b[2J")?;
+        Ok(RawMode {
+            tty,
+            saved: saved.trim().to_owned(),
+            size,
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // nothing sensible to do if restoring fails
+        let _ = self.tty.write_all(b"\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = stty(&self.tty, &[&self.saved]);
+    }
+}
+
+fn stty(tty: &File, args: &[&str]) -> Result<String, Error> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::from(tty.try_clone()?))
+        .output()?;
+    if !output.status.success() {
+        return Err(Error::semantic(format!(
+            "stty failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,162 @@
+use crate::{fmt_duration, read_day_input};
+use aoc_common::{bench, take_option, Baseline, BenchResult, Error, ErrorKind, Phase};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Options {
+    pub iterations: usize,
+    pub save: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    /// in percent
+    pub threshold: f64,
+}
+
+impl Options {
+    /// Take the bench options out of the command line arguments.
+    pub fn from_args(args: &mut Vec<String>) -> Result<Options, Error> {
+        let iterations = match take_option(args, "--iterations")? {
+            Some(n) => n.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                Error::semantic(format!("'{n}' is not a valid number of iterations"))
+            })?,
+            None => DEFAULT_ITERATIONS,
+        };
+        let threshold = match take_option(args, "--threshold")? {
+            Some(pct) => pct
+                .parse()
+                .map_err(|_| Error::semantic(format!("'{pct}' is not a valid threshold")))?,
+            None => DEFAULT_THRESHOLD,
+        };
+        Ok(Options {
+            iterations,
+            save: take_option(args, "--save")?.map(PathBuf::from),
+            compare: take_option(args, "--compare")?.map(PathBuf::from),
+            threshold,
+        })
+    }
+}
+
+pub fn bench_single(day: u8, filename: &Path, options: &Options) -> Result<(), Error> {
+    let content = read_to_string(filename).map_err(|e| {
+        Error::new(
+            ErrorKind::Io,
+            format!("Unable to read '{}': {e}", filename.display()),
+        )
+    })?;
+    let result = for_day!(day, bench(&content, options.iterations))?;
+    report(&[result], options)
+}
+
+pub fn bench_all(dir: &Path, options: &Options) -> Result<(), Error> {
+    let mut results = Vec::with_capacity(25);
+    for day in 1..=25 {
+        let Ok(content) = read_day_input(dir, day) else {
+            continue;
+        };
+        match for_day!(day, bench(&content, options.iterations)) {
+            Ok(result) => results.push(result),
+            Err(e) => println!("Day {day}: unable to benchmark:\n{e}\n"),
+        }
+    }
+    report(&results, options)
+}
+
+// print the results, compare them to and store them in the baselines if requested
+fn report(results: &[BenchResult], options: &Options) -> Result<(), Error> {
+    let compare = match &options.compare {
+        Some(path) => Some(load_baseline(path)?),
+        None => None,
+    };
+
+    let mut regressions = 0;
+    for result in results {
+        println!("Day {} ({} iterations):", result.day, result.iterations);
+        for phase in Phase::ALL {
+            let stats = result.stats(phase);
+            print!(
+                "  {phase}  min {:>9}  median {:>9}  p95 {:>9}",
+                fmt_duration(stats.min),
+                fmt_duration(stats.median),
+                fmt_duration(stats.p95)
+            );
+            match compare.as_ref().map(|b| b.get(result.day, phase)) {
+                Some(Some(baseline)) => {
+                    let change = stats.change_from(baseline) * 100.0;
+                    print!("  {change:+.1}% vs baseline");
+                    if change > options.threshold {
+                        regressions += 1;
+                        print!("  REGRESSION");
+                    }
+                }
+                Some(None) => print!("  not in baseline"),
+                None => {}
+            }
+            println!();
+        }
+    }
+
+    if let Some(path) = &options.save {
+        let mut baseline = load_baseline(path)?;
+        for result in results {
+            baseline.record(result);
+        }
+        baseline.save(path).map_err(|e| {
+            Error::new(
+                e.kind,
+                format!(
+                    "Unable to save baseline '{}': {}",
+                    path.display(),
+                    e.message
+                ),
+            )
+        })?;
+    }
+
+    if regressions > 0 {
+        Err(Error::semantic(format!(
+            "{regressions} phase(s) got more than {}% slower than the baseline",
+            options.threshold
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+// the baseline in `path`, with the path in the message of any error
+fn load_baseline(path: &Path) -> Result<Baseline, Error> {
+    Baseline::load(path).map_err(|e| {
+        let message = format!("Unable to load baseline '{}':\n{e}", path.display());
+        Error::new(e.kind, message)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn options_are_taken_from_args() {
+        // given
+        let mut args: Vec<String> = ["bench", "--iterations", "5", "16", "--save", "base.txt"]
+            .map(String::from)
+            .to_vec();
+
+        // when
+        let options = Options::from_args(&mut args);
+
+        // then
+        assert_eq!(
+            options,
+            Ok(Options {
+                iterations: 5,
+                save: Some(PathBuf::from("base.txt")),
+                compare: None,
+                threshold: DEFAULT_THRESHOLD,
+            })
+        );
+        assert_eq!(args, ["bench", "16"]);
+    }
+}
@@ -0,0 +1,59 @@
+use aoc_common::{fuzz, take_option, Error, GenerateOptions, Panic, Solution};
+
+const DEFAULT_RUNS: usize = 10_000;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Options {
+    pub runs: usize,
+}
+
+impl Options {
+    /// Take the fuzz options out of the command line arguments.
+    pub fn from_args(args: &mut Vec<String>) -> Result<Options, Error> {
+        let runs = match take_option(args, "--runs")? {
+            Some(n) => n
+                .parse()
+                .map_err(|_| Error::semantic(format!("'{n}' is not a valid number of runs")))?,
+            None => DEFAULT_RUNS,
+        };
+        Ok(Options { runs })
+    }
+}
+
+pub fn fuzz_days(days: &[u8], seed: u64, options: &Options) -> Result<(), Error> {
+    let mut failed = 0;
+    for &day in days {
+        match for_day!(day, fuzz_parse(seed, options.runs)) {
+            Ok(None) => println!("Day {day}: no panics in {} runs", options.runs),
+            Ok(Some(panic)) => {
+                println!("Day {day}: parser {panic}");
+                failed += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if failed > 0 {
+        Err(Error::semantic(format!("{failed} parser(s) panicked")))
+    } else {
+        Ok(())
+    }
+}
+
+// the parser of a day, fed with mutations of generated inputs
+fn fuzz_parse<S: Solution>(seed: u64, runs: usize) -> Result<Option<Panic>, Error> {
+    let corpus: Vec<String> = [1, 3, 10]
+        .into_iter()
+        .map(|size| {
+            S::generate(&GenerateOptions {
+                size: Some(size),
+                seed,
+                difficulty: Default::default(),
+            })
+        })
+        .collect();
+    let corpus: Vec<&str> = corpus.iter().map(|s| s.as_str()).collect();
+    Ok(fuzz(seed, runs, &corpus, |input| {
+        let _ = S::parse(input);
+    })
+    .err())
+}
@@ -0,0 +1,435 @@
+use aoc_common::{
+    error_json, inputs_from_args, solve, split_args, take_option, Answer, DayResult, Error, Format,
+    GenerateOptions, Input, Solution,
+};
+use std::env;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+
+/// Call the generic function `$f` with the solver of the given day, e.g.
+/// `for_day!(day, solve(input))` calls `solve::<day_NN::Solver>(input)`.
+macro_rules! for_day {
+    ($day:expr, $f:ident($($arg:expr),*)) => {
+        match $day {
+            1 => $f::<day_01::Solver>($($arg),*),
+            2 => $f::<day_02::Solver>($($arg),*),
+            3 => $f::<day_03::Solver>($($arg),*),
+            4 => $f::<day_04::Solver>($($arg),*),
+            5 => $f::<day_05::Solver>($($arg),*),
+            6 => $f::<day_06::Solver>($($arg),*),
+            7 => $f::<day_07::Solver>($($arg),*),
+            8 => $f::<day_08::Solver>($($arg),*),
+            9 => $f::<day_09::Solver>($($arg),*),
+            10 => $f::<day_10::Solver>($($arg),*),
+            11 => $f::<day_11::Solver>($($arg),*),
+            12 => $f::<day_12::Solver>($($arg),*),
+            13 => $f::<day_13::Solver>($($arg),*),
+            14 => $f::<day_14::Solver>($($arg),*),
+            15 => $f::<day_15::Solver>($($arg),*),
+            16 => $f::<day_16::Solver>($($arg),*),
+            17 => $f::<day_17::Solver>($($arg),*),
+            18 => $f::<day_18::Solver>($($arg),*),
+            19 => $f::<day_19::Solver>($($arg),*),
+            20 => $f::<day_20::Solver>($($arg),*),
+            21 => $f::<day_21::Solver>($($arg),*),
+            22 => $f::<day_22::Solver>($($arg),*),
+            23 => $f::<day_23::Solver>($($arg),*),
+            24 => $f::<day_24::Solver>($($arg),*),
+            25 => $f::<day_25::Solver>($($arg),*),
+            day => Err(aoc_common::Error::semantic(format!(
+                "There is no puzzle for day {day}"
+            ))),
+        }
+    };
+}
+
+mod bench;
+mod fuzz;
+mod verify;
+
+const USAGE: &str = r#"Usage:
+  aoc run <day> [input file...] [--format text|json]
+  aoc run all [input directory] [--format text|json]
+  aoc bench <day> <input file> [bench options]
+  aoc bench all [input directory] [bench options]
+  aoc verify <day> <input file>
+  aoc verify all [input directory]
+  aoc generate <day> [--size <n>] [--seed <n>] [--difficulty easy|normal|hard]
+  aoc fuzz <day|all> [--runs <n>] [--seed <n>]
+
+Without input file or with '-', the input is read from stdin. Several input files are solved one
+after another, with the results labelled by file.
+
+When running all days, the input for each day is read from '<input directory>/day-NN.txt'
+(default directory: 'inputs'). Days without input file are skipped.
+
+With '--format json', each day is printed as a JSON object with the answers and timings in
+microseconds. Running all days prints an array of these objects.
+
+Bench options:
+  --iterations <n>     how often parsing and each part are run (default: 10)
+  --save <file>        store the results as baseline in <file>, keeping other days in it
+  --compare <file>     compare the medians against the baseline in <file>
+  --threshold <pct>    slowdown in percent that counts as regression (default: 10)
+
+When comparing, the exit code is non-zero if any phase got slower than the threshold.
+
+Verifying compares the answers with the known answers stored next to the input file, e.g. in
+'day-07.answers.toml' for 'day-07.txt':
+
+  part1 = 1432936
+  part2 = 272298
+
+The exit code is non-zero if any answer is wrong. Missing answers are reported, but accepted.
+
+Generating prints a random input for the day that its solver accepts. What the size means
+depends on the day (lines, width of a map, number of valves, …), without size the input is about
+as large as a real one. Without seed, a random one is used and printed to stderr, so the input
+can be generated again.
+
+Fuzzing feeds the parser of a day with random changes of generated inputs (default: 10000 runs)
+and reports the smallest input found that makes it panic instead of returning an error."#;
+
+const DEFAULT_INPUT_DIR: &str = "inputs";
+
+fn main() -> ExitCode {
+    // print errors ourselves, returning them from main would use their Debug representation and
+    // mess up the multi-line parse errors
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), Error> {
+    let line = args.join(" ");
+    let (mut args, format) = split_args(args)?;
+    let command = if args.is_empty() {
+        String::new()
+    } else {
+        args.remove(0)
+    };
+    if format == Format::Json && command != "run" {
+        return Err(Error::semantic(format!(
+            "only 'run' prints JSON, leave out --format json for '{command}'"
+        )));
+    }
+    // each command takes its own options and rejects all others
+    let bench_options = match command.as_str() {
+        "bench" => Some(bench::Options::from_args(&mut args)?),
+        _ => None,
+    };
+    let generate_options = match command.as_str() {
+        "generate" => Some(generate_options_from_args(&mut args)?),
+        _ => None,
+    };
+    let fuzz_options = match command.as_str() {
+        "fuzz" => Some((
+            seed_from_args(&mut args)?,
+            fuzz::Options::from_args(&mut args)?,
+        )),
+        _ => None,
+    };
+    if let Some(option) = args.iter().find(|arg| arg.starts_with('-') && *arg != "-") {
+        return Err(Error::semantic(format!(
+            "unknown option '{option}' for '{command}'"
+        )));
+    }
+
+    let bench_options = || bench_options.as_ref().expect("bench has its options");
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match (command.as_str(), &args[..]) {
+        ("run", ["all"]) => run_all(Path::new(DEFAULT_INPUT_DIR), format),
+        ("run", ["all", dir]) => run_all(Path::new(dir), format),
+        ("run", [day, files @ ..]) => {
+            let files: Vec<String> = files.iter().map(|f| f.to_string()).collect();
+            run_inputs(parse_day(day)?, &inputs_from_args(&files), format)
+        }
+        ("bench", ["all"]) => bench::bench_all(Path::new(DEFAULT_INPUT_DIR), bench_options()),
+        ("bench", ["all", dir]) => bench::bench_all(Path::new(dir), bench_options()),
+        ("bench", [day, filename]) => {
+            bench::bench_single(parse_day(day)?, Path::new(filename), bench_options())
+        }
+        ("verify", ["all"]) => verify::verify_all(Path::new(DEFAULT_INPUT_DIR)),
+        ("verify", ["all", dir]) => verify::verify_all(Path::new(dir)),
+        ("verify", [day, filename]) => verify::verify_single(parse_day(day)?, Path::new(filename)),
+        ("generate", [day]) => {
+            let options = generate_options.expect("generate has its options");
+            eprintln!("seed {}", options.seed);
+            print!("{}", generate_day(parse_day(day)?, &options)?);
+            Ok(())
+        }
+        ("fuzz", [day]) => {
+            let (seed, options) = fuzz_options.expect("fuzz has its options");
+            eprintln!("seed {seed}");
+            let days: Vec<u8> = match *day {
+                "all" => (1..=25).collect(),
+                day => vec![parse_day(day)?],
+            };
+            fuzz::fuzz_days(&days, seed, &options)
+        }
+        _ => Err(usage(&line)),
+    }
+}
+
+// print how to use `aoc`, the error is about the command line that was given
+fn usage(line: &str) -> Error {
+    eprintln!("{USAGE}\n");
+    Error::semantic(format!("unknown command '{line}'"))
+}
+
+/// Remove the options for `generate` from the arguments.
+fn generate_options_from_args(args: &mut Vec<String>) -> Result<GenerateOptions, Error> {
+    let size = take_option(args, "--size")?
+        .map(|v| v.parse::<usize>())
+        .transpose()
+        .map_err(|e| Error::semantic(format!("invalid size: {e}")))?;
+    let seed = seed_from_args(args)?;
+    let difficulty = match take_option(args, "--difficulty")? {
+        Some(v) => v.parse()?,
+        None => Default::default(),
+    };
+    Ok(GenerateOptions {
+        size,
+        seed,
+        difficulty,
+    })
+}
+
+/// Remove `--seed <n>` from the arguments. Without seed, one is made up from the current time.
+fn seed_from_args(args: &mut Vec<String>) -> Result<u64, Error> {
+    match take_option(args, "--seed")? {
+        Some(v) => v
+            .parse::<u64>()
+            .map_err(|e| Error::semantic(format!("invalid seed: {e}"))),
+        None => Ok(SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)),
+    }
+}
+
+/// The input for `day` in `dir`, if there is one.
+fn read_day_input(dir: &Path, day: u8) -> Result<String, PathBuf> {
+    let filename: PathBuf = dir.join(format!("day-{day:02}.txt"));
+    read_to_string(&filename).map_err(|_| filename)
+}
+
+fn parse_day(day: &str) -> Result<u8, Error> {
+    day.parse::<u8>()
+        .ok()
+        .filter(|d| (1..=25).contains(d))
+        .ok_or_else(|| {
+            Error::semantic(format!(
+                "'{day}' is not a valid day, expected a number from 1 to 25"
+            ))
+        })
+}
+
+fn run_inputs(day: u8, inputs: &[Input], format: Format) -> Result<(), Error> {
+    // with several inputs, label the results and don't stop at the first failing one
+    let labelled = inputs.len() > 1;
+    let mut failed = 0;
+    for input in inputs {
+        let label = input.to_string();
+        let label_json = labelled.then_some(label.as_str());
+        if labelled && format == Format::Text {
+            println!("==> {label} <==");
+        }
+        match input.read().and_then(|content| solve_day(day, &content)) {
+            Ok(result) => match format {
+                Format::Text => print_result(&result),
+                Format::Json => println!("{}", result.to_labelled_json(label_json)),
+            },
+            Err(e) => {
+                if format == Format::Json {
+                    println!("{}", error_json(day, label_json, &e));
+                }
+                if !labelled {
+                    return Err(e);
+                }
+                eprintln!("{label}: {e}");
+                failed += 1;
+            }
+        }
+        if labelled && format == Format::Text {
+            println!();
+        }
+    }
+    if failed > 0 {
+        Err(Error::semantic(format!(
+            "{failed} of {} inputs failed",
+            inputs.len()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn run_all(dir: &Path, format: Format) -> Result<(), Error> {
+    let mut total = Duration::ZERO;
+    let mut json_objects: Vec<String> = Vec::with_capacity(25);
+    for day in 1..=25 {
+        let content = match read_day_input(dir, day) {
+            Ok(content) => content,
+            Err(filename) => {
+                // skipped days are simply missing from the JSON output
+                if format == Format::Text {
+                    println!(
+                        "Day {day}: no input at '{}', skipping\n",
+                        filename.display()
+                    );
+                }
+                continue;
+            }
+        };
+        let result = solve_day(day, &content);
+        if format == Format::Json {
+            json_objects.push(match result {
+                Ok(result) => result.to_json(),
+                Err(e) => error_json(day, None, &e),
+            });
+            continue;
+        }
+        match result {
+            Ok(result) => {
+                total += result.timings.total();
+                print_result(&result);
+            }
+            Err(e) => println!("Day {day}: unable to parse input:\n{e}"),
+        }
+        println!();
+    }
+    match format {
+        Format::Text => println!("Total time: {}", fmt_duration(total)),
+        Format::Json => println!("[{}]", json_objects.join(",\n")),
+    }
+    Ok(())
+}
+
+fn solve_day(day: u8, input: &str) -> Result<DayResult, Error> {
+    for_day!(day, solve(input))
+}
+
+fn generate<S: Solution>(options: &GenerateOptions) -> Result<String, Error> {
+    Ok(S::generate(options))
+}
+
+fn generate_day(day: u8, options: &GenerateOptions) -> Result<String, Error> {
+    for_day!(day, generate(options))
+}
+
+fn print_result(result: &DayResult) {
+    println!("Day {}:", result.day);
+    print_part(1, &result.part1, result.timings.part1);
+    print_part(2, &result.part2, result.timings.part2);
+    println!(
+        "  parsing took {}, {} in total",
+        fmt_duration(result.timings.parse),
+        fmt_duration(result.timings.total())
+    );
+}
+
+fn print_part(part: u8, answer: &Result<Answer, Error>, time: Duration) {
+    match answer {
+        // multi-line answers (like the CRT screen on day 10) get their own lines
+        Ok(Answer::Text(text)) if text.contains('\n') => {
+            println!("  Part {part} ({}):", fmt_duration(time));
+            for line in text.lines() {
+                println!("    {line}");
+            }
+        }
+        Ok(answer) => println!("  Part {part}: {answer} ({})", fmt_duration(time)),
+        Err(e) => println!("  Part {part} failed: {e} ({})", fmt_duration(time)),
+    }
+}
+
+fn fmt_duration(d: Duration) -> String {
+    if d.as_secs() > 0 {
+        format!("{:.2}s", d.as_secs_f64())
+    } else if d.as_millis() > 0 {
+        format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+    } else if d.as_micros() > 0 {
+        format!("{}µs", d.as_micros())
+    } else {
+        format!("{}ns", d.as_nanos())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::Difficulty;
+
+    #[test]
+    fn parse_day_accepts_only_advent_days() {
+        assert_eq!(parse_day("1"), Ok(1));
+        assert_eq!(parse_day("25"), Ok(25));
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("26").is_err());
+        assert!(parse_day("all").is_err());
+    }
+
+    #[test]
+    fn options_of_other_commands_are_rejected() {
+        // given
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<String>>();
+
+        // when
+        let run_with_iterations = run(args("run 1 --iterations 5"));
+        let verify_with_seed = run(args("verify 3 input.txt --seed 7"));
+        let bench_as_json = run(args("bench all --format json"));
+
+        // then
+        let message = |result: Result<(), Error>| result.expect_err("expected an error").message;
+        assert_eq!(
+            message(run_with_iterations),
+            "unknown option '--iterations' for 'run'"
+        );
+        assert_eq!(
+            message(verify_with_seed),
+            "unknown option '--seed' for 'verify'"
+        );
+        assert!(message(bench_as_json).contains("only 'run' prints JSON"));
+    }
+
+    #[test]
+    fn solve_day_dispatches_to_the_right_day() {
+        // given
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+        // when
+        let result = solve_day(6, input);
+
+        // then
+        let result = result.expect("expected successful run");
+        assert_eq!(result.day, day_06::Solver::DAY);
+        assert_eq!(result.part1, Ok(Answer::Int(7)));
+        assert_eq!(result.part2, Ok(Answer::Int(19)));
+    }
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for day in 1..=25 {
+            // given
+            let options = GenerateOptions {
+                size: Some(5),
+                seed: u64::from(day),
+                difficulty: Difficulty::Easy,
+            };
+            let input = generate_day(day, &options).expect("expected a generated input");
+
+            // when
+            let result = solve_day(day, &input);
+
+            // then
+            let result = result.unwrap_or_else(|e| panic!("day {day}: {e}"));
+            assert!(result.part1.is_ok(), "day {day}: {:?}", result.part1);
+            assert!(result.part2.is_ok(), "day {day}: {:?}", result.part2);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{read_day_input, solve_day};
+use aoc_common::{Answer, Error, ErrorKind, ExpectedAnswers, Verdict};
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// How many parts passed, failed or could not be checked.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Summary {
+    fn count(&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Pass => self.passed += 1,
+            Verdict::Fail { .. } | Verdict::Error(_) => self.failed += 1,
+            Verdict::Missing => self.missing += 1,
+        }
+    }
+
+    // only wrong answers are an error, missing answers just can't be checked
+    fn into_result(self) -> Result<(), Error> {
+        println!(
+            "{} passed, {} failed, {} missing",
+            self.passed, self.failed, self.missing
+        );
+        if self.failed > 0 {
+            Err(Error::semantic(format!(
+                "{} part(s) failed verification",
+                self.failed
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+pub fn verify_single(day: u8, filename: &Path) -> Result<(), Error> {
+    let content = read_to_string(filename).map_err(|e| {
+        Error::new(
+            ErrorKind::Io,
+            format!("Unable to read '{}': {e}", filename.display()),
+        )
+    })?;
+    let mut summary = Summary::default();
+    verify_day(day, filename, &content, &mut summary)?;
+    summary.into_result()
+}
+
+pub fn verify_all(dir: &Path) -> Result<(), Error> {
+    let mut summary = Summary::default();
+    for day in 1..=25 {
+        let filename = dir.join(format!("day-{day:02}.txt"));
+        let Ok(content) = read_day_input(dir, day) else {
+            continue;
+        };
+        verify_day(day, &filename, &content, &mut summary)?;
+    }
+    summary.into_result()
+}
+
+fn verify_day(day: u8, filename: &Path, content: &str, summary: &mut Summary) -> Result<(), Error> {
+    let expected = ExpectedAnswers::load_for(filename).map_err(|e| {
+        let path = ExpectedAnswers::path_for(filename);
+        Error::new(
+            e.kind,
+            format!("Unable to load answers '{}':\n{e}", path.display()),
+        )
+    })?;
+    println!("Day {day} ({}):", filename.display());
+    let (part1, part2) = match solve_day(day, content) {
+        Ok(result) => (result.part1, result.part2),
+        // if we can't parse the input, both parts fail
+        Err(e) => (Err(e.clone()), Err(e)),
+    };
+    for (part, actual) in [(1, part1), (2, part2)] {
+        let verdict = Verdict::check(expected.get(part), &actual);
+        print_verdict(part, &verdict);
+        summary.count(&verdict);
+    }
+    Ok(())
+}
+
+fn print_verdict(part: u8, verdict: &Verdict) {
+    match verdict {
+        Verdict::Pass => println!("  Part {part}: pass"),
+        Verdict::Fail { expected, actual } => {
+            println!(
+                "  Part {part}: FAIL, expected {}, got {}",
+                one_line(expected),
+                one_line(actual)
+            )
+        }
+        Verdict::Missing => println!("  Part {part}: missing answer"),
+        Verdict::Error(e) => println!("  Part {part}: FAIL, {e}"),
+    }
+}
+
+// multi-line answers (the CRT screen on day 10) would mess up the report
+fn one_line(answer: &Answer) -> String {
+    answer.to_string().trim_end().replace('\n', "⏎")
+}
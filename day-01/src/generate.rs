@@ -0,0 +1,17 @@
+use aoc_common::GenerateOptions;
+
+/// Elves carrying a few snacks each. The size is the number of elves, the harder the puzzle, the
+/// more snacks each elf carries.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let max_snacks = options.pick(5, 15, 40);
+    let elves: Vec<String> = (0..options.size_or(250))
+        .map(|_| {
+            let snacks = rng.between(1, max_snacks);
+            (0..snacks)
+                .map(|_| format!("{}\n", rng.between(1000, 60000)))
+                .collect()
+        })
+        .collect();
+    elves.join("\n")
+}
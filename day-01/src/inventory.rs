@@ -0,0 +1,220 @@
+use aoc_common::{parse_number, Error};
+use std::fmt;
+use std::str::FromStr;
+
+// only the first warnings are kept, the others are just counted
+const MAX_WARNINGS: usize = 100;
+
+/// What separates the snacks of one elf from those of the next.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub enum Separator {
+    /// An empty line, as in the puzzle.
+    #[default]
+    Blank,
+    /// A line with exactly this text, e.g. `---`.
+    Line(String),
+}
+
+/// How the inventory is read.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ParseOptions {
+    pub separator: Separator,
+    /// Lines starting with this prefix are ignored.
+    pub comment: Option<String>,
+    /// Skip lines that are not numbers and runs of separators with a warning instead of failing,
+    /// and ignore whitespace around the numbers.
+    pub tolerant: bool,
+}
+
+/// A line that was skipped or looks suspicious.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Warning {
+    pub line: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}: '{}'", self.line, self.message, self.text)
+    }
+}
+
+/// The first warnings and how many there were in total.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Warnings {
+    pub first: Vec<Warning>,
+    pub count: usize,
+}
+
+impl Warnings {
+    fn push(&mut self, line: usize, text: &str, message: &str) {
+        self.count += 1;
+        if self.first.len() < MAX_WARNINGS {
+            self.first.push(Warning {
+                line,
+                text: text.to_owned(),
+                message: message.to_owned(),
+            });
+        }
+    }
+}
+
+pub(crate) enum Line<T> {
+    Snack(T),
+    Separator,
+    Skip,
+}
+
+// Tells for each line of the inventory what it is. Line endings are normalised, so `\r\n` works
+// as well as `\n`.
+#[derive(Clone, Debug)]
+pub(crate) struct LineParser {
+    options: ParseOptions,
+    line_number: usize,
+    // no snack since the last separator, or since the start
+    after_separator: bool,
+    pub(crate) warnings: Warnings,
+}
+
+impl LineParser {
+    pub(crate) fn new(options: ParseOptions) -> LineParser {
+        LineParser {
+            options,
+            line_number: 0,
+            after_separator: true,
+            warnings: Warnings::default(),
+        }
+    }
+
+    pub(crate) fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    pub(crate) fn parse<T>(&mut self, line: &str) -> Result<Line<T>, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.line_number += 1;
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let tolerant = self.options.tolerant;
+        let trimmed = line.trim();
+        if tolerant && !trimmed.is_empty() && trimmed.len() != line.len() {
+            self.warn(line, "whitespace around the line");
+        }
+        let line = if tolerant { trimmed } else { line };
+
+        if let Some(prefix) = &self.options.comment {
+            if line.starts_with(prefix.as_str()) {
+                return Ok(Line::Skip);
+            }
+        }
+
+        let is_separator = match &self.options.separator {
+            Separator::Blank => line.is_empty(),
+            Separator::Line(separator) => line == separator,
+        };
+        if is_separator && !self.after_separator {
+            self.after_separator = true;
+            return Ok(Line::Separator);
+        }
+        if is_separator {
+            return self.reject(line, Error::syntax(line, line, "separator without elf"));
+        }
+        if tolerant && line.is_empty() {
+            self.warn(line, "skipped empty line");
+            return Ok(Line::Skip);
+        }
+        match parse_number(line, line) {
+            Ok(snack) => {
+                self.after_separator = false;
+                Ok(Line::Snack(snack))
+            }
+            Err(e) => self.reject(line, e),
+        }
+    }
+
+    // skip the line in tolerant mode, fail otherwise
+    fn reject<T>(&mut self, line: &str, error: Error) -> Result<Line<T>, Error> {
+        if self.options.tolerant {
+            self.warn(line, &format!("skipped, {}", error.message));
+            Ok(Line::Skip)
+        } else {
+            Err(error.at_line(self.line_number))
+        }
+    }
+
+    fn warn(&mut self, line: &str, message: &str) {
+        self.warnings.push(self.line_number, line, message);
+    }
+}
+
+/// Parse the inventory of each elf as described by `options`, with warnings about the lines that
+/// were skipped or look suspicious.
+pub fn parse_input_with(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<(Vec<Vec<u32>>, Warnings), Error> {
+    let mut parser = LineParser::new(options.clone());
+    let mut elves = Vec::new();
+    let mut elf = Vec::new();
+    for line in input.lines() {
+        match parser.parse(line)? {
+            Line::Snack(snack) => elf.push(snack),
+            Line::Separator => elves.push(std::mem::take(&mut elf)),
+            Line::Skip => {}
+        }
+    }
+    if !elf.is_empty() {
+        elves.push(elf);
+    }
+    Ok((elves, parser.warnings))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_input_with_tolerates_messy_input() {
+        // given
+        let input = "# inventory\r\n1000\r\n2000 \r\n\r\n\r\n\r\nabc\r\n3000\r\n---\r\n4000\r\n";
+        let options = ParseOptions {
+            comment: Some("#".to_owned()),
+            tolerant: true,
+            ..Default::default()
+        };
+
+        // when
+        let result = parse_input_with(input, &options);
+
+        // then
+        let (elves, warnings) = result.expect("expected successful parsing");
+        assert_eq!(elves, [vec![1000, 2000], vec![3000, 4000]]);
+        let lines: Vec<usize> = warnings.first.iter().map(|w| w.line).collect();
+        assert_eq!(lines, [3, 5, 6, 7, 9]);
+        assert_eq!(warnings.count, 5);
+    }
+
+    #[test]
+    fn parse_input_with_uses_separator_lines() {
+        // given
+        let input = "1000\n---\n2000\n\n3000\n";
+        let options = ParseOptions {
+            separator: Separator::Line("---".to_owned()),
+            ..Default::default()
+        };
+
+        // when
+        let result = parse_input_with(input, &options);
+
+        // then
+        let error = result.expect_err("expected an error for the empty line");
+        assert_eq!(error.location.map(|l| l.line), Some(4));
+        let (elves, _) = parse_input_with("1000\n---\n2000\n3000\n", &options)
+            .expect("expected successful parsing");
+        assert_eq!(elves, [vec![1000], vec![2000, 3000]]);
+    }
+}
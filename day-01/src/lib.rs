@@ -0,0 +1,134 @@
+//! Day 1: Calorie Counting
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+
+mod generate;
+mod inventory;
+mod stream;
+
+pub use generate::generate_input;
+pub use inventory::{parse_input_with, ParseOptions, Separator, Warning, Warnings};
+pub use stream::{summarize, summarize_with, ElfTotals, Histogram, Statistics, Summary, TopN};
+
+/// Solves both parts of day 1 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 1;
+    // the totals of the three elves carrying the most, the largest first
+    type Input<'a> = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        let summary = summarize(input.as_bytes(), 3)?;
+        Ok(summary.top.into_iter().map(|(_, total)| total).collect())
+    }
+    fn part1(top_calories: &Self::Input<'_>) -> Result<Answer, Error> {
+        top_calories
+            .first()
+            .map(|cal| Answer::from(*cal))
+            .ok_or_else(|| Error::semantic("Apparently, no one joined the expedition"))
+    }
+    fn part2(top_calories: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(top_calories
+            .iter()
+            .map(|&cal| u128::from(cal))
+            .sum::<u128>()
+            .into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// Parse the inventory of each elf. Elves are separated by an empty line.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u32>>, Error> {
+    parse_input_with(input, &ParseOptions::default()).map(|(elves, _)| elves)
+}
+
+/// Total calories carried by each elf, in ascending order.
+pub fn sorted_calories(elf_backpacks: &[Vec<u32>]) -> Vec<u32> {
+    let mut elf_calories: Vec<u32> = elf_backpacks
+        .iter()
+        .map(|elf| elf.iter().sum::<u32>())
+        .collect();
+
+    // this may not be the fastest solution for part 2, but it sure is the easiest
+    elf_calories.sort_unstable();
+    elf_calories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::fuzz;
+
+    static EXAMPLE: &str = r#"1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000"#;
+
+    #[test]
+    fn parse_input_parses_example_input() {
+        // when
+        let result = parse_input(EXAMPLE);
+
+        // then
+        let elfs = result.expect("expected successful parsing");
+        assert_eq!(
+            &elfs,
+            &[
+                vec![1000, 2000, 3000],
+                vec![4000],
+                vec![5000, 6000],
+                vec![7000, 8000, 9000],
+                vec![10000]
+            ]
+        );
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = summarize(input.as_bytes(), 3);
+            let tolerant = ParseOptions {
+                tolerant: true,
+                ..Default::default()
+            };
+            let _ = parse_input_with(input, &tolerant);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn part2_sums_the_largest_totals_without_overflow() {
+        // given
+        let top_calories = vec![u64::MAX, u64::MAX, u64::MAX - 1];
+
+        // when
+        let result = Solver::part2(&top_calories);
+
+        // then
+        assert_eq!(result, Ok(Answer::Int(3 * u64::MAX as i128 - 1)));
+    }
+}
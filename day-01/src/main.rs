@@ -1,83 +1,104 @@
+use aoc_common::{inputs_from_args, run_main, split_text_args, take_option, Error};
+use day_01::{summarize, summarize_with, ParseOptions, Separator, Solver, Summary};
 use std::env;
-use std::fs::read_to_string;
-use std::path::Path;
 
-fn main() -> Result<(), String> {
-    let filename = env::args()
-        .nth(1)
-        .ok_or_else(|| "No file name given.".to_owned())?;
-    let content = read_to_string(Path::new(&filename)).map_err(|e| e.to_string())?;
+// the longest bar of the histogram
+const BAR_WIDTH: u64 = 50;
 
-    let elf_backpacks = parse_input(&content)?;
-    let mut elf_calories: Vec<u32> = elf_backpacks
-        .iter()
-        .map(|elf| elf.iter().sum::<u32>())
-        .collect();
-
-    // this may not be the fastest solution for part 2, but it sure is the easiest
-    elf_calories.sort_unstable();
-
-    if let Some(cal) = elf_calories.last() {
-        println!("The elf carrying the most carries {cal} calories");
-    } else {
-        println!("Apparently, no one joined the expedition");
+fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let top = take_option(&mut args, "--top")?
+        .map(|n| {
+            n.parse::<usize>()
+                .map_err(|_| Error::semantic(format!("'{n}' is not a number of elves")))
+        })
+        .transpose()?;
+    let options = parse_options_from_args(&mut args)?;
+    if top.is_none() && options == ParseOptions::default() {
+        return run_main::<Solver>(print_answers);
     }
 
-    let cal3: u32 = elf_calories.iter().rev().take(3).sum();
-    println!("The three most heavily loaded elfs carry {cal3} cal in total.");
-
+    // the inputs are read bit by bit, so they may be larger than the memory
+    let positional = split_text_args(args)?;
+    for (i, input) in inputs_from_args(&positional).iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let summary = summarize_with(input.open()?, top.unwrap_or(3), &options)?;
+        for warning in &summary.warnings.first {
+            eprintln!("warning: {warning}");
+        }
+        let more = summary.warnings.count - summary.warnings.first.len();
+        if more > 0 {
+            eprintln!("warning: {more} more lines were skipped or look suspicious");
+        }
+        match top {
+            Some(_) => print_summary(&summary),
+            None => print_top_three(&summary),
+        }
+    }
     Ok(())
 }
 
-fn parse_input(input: &str) -> Result<Vec<Vec<u32>>, String> {
-    input
-        .split("\n\n")
-        .map(|elf| {
-            elf.lines()
-                .map(|line| {
-                    line.parse::<u32>()
-                        .map_err(|e| format!("unable to parse line '{line}': {e}"))
-                })
-                .collect::<Result<Vec<u32>, String>>()
-        })
-        .collect()
+// `--tolerant`, `--separator <line>` and `--comment <prefix>`
+fn parse_options_from_args(args: &mut Vec<String>) -> Result<ParseOptions, Error> {
+    let separator = match take_option(args, "--separator")? {
+        Some(line) if !line.is_empty() => Separator::Line(line),
+        _ => Separator::Blank,
+    };
+    let comment = take_option(args, "--comment")?;
+    let tolerant = args.iter().any(|arg| arg == "--tolerant");
+    args.retain(|arg| arg != "--tolerant");
+    Ok(ParseOptions {
+        separator,
+        comment,
+        tolerant,
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static EXAMPLE: &str = r#"1000
-2000
-3000
-
-4000
+fn print_answers(content: &str) -> Result<(), Error> {
+    print_top_three(&summarize(content.as_bytes(), 3)?);
+    Ok(())
+}
 
-5000
-6000
+fn print_top_three(summary: &Summary) {
+    if let Some((_, cal)) = summary.top.first() {
+        println!("The elf carrying the most carries {cal} calories");
+    } else {
+        println!("Apparently, no one joined the expedition");
+    }
 
-7000
-8000
-9000
+    let cal3: u128 = summary.top.iter().map(|&(_, cal)| u128::from(cal)).sum();
+    println!("The three most heavily loaded elfs carry {cal3} cal in total.");
+}
 
-10000"#;
+fn print_summary(summary: &Summary) {
+    let statistics = &summary.statistics;
+    let (Some(mean), Some(median)) = (statistics.mean(), statistics.estimated_median()) else {
+        println!("Apparently, no one joined the expedition");
+        return;
+    };
 
-    #[test]
-    fn parse_input_parses_example_input() {
-        // when
-        let result = parse_input(EXAMPLE);
+    println!("The {} elves carrying the most:", summary.top.len());
+    for (rank, (elf, cal)) in summary.top.iter().enumerate() {
+        println!("{:>4}. elf {:<8} {cal:>10} calories", rank + 1, elf + 1);
+    }
+    let total: u128 = summary.top.iter().map(|&(_, cal)| u128::from(cal)).sum();
+    println!("Together they carry {total} calories.");
 
-        // then
-        let elfs = result.expect("expected successful parsing");
-        assert_eq!(
-            &elfs,
-            &[
-                vec![1000, 2000, 3000],
-                vec![4000],
-                vec![5000, 6000],
-                vec![7000, 8000, 9000],
-                vec![10000]
-            ]
-        );
+    println!();
+    println!(
+        "{} elves carry {mean:.0} calories on average, the median is about {median:.0} calories.",
+        statistics.count
+    );
+    let most = statistics
+        .histogram
+        .buckets()
+        .map(|(_, count)| count)
+        .max()
+        .unwrap_or(1);
+    for (range, count) in statistics.histogram.buckets() {
+        let bar = "#".repeat((count * BAR_WIDTH).div_ceil(most) as usize);
+        println!("{:>8} - {:>8} | {bar} {count}", range.start(), range.end());
     }
 }
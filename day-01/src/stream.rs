@@ -0,0 +1,312 @@
+use crate::inventory::{Line, LineParser};
+use crate::{ParseOptions, Warnings};
+use aoc_common::Error;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+// the histogram gets coarser once it has more buckets
+const MAX_BUCKETS: usize = 32;
+const INITIAL_BUCKET_WIDTH: u64 = 1000;
+
+/// The total calories of each elf, read line by line, so the inventory never has to fit into
+/// memory. Elves are separated by an empty line, unless the [`ParseOptions`] say otherwise.
+pub struct ElfTotals<R> {
+    reader: R,
+    line: String,
+    parser: LineParser,
+    finished: bool,
+}
+
+impl<R: BufRead> ElfTotals<R> {
+    pub fn new(reader: R) -> ElfTotals<R> {
+        ElfTotals::with_options(reader, ParseOptions::default())
+    }
+
+    pub fn with_options(reader: R, options: ParseOptions) -> ElfTotals<R> {
+        ElfTotals {
+            reader,
+            line: String::new(),
+            parser: LineParser::new(options),
+            finished: false,
+        }
+    }
+
+    /// Warnings about the lines read so far, there are only some in tolerant mode.
+    pub fn warnings(&self) -> &Warnings {
+        &self.parser.warnings
+    }
+}
+
+impl<R: BufRead> Iterator for ElfTotals<R> {
+    type Item = Result<u64, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let mut total: Option<u64> = None;
+        loop {
+            self.line.clear();
+            let line = match self.reader.read_line(&mut self.line) {
+                Err(e) => Err(e.into()),
+                Ok(0) => {
+                    self.finished = true;
+                    return total.map(Ok);
+                }
+                Ok(_) => self.parser.parse::<u64>(&self.line),
+            };
+            let snack = match line {
+                Ok(Line::Snack(snack)) => snack,
+                Ok(Line::Separator) => return total.map(Ok),
+                Ok(Line::Skip) => continue,
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            };
+            match snack.checked_add(total.unwrap_or(0)) {
+                Some(sum) => total = Some(sum),
+                None => {
+                    self.finished = true;
+                    let line = self.line.trim_end();
+                    let error = Error::syntax(line, line, "too many calories for one elf");
+                    return Some(Err(error.at_line(self.parser.line_number())));
+                }
+            }
+        }
+    }
+}
+
+/// The `n` largest totals seen so far, with the index of their elf. Only `n` of them are kept, in
+/// a heap with the smallest on top. Of elves with the same total, the first ones are kept.
+#[derive(Clone, Debug)]
+pub struct TopN {
+    n: usize,
+    heap: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+}
+
+impl TopN {
+    pub fn new(n: usize) -> TopN {
+        TopN {
+            n,
+            heap: BinaryHeap::with_capacity(n + 1),
+        }
+    }
+
+    pub fn push(&mut self, elf: usize, total: u64) {
+        self.heap.push(Reverse((total, Reverse(elf))));
+        if self.heap.len() > self.n {
+            self.heap.pop();
+        }
+    }
+
+    /// Elf indices and totals, the largest total first.
+    pub fn into_sorted(self) -> Vec<(usize, u64)> {
+        // sorting the reversed entries puts the largest total first
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(elf)))| (elf, total))
+            .collect()
+    }
+}
+
+/// The number of elves per range of totals. The buckets start small and double in width
+/// whenever there are too many of them, so the histogram never takes much memory.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Histogram {
+    width: u64,
+    // elves per bucket, bucket i holds totals from i * width to (i + 1) * width - 1
+    counts: BTreeMap<u64, u64>,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            width: INITIAL_BUCKET_WIDTH,
+            counts: BTreeMap::new(),
+        }
+    }
+}
+
+impl Histogram {
+    pub fn add(&mut self, total: u64) {
+        *self.counts.entry(total / self.width).or_default() += 1;
+        while self.counts.len() > MAX_BUCKETS {
+            self.width *= 2;
+            let mut merged = BTreeMap::new();
+            for (bucket, count) in &self.counts {
+                *merged.entry(bucket / 2).or_default() += count;
+            }
+            self.counts = merged;
+        }
+    }
+
+    /// The width of each bucket.
+    pub fn width(&self) -> u64 {
+        self.width
+    }
+
+    /// The ranges of totals with the number of elves in them, without empty ranges. The last
+    /// range ends at `u64::MAX` at the latest.
+    pub fn buckets(&self) -> impl Iterator<Item = (RangeInclusive<u64>, u64)> + '_ {
+        self.counts.iter().map(|(&bucket, &count)| {
+            // the start is at most a total that was added, so only the end can overflow
+            let start = bucket * self.width;
+            (start..=start.saturating_add(self.width - 1), count)
+        })
+    }
+}
+
+/// Count, mean and estimated median of the totals, and their histogram.
+#[derive(Clone, Default, Debug)]
+pub struct Statistics {
+    pub count: u64,
+    sum: u128,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub histogram: Histogram,
+}
+
+impl Statistics {
+    pub fn add(&mut self, total: u64) {
+        self.count += 1;
+        self.sum += total as u128;
+        self.min = Some(self.min.map_or(total, |min| min.min(total)));
+        self.max = Some(self.max.map_or(total, |max| max.max(total)));
+        self.histogram.add(total);
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum as f64 / self.count as f64)
+    }
+
+    /// An estimate of the median from the histogram, without keeping all totals: it is off by at
+    /// most the width of a bucket.
+    pub fn estimated_median(&self) -> Option<f64> {
+        let (min, max) = (self.min?, self.max?);
+        let rank = (self.count - 1) as f64 / 2.0;
+        let mut below = 0;
+        for (range, count) in self.histogram.buckets() {
+            if rank < (below + count) as f64 {
+                // assume the totals are spread evenly over the part of the bucket between the
+                // smallest and the largest total
+                let (start, end) = (*range.start().max(&min), *range.end().min(&max));
+                let fraction = (rank - below as f64 + 0.5) / count as f64;
+                return Some(start as f64 + fraction * ((end - start) as f64 + 1.0));
+            }
+            below += count;
+        }
+        None
+    }
+}
+
+/// What is known about the elves after reading the inventory once.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    /// Elf indices (counting from 0) and totals of the elves carrying the most, the largest first.
+    pub top: Vec<(usize, u64)>,
+    pub statistics: Statistics,
+    pub warnings: Warnings,
+}
+
+/// Read the inventory once and keep only the `n` elves carrying the most and the statistics.
+pub fn summarize(reader: impl BufRead, n: usize) -> Result<Summary, Error> {
+    summarize_with(reader, n, &ParseOptions::default())
+}
+
+/// Like [`summarize`], but the inventory is read as described by `options`.
+pub fn summarize_with(
+    reader: impl BufRead,
+    n: usize,
+    options: &ParseOptions,
+) -> Result<Summary, Error> {
+    let mut top = TopN::new(n);
+    let mut statistics = Statistics::default();
+    let mut totals = ElfTotals::with_options(reader, options.clone());
+    for (elf, total) in totals.by_ref().enumerate() {
+        let total = total?;
+        top.push(elf, total);
+        statistics.add(total);
+    }
+    Ok(Summary {
+        top: top.into_sorted(),
+        statistics,
+        warnings: totals.warnings().clone(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summarize_finds_the_top_elves() {
+        // given
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+        // when
+        let summary = summarize(input.as_bytes(), 3).expect("expected successful parsing");
+
+        // then
+        assert_eq!(summary.top, [(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(summary.statistics.count, 5);
+        assert_eq!(summary.statistics.mean(), Some(11000.0));
+        let statistics = &summary.statistics;
+        let median = statistics.estimated_median().expect("expected a median");
+        assert!((median - 10000.0).abs() <= statistics.histogram.width() as f64);
+    }
+
+    #[test]
+    fn elf_totals_reports_the_line_of_errors() {
+        // given
+        let input = "1000\n\n2000\nabc\n";
+
+        // when
+        let totals: Vec<Result<u64, Error>> = ElfTotals::new(input.as_bytes()).collect();
+
+        // then
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0], Ok(1000));
+        let error = totals[1].clone().expect_err("expected an error");
+        assert_eq!(error.location.map(|l| l.line), Some(4));
+    }
+
+    #[test]
+    fn histogram_merges_buckets() {
+        // given
+        let mut histogram = Histogram::default();
+
+        // when
+        for total in (0..100).map(|i| i * 1000) {
+            histogram.add(total);
+        }
+
+        // then
+        let buckets: Vec<(RangeInclusive<u64>, u64)> = histogram.buckets().collect();
+        assert_eq!(buckets.len(), 25);
+        assert_eq!(buckets[0], (0..=3999, 4));
+        assert_eq!(buckets.iter().map(|(_, count)| count).sum::<u64>(), 100);
+    }
+
+    #[test]
+    fn statistics_cope_with_the_largest_total() {
+        // given
+        let mut statistics = Statistics::default();
+
+        // when
+        for total in [u64::MAX, u64::MAX - 1, u64::MAX] {
+            statistics.add(total);
+        }
+
+        // then
+        let buckets: Vec<(RangeInclusive<u64>, u64)> = statistics.histogram.buckets().collect();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(*buckets[0].0.end(), u64::MAX);
+        let median = statistics.estimated_median().expect("expected a median");
+        assert!(median >= *buckets[0].0.start() as f64);
+        assert!(median <= u64::MAX as f64);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::Outcome;
+use aoc_common::{parse_lines, Error};
+
+/// A move of a [`Game`], as index into its list of moves.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Move(pub usize);
+
+/// Points for the move we choose and for the outcome of a round.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Scoring {
+    /// Points for each move, in the order of the moves of the game.
+    pub moves: Vec<u32>,
+    pub loss: u32,
+    pub draw: u32,
+    pub win: u32,
+}
+
+impl Scoring {
+    /// As in the puzzle: 1 point for the first move, 2 for the second, … and 0, 3 or 6 points for
+    /// a loss, draw or win.
+    pub fn standard(moves: usize) -> Scoring {
+        Scoring {
+            moves: (1..=moves as u32).collect(),
+            loss: Outcome::Loss.score(),
+            draw: Outcome::Draw.score(),
+            win: Outcome::Win.score(),
+        }
+    }
+
+    pub fn outcome(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Loss => self.loss,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+/// The letters of the strategy guide.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Encoding {
+    /// What the opponent plays, for each move of the game.
+    pub opponent: Vec<String>,
+    /// What we play, for each move of the game.
+    pub response: Vec<String>,
+    /// The outcome we should go for: loss, draw and win.
+    pub outcomes: [String; 3],
+}
+
+impl Encoding {
+    /// As in the puzzle: the opponent's moves are `A`, `B`, `C`, …, ours are the last letters of
+    /// the alphabet, ending with `Z`, and the outcomes are `X`, `Y` and `Z`.
+    pub fn letters(moves: usize) -> Encoding {
+        let letters = |first: u8| -> Vec<String> {
+            (0..moves as u8)
+                .map(|i| char::from(first + i).to_string())
+                .collect()
+        };
+        Encoding {
+            opponent: letters(b'A'),
+            response: letters(b'Z' + 1 - moves as u8),
+            outcomes: ["X", "Y", "Z"].map(String::from),
+        }
+    }
+}
+
+/// A game like rock paper scissors, given by a table of which move beats which. Moves that don't
+/// beat each other are a draw.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Game {
+    names: Vec<String>,
+    // beats[a][b]: move a beats move b
+    beats: Vec<Vec<bool>>,
+    pub scoring: Scoring,
+    pub encoding: Encoding,
+}
+
+impl Game {
+    /// The most moves a game can have, as the letters of the responses start at `Z - n + 1`.
+    pub const MAX_MOVES: usize = 13;
+
+    /// Checks the number of moves before anything of that size is built.
+    pub fn check_move_count(n: usize) -> Result<(), Error> {
+        if (1..=Game::MAX_MOVES).contains(&n) {
+            Ok(())
+        } else {
+            Err(Error::semantic(format!(
+                "a game needs between 1 and {} moves, not {n}",
+                Game::MAX_MOVES
+            )))
+        }
+    }
+
+    /// A game with the given rule table, standard scoring and letters.
+    pub fn from_table(names: &[&str], beats: Vec<Vec<bool>>) -> Result<Game, Error> {
+        let n = names.len();
+        Game::check_move_count(n)?;
+        if beats.len() != n || beats.iter().any(|row| row.len() != n) {
+            return Err(Error::semantic(format!(
+                "the rule table of {n} moves has to be {n}x{n}"
+            )));
+        }
+        for a in 0..n {
+            for b in 0..n {
+                if beats[a][b] && beats[b][a] {
+                    return Err(Error::semantic(format!(
+                        "{} and {} can't beat each other",
+                        names[a], names[b]
+                    )));
+                }
+            }
+        }
+        Ok(Game {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            beats,
+            scoring: Scoring::standard(n),
+            encoding: Encoding::letters(n),
+        })
+    }
+
+    /// A game with an odd number of moves in a circle, where each move beats the half of the
+    /// other moves that come right before it.
+    pub fn cyclic(names: &[&str]) -> Result<Game, Error> {
+        let n = names.len();
+        Game::check_move_count(n)?;
+        if n.is_multiple_of(2) {
+            return Err(Error::semantic(format!(
+                "a fair cyclic game needs an odd number of moves, not {n}"
+            )));
+        }
+        let beats = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| (1..=n / 2).contains(&((a + n - b) % n)))
+                    .collect()
+            })
+            .collect();
+        Game::from_table(names, beats)
+    }
+
+    pub fn rock_paper_scissors() -> Game {
+        Game::cyclic(&["Rock", "Paper", "Scissors"]).expect("three moves are a valid game")
+    }
+
+    /// Rock crushes scissors and lizard, Spock smashes scissors and vaporizes rock, paper covers
+    /// rock and disproves Spock, lizard eats paper and poisons Spock, scissors cut paper and
+    /// decapitate lizard. The letters are `A` to `E` and `V` to `Z` in the order of the moves.
+    pub fn rock_paper_scissors_lizard_spock() -> Game {
+        // in this order, every move beats the two before it
+        let cyclic = Game::cyclic(&["Rock", "Spock", "Paper", "Lizard", "Scissors"])
+            .expect("five moves are a valid game");
+        // reorder to the usual order
+        let order = [0, 2, 4, 3, 1];
+        let names: Vec<&str> = order.iter().map(|&i| cyclic.names[i].as_str()).collect();
+        let beats = order
+            .iter()
+            .map(|&a| order.iter().map(|&b| cyclic.beats[a][b]).collect())
+            .collect();
+        Game::from_table(&names, beats).expect("reordering keeps the game valid")
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.names.len()).map(Move)
+    }
+
+    pub fn name(&self, m: Move) -> &str {
+        &self.names[m.0]
+    }
+
+    pub fn outcome(&self, ours: Move, theirs: Move) -> Outcome {
+        if self.beats[ours.0][theirs.0] {
+            Outcome::Win
+        } else if self.beats[theirs.0][ours.0] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// Points for a round where we play `ours` against `theirs`.
+    pub fn score(&self, ours: Move, theirs: Move) -> u32 {
+        self.scoring.moves[ours.0] + self.scoring.outcome(self.outcome(ours, theirs))
+    }
+
+    /// The move that gets the given outcome against `theirs`. Of several such moves, the one
+    /// with the most points is taken; there may be none in games that aren't cyclic.
+    pub fn move_for(&self, outcome: Outcome, theirs: Move) -> Option<Move> {
+        self.moves()
+            .filter(|ours| self.outcome(*ours, theirs) == outcome)
+            .max_by_key(|ours| (self.scoring.moves[ours.0], std::cmp::Reverse(ours.0)))
+    }
+
+    /// Parse the strategy guide, interpreting both columns as moves.
+    pub fn parse_part1(&self, content: &str) -> Result<Vec<(Move, Move)>, Error> {
+        let guide = self.parse_columns(content, &self.encoding.response, "move")?;
+        Ok(guide
+            .into_iter()
+            .map(|(theirs, ours)| (theirs, Move(ours)))
+            .collect())
+    }
+
+    /// Parse the strategy guide, interpreting the second column as the desired outcome.
+    pub fn parse_part2(&self, content: &str) -> Result<Vec<(Move, Outcome)>, Error> {
+        let guide = self.parse_columns(content, &self.encoding.outcomes, "outcome")?;
+        Ok(guide
+            .into_iter()
+            .map(|(theirs, outcome)| (theirs, Outcome::ALL[outcome]))
+            .collect())
+    }
+
+    // the opponent's move and the index of the letter in the second column among `letters`
+    pub(crate) fn parse_columns(
+        &self,
+        content: &str,
+        letters: &[String],
+        what: &str,
+    ) -> Result<Vec<(Move, usize)>, Error> {
+        parse_lines(content, |line| {
+            let (left, right) = line
+                .split_once(' ')
+                .ok_or_else(|| Error::syntax(line, line, "line has no whitespace to split"))?;
+            let theirs = find(&self.encoding.opponent, line, left, "move")?;
+            Ok((Move(theirs), find(letters, line, right, what)?))
+        })
+    }
+
+    /// Total score if every round is played as written in the guide.
+    pub fn evaluate_part1(&self, guide: &[(Move, Move)]) -> u32 {
+        guide
+            .iter()
+            .map(|(theirs, ours)| self.score(*ours, *theirs))
+            .sum()
+    }
+
+    /// Total score if we play for the outcome given in the guide.
+    pub fn evaluate_part2(&self, guide: &[(Move, Outcome)]) -> Result<u32, Error> {
+        guide
+            .iter()
+            .map(|(theirs, outcome)| {
+                let ours = self.move_for(*outcome, *theirs).ok_or_else(|| {
+                    Error::semantic(format!(
+                        "no move gets a {outcome:?} against {}",
+                        self.name(*theirs)
+                    ))
+                })?;
+                Ok(self.score(ours, *theirs))
+            })
+            .sum()
+    }
+}
+
+// the index of `token` in `letters`
+fn find(letters: &[String], line: &str, token: &str, what: &str) -> Result<usize, Error> {
+    letters
+        .iter()
+        .position(|letter| letter == token)
+        .ok_or_else(|| Error::syntax(line, token, format!("'{token}' is not a valid {what}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rock_paper_scissors_lizard_spock_has_the_usual_rules() {
+        // given
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let by_name = |name: &str| game.moves().find(|m| game.name(*m) == name).unwrap();
+
+        // when
+        let wins: Vec<(&str, Vec<&str>)> = game
+            .moves()
+            .map(|ours| {
+                let beaten = game
+                    .moves()
+                    .filter(|theirs| game.outcome(ours, *theirs) == Outcome::Win);
+                (game.name(ours), beaten.map(|m| game.name(m)).collect())
+            })
+            .collect();
+
+        // then
+        assert_eq!(
+            wins,
+            [
+                ("Rock", vec!["Scissors", "Lizard"]),
+                ("Paper", vec!["Rock", "Spock"]),
+                ("Scissors", vec!["Paper", "Lizard"]),
+                ("Lizard", vec!["Paper", "Spock"]),
+                ("Spock", vec!["Rock", "Scissors"]),
+            ]
+        );
+        assert_eq!(game.encoding.response[by_name("Spock").0], "Z");
+    }
+
+    #[test]
+    fn evaluate_works_for_cyclic_games() {
+        // given
+        let mut game = Game::rock_paper_scissors_lizard_spock();
+        game.scoring.win = 10;
+        let guide = "A V\nE W\nD Z\n";
+
+        // when
+        let part1 = game.parse_part1(guide).map(|g| game.evaluate_part1(&g));
+        let part2 = game
+            .parse_part2("A X\nB Z\n")
+            .map(|g| game.evaluate_part2(&g));
+        let even = Game::cyclic(&["a", "b", "c", "d"]);
+
+        // then
+        // rock draws rock, paper beats Spock, Spock loses against lizard
+        assert_eq!(part1, Ok(1 + 3 + 2 + 10 + 5));
+        // lose against rock with lizard, win against paper with lizard
+        assert_eq!(part2, Ok(Ok(4 + 4 + 10)));
+        assert!(even.is_err());
+    }
+
+    #[test]
+    fn move_counts_are_checked_before_building_a_game() {
+        // given
+        let names = ["a"; Game::MAX_MOVES + 2];
+
+        // when
+        let huge = Game::check_move_count(usize::MAX);
+        let too_many = Game::cyclic(&names);
+        let most = Game::cyclic(&names[..Game::MAX_MOVES]);
+
+        // then
+        assert!(huge.is_err());
+        assert!(too_many.is_err());
+        assert!(Game::cyclic(&[]).is_err());
+        assert!(most.is_ok());
+    }
+}
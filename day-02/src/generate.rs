@@ -0,0 +1,16 @@
+use aoc_common::GenerateOptions;
+
+/// A strategy guide with one round per line. The size is the number of rounds, the difficulty
+/// makes no difference for a list of rounds.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    (0..options.size_or(2500))
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&["A", "B", "C"]),
+                rng.choose(&["X", "Y", "Z"])
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,238 @@
+//! Day 2: Rock Paper Scissors
+
+use aoc_common::{parse_lines, Answer, Error, GenerateOptions, Solution};
+use std::sync::LazyLock;
+
+mod game;
+mod generate;
+mod optimise;
+
+pub use game::{Encoding, Game, Move, Scoring};
+pub use generate::generate_input;
+pub use optimise::{optimise, Interpretation, Scored};
+
+// the game of the puzzle, in the order of `Hand`
+static ROCK_PAPER_SCISSORS: LazyLock<Game> = LazyLock::new(Game::rock_paper_scissors);
+
+/// Solves both parts of day 2 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 2;
+    type Input<'a> = (Vec<(Hand, Hand)>, Vec<(Hand, Outcome)>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok((parse_part1(input)?, parse_part2(input)?))
+    }
+    fn part1((guide, _): &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(evaluate_part1(guide).into())
+    }
+    fn part2((_, guide): &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(evaluate_part2(guide).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// Parse the strategy guide, interpreting both columns as hands.
+pub fn parse_part1(content: &str) -> Result<Vec<(Hand, Hand)>, Error> {
+    parse_lines(content, parse_line_part1)
+}
+
+pub fn parse_line_part1(line: &str) -> Result<(Hand, Hand), Error> {
+    let (left, right) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::syntax(line, line, "line has no whitespace to split"))?;
+    let left_hand = match left {
+        "A" => Hand::Rock,
+        "B" => Hand::Paper,
+        "C" => Hand::Scissors,
+        s => return Err(Error::syntax(line, s, format!("'{s}' is not a valid hand"))),
+    };
+    let right_hand = match right {
+        "X" => Hand::Rock,
+        "Y" => Hand::Paper,
+        "Z" => Hand::Scissors,
+        s => return Err(Error::syntax(line, s, format!("'{s}' is not a valid hand"))),
+    };
+    Ok((left_hand, right_hand))
+}
+
+/// Parse the strategy guide, interpreting the second column as the desired outcome.
+pub fn parse_part2(content: &str) -> Result<Vec<(Hand, Outcome)>, Error> {
+    parse_lines(content, parse_line_part2)
+}
+
+pub fn parse_line_part2(line: &str) -> Result<(Hand, Outcome), Error> {
+    let (left, right) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::syntax(line, line, "line has no whitespace to split"))?;
+    let left_hand = match left {
+        "A" => Hand::Rock,
+        "B" => Hand::Paper,
+        "C" => Hand::Scissors,
+        s => return Err(Error::syntax(line, s, format!("'{s}' is not a valid hand"))),
+    };
+    let right_hand = match right {
+        "X" => Outcome::Loss,
+        "Y" => Outcome::Draw,
+        "Z" => Outcome::Win,
+        s => {
+            return Err(Error::syntax(
+                line,
+                s,
+                format!("'{s}' is not a valid outcome"),
+            ))
+        }
+    };
+    Ok((left_hand, right_hand))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Hand {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Hand {
+    /// Points for choosing this hand.
+    pub fn score(self) -> u32 {
+        match self {
+            Hand::Rock => 1,
+            Hand::Paper => 2,
+            Hand::Scissors => 3,
+        }
+    }
+    pub fn outcome_against(self, other: Hand) -> Outcome {
+        ROCK_PAPER_SCISSORS.outcome(self.into(), other.into())
+    }
+    /// Points for a round where we play this hand against `other`.
+    pub fn score_against(self, other: Hand) -> u32 {
+        self.outcome_against(other).score() + self.score()
+    }
+}
+
+impl From<Hand> for Move {
+    fn from(hand: Hand) -> Move {
+        Move(hand as usize)
+    }
+}
+
+impl From<Move> for Hand {
+    fn from(m: Move) -> Hand {
+        [Hand::Rock, Hand::Paper, Hand::Scissors][m.0]
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    /// Points for this outcome of a round.
+    pub fn score(self) -> u32 {
+        match self {
+            Outcome::Loss => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+/// Total score if every round is played as written in the guide.
+pub fn evaluate_part1(strat_guide: &[(Hand, Hand)]) -> u32 {
+    let guide: Vec<(Move, Move)> = strat_guide
+        .iter()
+        .map(|(left, right)| ((*left).into(), (*right).into()))
+        .collect();
+    ROCK_PAPER_SCISSORS.evaluate_part1(&guide)
+}
+
+/// The hand we need to play against `opponent` to get the given outcome.
+pub fn hand_required(outcome: Outcome, opponent: Hand) -> Hand {
+    ROCK_PAPER_SCISSORS
+        .move_for(outcome, opponent.into())
+        .expect("every outcome is possible in rock paper scissors")
+        .into()
+}
+
+/// Total score if we play for the outcome given in the guide.
+pub fn evaluate_part2(strat_guide: &[(Hand, Outcome)]) -> u32 {
+    let guide: Vec<(Move, Outcome)> = strat_guide
+        .iter()
+        .map(|(op, outcome)| ((*op).into(), *outcome))
+        .collect();
+    ROCK_PAPER_SCISSORS
+        .evaluate_part2(&guide)
+        .expect("every outcome is possible in rock paper scissors")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    static EXAMPLE: &str = r#"A Y
+B X
+C Z"#;
+
+    #[test]
+    fn evaluate_part1_works_correctly() {
+        // given
+        let guide = parse_part1(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let score = evaluate_part1(&guide);
+
+        // then
+        assert_eq!(score, 15);
+    }
+
+    #[test]
+    fn evaluate_part2_works_correctly() {
+        // given
+        let guide = parse_part2(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let score = evaluate_part2(&guide);
+
+        // then
+        assert_eq!(score, 12);
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        let game = Game::rock_paper_scissors_lizard_spock();
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_part1(input);
+            let _ = parse_part2(input);
+            let _ = parse_line_part1(input);
+            let _ = parse_line_part2(input);
+            let _ = game
+                .parse_part1(input)
+                .map(|guide| game.evaluate_part1(&guide));
+            let _ = game
+                .parse_part2(input)
+                .map(|guide| game.evaluate_part2(&guide));
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
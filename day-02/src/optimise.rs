@@ -0,0 +1,167 @@
+use crate::{Game, Move, Outcome};
+use aoc_common::Error;
+
+// n! mappings of the response letters are tried, that's 40320 for 8 moves
+const MAX_MOVES: usize = 8;
+
+/// What the letters of the second column of the strategy guide stand for.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Interpretation {
+    /// The move we play, for each response letter.
+    Moves(Vec<Move>),
+    /// The outcome we go for, for each outcome letter.
+    Outcomes([Outcome; 3]),
+}
+
+impl Interpretation {
+    /// The mapping as `X=Rock, Y=Paper, Z=Scissors`.
+    pub fn describe(&self, game: &Game) -> String {
+        let pairs: Vec<String> = match self {
+            Interpretation::Moves(moves) => game
+                .encoding
+                .response
+                .iter()
+                .zip(moves)
+                .map(|(letter, m)| format!("{letter}={}", game.name(*m)))
+                .collect(),
+            Interpretation::Outcomes(outcomes) => game
+                .encoding
+                .outcomes
+                .iter()
+                .zip(outcomes)
+                .map(|(letter, outcome)| format!("{letter}={outcome:?}"))
+                .collect(),
+        };
+        pairs.join(", ")
+    }
+}
+
+/// An interpretation with the total score of the guide read that way.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Scored {
+    pub interpretation: Interpretation,
+    pub score: u32,
+}
+
+/// Score the guide for every one-to-one mapping of the second column to moves or outcomes, the
+/// best first and the worst last. Outcome mappings are only tried if the guide uses just the
+/// outcome letters, and skipped if one of the outcomes can't be reached in the game.
+pub fn optimise(game: &Game, content: &str) -> Result<Vec<Scored>, Error> {
+    let n = game.moves().count();
+    if n > MAX_MOVES {
+        return Err(Error::semantic(format!(
+            "trying every mapping of {n} moves takes too long, at most {MAX_MOVES} are supported"
+        )));
+    }
+
+    let mut scored = Vec::new();
+    let counts = count_rounds(
+        n,
+        game.parse_columns(content, &game.encoding.response, "move")?,
+    );
+    for mapping in permutations(n) {
+        let score = total(&counts, |theirs, letter| {
+            Some(game.score(Move(mapping[letter]), theirs))
+        });
+        if let Some(score) = score {
+            let moves = mapping.into_iter().map(Move).collect();
+            scored.push(Scored {
+                interpretation: Interpretation::Moves(moves),
+                score,
+            });
+        }
+    }
+
+    if let Ok(guide) = game.parse_columns(content, &game.encoding.outcomes, "outcome") {
+        let counts = count_rounds(n, guide);
+        for mapping in permutations(3) {
+            let outcomes = [0, 1, 2].map(|letter| Outcome::ALL[mapping[letter]]);
+            let score = total(&counts, |theirs, letter| {
+                let ours = game.move_for(outcomes[letter], theirs)?;
+                Some(game.score(ours, theirs))
+            });
+            if let Some(score) = score {
+                scored.push(Scored {
+                    interpretation: Interpretation::Outcomes(outcomes),
+                    score,
+                });
+            }
+        }
+    }
+
+    // stable, so of equal scores the moves and the earlier mappings come first
+    scored.sort_by_key(|s| std::cmp::Reverse(s.score));
+    Ok(scored)
+}
+
+// counts[theirs][letter]: how often the opponent's move meets the letter of the second column
+fn count_rounds(n: usize, guide: Vec<(Move, usize)>) -> Vec<Vec<u32>> {
+    let mut counts = vec![vec![0; n.max(3)]; n];
+    for (theirs, letter) in guide {
+        counts[theirs.0][letter] += 1;
+    }
+    counts
+}
+
+// the sum of the points of all rounds, None if a round can't be played
+fn total(counts: &[Vec<u32>], points: impl Fn(Move, usize) -> Option<u32>) -> Option<u32> {
+    let mut sum = 0;
+    for (theirs, row) in counts.iter().enumerate() {
+        for (letter, &count) in row.iter().enumerate() {
+            if count > 0 {
+                sum += count * points(Move(theirs), letter)?;
+            }
+        }
+    }
+    Some(sum)
+}
+
+// all orderings of 0..n, in lexicographic order
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut all = Vec::new();
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut mapping = vec![first];
+            mapping.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            all.push(mapping);
+        }
+    }
+    all
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn optimise_finds_the_best_and_worst_mapping() {
+        // given
+        let game = Game::rock_paper_scissors();
+        let guide = "A Y\nB X\nC Z\n";
+
+        // when
+        let scored = optimise(&game, guide).expect("expected successful parsing");
+
+        // then
+        // 3! mappings to moves and 3! to outcomes
+        assert_eq!(scored.len(), 12);
+        let best = &scored[0];
+        assert_eq!(best.score, 24);
+        assert_eq!(
+            best.interpretation.describe(&game),
+            "X=Scissors, Y=Paper, Z=Rock"
+        );
+        let worst = &scored[scored.len() - 1];
+        assert_eq!(worst.score, 6);
+        assert_eq!(
+            worst.interpretation.describe(&game),
+            "X=Rock, Y=Scissors, Z=Paper"
+        );
+        let part2 = Interpretation::Outcomes(Outcome::ALL);
+        let score = scored.iter().find(|s| s.interpretation == part2);
+        assert_eq!(score.map(|s| s.score), Some(12));
+    }
+}
@@ -0,0 +1,50 @@
+use aoc_common::GenerateOptions;
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Rucksacks in groups of three. Each rucksack has exactly one item in both compartments, and
+/// each group has exactly one badge all three elves carry. The size is the number of groups, the
+/// harder the puzzle, the fuller the rucksacks.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let (min_items, max_items) = options.pick((4, 8), (8, 16), (16, 24));
+    let mut lines: Vec<String> = Vec::with_capacity(options.size_or(100) * 3);
+    for _ in 0..options.size_or(100) {
+        let badge = *rng.choose(ITEMS);
+        // every other item is available to at most two elves of the group, so the badge is the
+        // only item all three of them can have
+        let mut available: [Vec<u8>; 3] = Default::default();
+        for item in ITEMS.iter().filter(|item| **item != badge) {
+            let missing = rng.below(3);
+            for (elf, items) in available.iter_mut().enumerate() {
+                if elf != missing {
+                    items.push(*item);
+                }
+            }
+        }
+        for mut items in available {
+            rng.shuffle(&mut items);
+            let double = if rng.chance(0.2) {
+                badge
+            } else {
+                items.pop().unwrap_or(badge)
+            };
+            // the remaining items only go into one of the compartments
+            let right_items = items.split_off(items.len() / 2);
+            let mut compartments = [vec![double], vec![double]];
+            if double != badge {
+                compartments[rng.below(2)].push(badge);
+            }
+            let n = rng.between(min_items, max_items) as usize;
+            for (compartment, pool) in compartments.iter_mut().zip([&items, &right_items]) {
+                while compartment.len() < n {
+                    compartment.push(*rng.choose(pool));
+                }
+                rng.shuffle(compartment);
+            }
+            let [left, right] = compartments;
+            lines.push(String::from_utf8_lossy(&[left, right].concat()).into_owned());
+        }
+    }
+    lines.join("\n") + "\n"
+}
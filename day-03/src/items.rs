@@ -0,0 +1,140 @@
+use aoc_common::Error;
+use std::collections::HashMap;
+
+// priority 0 marks the end of a line
+const MAX_ITEMS: usize = 255;
+
+/// The items that can be in a rucksack, in the order of their priorities: the first item has
+/// priority 1, the second priority 2, and so on.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Alphabet {
+    items: Vec<char>,
+    priorities: HashMap<char, u8>,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::new("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ")
+            .expect("the letters are a valid alphabet")
+    }
+}
+
+impl Alphabet {
+    /// An alphabet of up to 255 different items, which may be any characters but line breaks.
+    pub fn new(items: &str) -> Result<Alphabet, Error> {
+        let items: Vec<char> = items.chars().collect();
+        if items.is_empty() || items.len() > MAX_ITEMS {
+            return Err(Error::semantic(format!(
+                "an alphabet needs between 1 and {MAX_ITEMS} items, not {}",
+                items.len()
+            )));
+        }
+        let mut priorities = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            if *item == '\n' || *item == '\r' {
+                return Err(Error::semantic("line breaks can't be items"));
+            }
+            if priorities.insert(*item, i as u8 + 1).is_some() {
+                return Err(Error::semantic(format!(
+                    "'{item}' appears twice in the alphabet"
+                )));
+            }
+        }
+        Ok(Alphabet { items, priorities })
+    }
+
+    pub fn priority(&self, item: char) -> Option<u8> {
+        self.priorities.get(&item).copied()
+    }
+
+    pub fn item(&self, priority: u8) -> Option<char> {
+        self.items.get((priority as usize).checked_sub(1)?).copied()
+    }
+
+    /// Replace each item by its priority, and line breaks by 0, like [`crate::to_priorities`].
+    /// Lines may end in `\r\n` as well.
+    pub fn to_priorities(&self, content: &str) -> Result<Vec<u8>, Error> {
+        let mut priorities = Vec::with_capacity(content.len());
+        for (i, line) in content.lines().enumerate() {
+            for (column, item) in line.char_indices() {
+                let priority = self.priority(item).ok_or_else(|| {
+                    let span = &line[column..column + item.len_utf8()];
+                    Error::syntax(line, span, format!("'{item}' is not an item")).at_line(i + 1)
+                })?;
+                priorities.push(priority);
+            }
+            priorities.push(0);
+        }
+        Ok(priorities)
+    }
+}
+
+/// A set of item priorities, one bit per priority, so intersecting sets is cheap.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Hash)]
+pub struct ItemSet([u64; 4]);
+
+impl ItemSet {
+    /// The set of all priorities, the start for intersecting the items of several rucksacks.
+    pub fn all() -> ItemSet {
+        ItemSet([!0; 4])
+    }
+
+    pub fn insert(&mut self, priority: u8) {
+        self.0[priority as usize / 64] |= 1 << (priority % 64);
+    }
+
+    pub fn contains(&self, priority: u8) -> bool {
+        self.0[priority as usize / 64] & (1 << (priority % 64)) != 0
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        ItemSet([0, 1, 2, 3].map(|i| self.0[i] & other.0[i]))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+
+    /// The priorities in the set, the lowest first.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|priority| self.contains(*priority))
+    }
+}
+
+impl FromIterator<u8> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = u8>>(priorities: I) -> Self {
+        let mut set = ItemSet::default();
+        for priority in priorities {
+            set.insert(priority);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn alphabet_maps_items_to_priorities() {
+        // given
+        let alphabet = Alphabet::new("äöü€").expect("expected a valid alphabet");
+
+        // when
+        let priorities = alphabet.to_priorities("ä€\nüö\n");
+        let error = alphabet.to_priorities("äö\nüx\n");
+        let crlf = alphabet.to_priorities("ä€\r\nüö\r\n");
+
+        // then
+        assert_eq!(priorities, Ok(vec![1, 4, 0, 3, 2, 0]));
+        assert_eq!(crlf, priorities);
+        let error = error.expect_err("expected an error for the unknown item");
+        assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 2)));
+        assert!(Alphabet::new("abca").is_err());
+        assert_eq!(Alphabet::default().item(27), Some('A'));
+    }
+}
@@ -0,0 +1,323 @@
+//! Day 3: Rucksack Reorganization
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+use std::ops::Range;
+
+mod generate;
+mod items;
+mod validate;
+
+pub use generate::generate_input;
+pub use items::{Alphabet, ItemSet};
+pub use validate::{validate, Problem, Report};
+
+/// Solves both parts of day 3 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 3;
+    type Input<'a> = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        to_priorities(input.as_bytes().to_vec())
+    }
+    fn part1(priorities: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(sum_doubles(&parse_rucksacks(priorities)).into())
+    }
+    fn part2(priorities: &Self::Input<'_>) -> Result<Answer, Error> {
+        let rucksacks = parse_rucksacks(priorities);
+        if !rucksacks.len().is_multiple_of(3) {
+            return Err(Error::semantic(
+                "it appears we have lost an elf somewhere in the jungle",
+            ));
+        }
+        Ok(sum_common(&rucksacks).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// Replace each item by its priority (1–52). Line breaks are replaced by 0.
+pub fn to_priorities(mut content: Vec<u8>) -> Result<Vec<u8>, Error> {
+    // check first, so we still have the original line for the error message
+    if let Some(i) = content
+        .iter()
+        .position(|b| !b.is_ascii_alphabetic() && *b != b'\n')
+    {
+        return Err(unexpected_byte(&content, i));
+    }
+    for b in &mut content {
+        if *b >= b'a' && *b <= b'z' {
+            *b = *b - b'a' + 1;
+        } else if *b >= b'A' && *b <= b'Z' {
+            *b = *b - b'A' + 27;
+        } else {
+            *b = 0;
+        }
+    }
+    Ok(content)
+}
+
+fn unexpected_byte(content: &[u8], i: usize) -> Error {
+    let line_start = content[..i]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map(|p| p + 1)
+        .unwrap_or(0);
+    let line_end = content[i..]
+        .iter()
+        .position(|b| *b == b'\n')
+        .map(|p| p + i)
+        .unwrap_or(content.len());
+    let line_no = content[..line_start]
+        .iter()
+        .filter(|b| **b == b'\n')
+        .count()
+        + 1;
+    let line = String::from_utf8_lossy(&content[line_start..line_end]);
+    let column = String::from_utf8_lossy(&content[line_start..i]).len();
+    let span_len = line[column..]
+        .chars()
+        .next()
+        .map(char::len_utf8)
+        .unwrap_or(0);
+    Error::syntax(
+        &line,
+        &line[column..column + span_len],
+        format!("unexpected byte {} in input", content[i]),
+    )
+    .at_line(line_no)
+}
+
+/// Split the output of [`to_priorities`] into rucksacks with two compartments each.
+pub fn parse_rucksacks(content: &[u8]) -> Vec<(&[u8], &[u8])> {
+    content
+        .split(|b| *b == 0)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split_at(line.len() / 2))
+        .collect()
+}
+
+/// Sum of the priorities of the items that appear in both compartments of a rucksack.
+pub fn sum_doubles(rucksacks: &[(&[u8], &[u8])]) -> u32 {
+    rucksacks
+        .iter()
+        .map(|(c1, c2)| find_double(c1, c2) as u32)
+        .sum::<u32>()
+}
+
+/// Priority of the item that appears in both compartments, or 0 if there is none.
+pub fn find_double(c1: &[u8], c2: &[u8]) -> u8 {
+    let found: ItemSet = c1.iter().copied().collect();
+    c2.iter()
+        .copied()
+        .find(|prio| found.contains(*prio))
+        .unwrap_or(0)
+}
+
+/// Sum of the priorities of the badges of each group of three elves.
+pub fn sum_common(rucksacks: &[(&[u8], &[u8])]) -> u32 {
+    // any remainder (group of less than 3) will be ignored. should be checked beforehand
+    rucksacks
+        .chunks_exact(3)
+        .map(|group| find_common(group[0], group[1], group[2]) as u32)
+        .sum::<u32>()
+}
+
+/// Priority of the item all three elves carry, or 0 if there is none.
+pub fn find_common(e1: (&[u8], &[u8]), e2: (&[u8], &[u8]), e3: (&[u8], &[u8])) -> u8 {
+    find_badge(&[e1, e2, e3])
+}
+
+/// Priority of the badge of a group: the first item of the last elf that all others carry as
+/// well, or 0 if there is none.
+pub fn find_badge(group: &[(&[u8], &[u8])]) -> u8 {
+    let Some(((c1, c2), others)) = group.split_last() else {
+        return 0;
+    };
+    let found = find_shared(others);
+    c1.iter()
+        .chain(*c2)
+        .copied()
+        .find(|prio| found.contains(*prio))
+        .unwrap_or(0)
+}
+
+/// The items every elf of the group carries, in either compartment.
+pub fn find_shared(group: &[(&[u8], &[u8])]) -> ItemSet {
+    group.iter().fold(ItemSet::all(), |shared, (c1, c2)| {
+        let items: ItemSet = c1.iter().chain(*c2).copied().collect();
+        shared.intersection(&items)
+    })
+}
+
+/// A group of elves and the items all of them carry, which should be just their badge.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Group {
+    /// Indices of the elves in the group, counting from 0.
+    pub elves: Range<usize>,
+    pub shared: ItemSet,
+    /// See [`find_badge`].
+    pub badge: u8,
+}
+
+/// Split the rucksacks into groups of `size` elves. Elves left over that don't fill a last group
+/// are an error.
+pub fn groups(rucksacks: &[(&[u8], &[u8])], size: usize) -> Result<Vec<Group>, Error> {
+    if size == 0 {
+        return Err(Error::semantic("a group needs at least one elf"));
+    }
+    let complete = rucksacks.len() - rucksacks.len() % size;
+    let left_over = match rucksacks.len() - complete {
+        0 => None,
+        1 => Some(format!("elf {} is", rucksacks.len())),
+        _ => Some(format!("elves {} to {} are", complete + 1, rucksacks.len())),
+    };
+    if let Some(elves) = left_over {
+        return Err(Error::semantic(format!(
+            "{elves} left over, a group needs {size} elves"
+        )));
+    }
+    Ok(rucksacks
+        .chunks_exact(size)
+        .enumerate()
+        .map(|(i, group)| Group {
+            elves: i * size..(i + 1) * size,
+            shared: find_shared(group),
+            badge: find_badge(group),
+        })
+        .collect())
+}
+
+/// Sum of the priorities of the badges, one per group.
+pub fn sum_badges(groups: &[Group]) -> u32 {
+    groups.iter().map(|group| u32::from(group.badge)).sum()
+}
+
+/// Sum of the priorities of all items shared within each group. That's the sum of the badges if
+/// every group has exactly one.
+pub fn sum_shared(groups: &[Group]) -> u32 {
+    groups
+        .iter()
+        .flat_map(|group| group.shared.iter())
+        .map(u32::from)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &[u8] = br#"vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+"#;
+
+    #[test]
+    fn sum_doubles_works_for_example() {
+        // given
+        let priorities = to_priorities(EXAMPLE.to_vec()).expect("Expected valid input");
+        let rucksacks = parse_rucksacks(&priorities);
+
+        // when
+        let sum = sum_doubles(&rucksacks);
+
+        // then
+        assert_eq!(sum, 157);
+    }
+
+    #[test]
+    fn sum_common_works_for_example() {
+        // given
+        let priorities = to_priorities(EXAMPLE.to_vec()).expect("Expected valid input");
+        let rucksacks = parse_rucksacks(&priorities);
+
+        // when
+        let sum = sum_common(&rucksacks);
+
+        // then
+        assert_eq!(sum, 70);
+    }
+
+    #[test]
+    fn groups_report_every_shared_item() {
+        // given
+        let alphabet = Alphabet::default();
+        let priorities = alphabet
+            .to_priorities("abcd\nbcda\nxbcx\nxycz\n")
+            .expect("expected valid input");
+        let rucksacks = parse_rucksacks(&priorities);
+
+        // when
+        let pairs = groups(&rucksacks, 2).expect("expected a valid group size");
+        let all = groups(&rucksacks, 4).expect("expected a valid group size");
+
+        // then
+        let shared: Vec<Vec<u8>> = pairs.iter().map(|g| g.shared.iter().collect()).collect();
+        assert_eq!(shared, [vec![1, 2, 3, 4], vec![3, 24]]);
+        assert_eq!(pairs[1].elves, 2..4);
+        assert_eq!(all[0].shared.iter().collect::<Vec<u8>>(), [3]);
+        assert_eq!(pairs[1].badge, 24);
+        assert!(groups(&rucksacks, 0).is_err());
+    }
+
+    #[test]
+    fn sum_shared_counts_every_shared_item() {
+        // given
+        let alphabet = Alphabet::default();
+        let priorities = alphabet
+            .to_priorities("abcd\nbcda\nxbcx\nxycz\n")
+            .expect("expected valid input");
+        let pairs = groups(&parse_rucksacks(&priorities), 2).expect("expected a valid group size");
+
+        // when
+        let shared = sum_shared(&pairs);
+        let badges = sum_badges(&pairs);
+
+        // then
+        assert_eq!(shared, 1 + 2 + 3 + 4 + 3 + 24);
+        assert_eq!(badges, 2 + 24);
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[&generated], |input| {
+            if let Ok(priorities) = Solver::parse(input) {
+                let _ = parse_rucksacks(&priorities);
+            }
+            let _ = parse_rucksacks(input.as_bytes());
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn groups_reject_elves_left_over() {
+        // given
+        let alphabet = Alphabet::default();
+        let priorities = alphabet
+            .to_priorities("abcd\nbcda\nxbcx\nxycz\naabb\n")
+            .expect("expected valid input");
+        let rucksacks = parse_rucksacks(&priorities);
+
+        // when
+        let result = groups(&rucksacks, 2);
+
+        // then
+        let error = result.expect_err("expected the fifth elf to be left over");
+        assert_eq!(error.message, "elf 5 is left over, a group needs 2 elves");
+    }
+}
@@ -1,135 +1,100 @@
+use aoc_common::{inputs_from_args, run_main, split_text_args, take_option, Error};
+use day_03::{
+    groups, parse_rucksacks, sum_badges, sum_common, sum_doubles, sum_shared, to_priorities,
+    validate, Alphabet, Solver,
+};
 use std::env;
-use std::fs::read;
-use std::path::Path;
 
-fn main() -> Result<(), String> {
-    let filename = env::args()
-        .nth(1)
-        .ok_or_else(|| "No file name given.".to_owned())?;
-    let content = read(Path::new(&filename)).map_err(|e| e.to_string())?;
-    let priorities = to_priorities(content)?;
-    let rucksacks = parse_rucksacks(&priorities);
-
-    let sum_double_items = sum_doubles(&rucksacks);
-    println!(
-        "The sum of priorities of items that appear in both compartments is {sum_double_items}."
-    );
-
-    if rucksacks.len() % 3 != 0 {
-        return Err("it appears we have lost an elf somewhere in the jungle".to_owned());
+fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let size = take_option(&mut args, "--group-size")?
+        .map(|n| {
+            n.parse::<usize>()
+                .map_err(|_| Error::semantic(format!("'{n}' is not a number of elves")))
+        })
+        .transpose()?;
+    let alphabet = take_option(&mut args, "--alphabet")?
+        .map(|items| Alphabet::new(&items))
+        .transpose()?;
+    let validating = args.iter().any(|arg| arg == "--validate");
+    args.retain(|arg| arg != "--validate");
+    if size.is_none() && alphabet.is_none() && !validating {
+        return run_main::<Solver>(print_answers);
     }
-    let sum_badge_priorities = sum_common(&rucksacks);
-    println!("The sum of badge priorities is {sum_badge_priorities}.");
-
-    Ok(())
-}
 
-fn to_priorities(mut content: Vec<u8>) -> Result<Vec<u8>, String> {
-    for b in &mut content {
-        if *b >= b'a' && *b <= b'z' {
-            *b = *b - b'a' + 1;
-        } else if *b >= b'A' && *b <= b'Z' {
-            *b = *b - b'A' + 27;
-        } else if *b == b'\n' {
-            *b = 0;
-        } else {
-            return Err(format!("unexpected byte {b} in input"));
+    let alphabet = alphabet.unwrap_or_default();
+    let positional = split_text_args(args)?;
+    for input in inputs_from_args(&positional) {
+        if validating {
+            print_report(&input.read()?, &alphabet, size.unwrap_or(3))?;
+            continue;
         }
-    }
-    Ok(content)
-}
-
-fn parse_rucksacks(content: &[u8]) -> Vec<(&[u8], &[u8])> {
-    content
-        .split(|b| *b == 0)
-        .filter(|line| !line.is_empty())
-        .map(|line| line.split_at(line.len() / 2))
-        .collect()
-}
-
-fn sum_doubles(rucksacks: &[(&[u8], &[u8])]) -> u32 {
-    rucksacks
-        .iter()
-        .map(|(c1, c2)| find_double(c1, c2) as u32)
-        .sum::<u32>()
-}
-
-fn find_double(c1: &[u8], c2: &[u8]) -> u8 {
-    let mut found: [bool; 53] = [false; 53];
-    for prio in c1 {
-        found[*prio as usize] = true;
-    }
-    for prio in c2 {
-        if found[*prio as usize] {
-            return *prio;
+        let priorities = alphabet.to_priorities(&input.read()?)?;
+        let rucksacks = parse_rucksacks(&priorities);
+        println!(
+            "The sum of priorities of items that appear in both compartments is {}.",
+            sum_doubles(&rucksacks)
+        );
+        let groups = groups(&rucksacks, size.unwrap_or(3))?;
+        let badges = sum_badges(&groups);
+        println!("The sum of badge priorities is {badges}.");
+        let shared = sum_shared(&groups);
+        if shared != badges {
+            println!("Counting every item a group shares, the sum is {shared}.");
+        }
+        for group in groups.iter().filter(|group| group.shared.len() != 1) {
+            let elves = format!("Elves {} to {}", group.elves.start + 1, group.elves.end);
+            let items: Vec<String> = group
+                .shared
+                .iter()
+                .filter_map(|priority| alphabet.item(priority))
+                .map(String::from)
+                .collect();
+            match items.len() {
+                0 => println!("{elves} share no item."),
+                n => println!("{elves} share {n} items: {}.", items.join(", ")),
+            }
         }
     }
-    0
-}
-
-fn sum_common(rucksacks: &[(&[u8], &[u8])]) -> u32 {
-    // any remainder (group of less than 3) will be ignored. should be checked beforehand
-    rucksacks
-        .chunks_exact(3)
-        .map(|group| find_common(group[0], group[1], group[2]) as u32)
-        .sum::<u32>()
+    Ok(())
 }
 
-fn find_common(e1: (&[u8], &[u8]), e2: (&[u8], &[u8]), e3: (&[u8], &[u8])) -> u8 {
-    // We have these backpacks in compartments now and I'm too lazy to change that, so we work with
-    // the compartments
-    let mut found: [u8; 53] = [0; 53];
-    for prio in e1.0.iter().chain(e1.1) {
-        found[*prio as usize] = 1;
-    }
-    for prio in e2.0.iter().chain(e2.1) {
-        if found[*prio as usize] == 1 {
-            found[*prio as usize] = 2;
-        }
+// all problems, then the results of the rucksacks and groups without problems
+fn print_report(content: &str, alphabet: &Alphabet, size: usize) -> Result<(), Error> {
+    let report = validate(content, alphabet, size)?;
+    for problem in &report.problems {
+        println!("{}", problem.describe(alphabet));
     }
-    for prio in e3.0.iter().chain(e3.1) {
-        if found[*prio as usize] == 2 {
-            return *prio;
-        }
-    }
-    0
+    let partial = if report.is_valid() {
+        "The rucksacks look fine."
+    } else {
+        "Leaving out the rucksacks and groups above:"
+    };
+    println!("{partial}");
+    println!(
+        "The sum of priorities of items that appear in both compartments is {}.",
+        report.doubles
+    );
+    println!("The sum of badge priorities is {}.", report.badges);
+    Ok(())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const EXAMPLE: &[u8] = br#"vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw
-"#;
-
-    #[test]
-    fn sum_doubles_works_for_example() {
-        // given
-        let priorities = to_priorities(EXAMPLE.to_vec()).expect("Expected valid input");
-        let rucksacks = parse_rucksacks(&priorities);
+fn print_answers(content: &str) -> Result<(), Error> {
+    let priorities = to_priorities(content.as_bytes().to_vec())?;
+    let rucksacks = parse_rucksacks(&priorities);
 
-        // when
-        let sum = sum_doubles(&rucksacks);
+    let sum_double_items = sum_doubles(&rucksacks);
+    println!(
+        "The sum of priorities of items that appear in both compartments is {sum_double_items}."
+    );
 
-        // then
-        assert_eq!(sum, 157);
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(Error::semantic(
+            "it appears we have lost an elf somewhere in the jungle, --validate shows where",
+        ));
     }
+    let sum_badge_priorities = sum_common(&rucksacks);
+    println!("The sum of badge priorities is {sum_badge_priorities}.");
 
-    #[test]
-    fn sum_common_works_for_example() {
-        // given
-        let priorities = to_priorities(EXAMPLE.to_vec()).expect("Expected valid input");
-        let rucksacks = parse_rucksacks(&priorities);
-
-        // when
-        let sum = sum_common(&rucksacks);
-
-        // then
-        assert_eq!(sum, 70);
-    }
+    Ok(())
 }
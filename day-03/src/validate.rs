@@ -0,0 +1,237 @@
+use crate::{find_shared, Alphabet, ItemSet};
+use aoc_common::Error;
+
+/// Something wrong with the rucksacks, found by [`validate`]. Lines count from 1.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Problem {
+    /// The item is not in the alphabet, it is ignored.
+    UnknownItem {
+        line: usize,
+        item: char,
+    },
+    /// The items can't be split into two compartments of the same size.
+    OddLength {
+        line: usize,
+        items: usize,
+    },
+    NoDouble {
+        line: usize,
+    },
+    SeveralDoubles {
+        line: usize,
+        doubles: ItemSet,
+    },
+    NoBadge {
+        lines: Vec<usize>,
+    },
+    SeveralBadges {
+        lines: Vec<usize>,
+        badges: ItemSet,
+    },
+    /// The last group has less elves than the others.
+    IncompleteGroup {
+        lines: Vec<usize>,
+        missing: usize,
+    },
+}
+
+impl Problem {
+    /// The problem as `line 3: no item is in both compartments`, with the items named in
+    /// `alphabet`.
+    pub fn describe(&self, alphabet: &Alphabet) -> String {
+        let names = |items: &ItemSet| -> String {
+            let names: Vec<String> = items
+                .iter()
+                .filter_map(|priority| alphabet.item(priority))
+                .map(String::from)
+                .collect();
+            names.join(", ")
+        };
+        let lines = |lines: &[usize]| -> String {
+            let numbers: Vec<String> = lines.iter().map(usize::to_string).collect();
+            match lines.len() {
+                1 => format!("line {}", numbers[0]),
+                _ => format!("lines {}", numbers.join(", ")),
+            }
+        };
+        match self {
+            Problem::UnknownItem { line, item } => format!("line {line}: '{item}' is not an item"),
+            Problem::OddLength { line, items } => format!(
+                "line {line}: {items} items can't be split into two compartments of the same size"
+            ),
+            Problem::NoDouble { line } => format!("line {line}: no item is in both compartments"),
+            Problem::SeveralDoubles { line, doubles } => format!(
+                "line {line}: {} items are in both compartments: {}",
+                doubles.len(),
+                names(doubles)
+            ),
+            Problem::NoBadge { lines: l } => format!("{}: the elves share no item", lines(l)),
+            Problem::SeveralBadges { lines: l, badges } => format!(
+                "{}: the elves share {} items: {}",
+                lines(l),
+                badges.len(),
+                names(badges)
+            ),
+            Problem::IncompleteGroup { lines: l, missing } => {
+                format!("{}: the group is missing {missing} elves", lines(l))
+            }
+        }
+    }
+}
+
+/// All problems of the rucksacks, and the sums of the rucksacks and groups without problems.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Report {
+    pub problems: Vec<Problem>,
+    /// Sum of the priorities of the items in both compartments, of rucksacks with exactly one.
+    /// Rucksacks with unknown items or of odd length are left out.
+    pub doubles: u32,
+    /// Sum of the priorities of the badges, of complete groups with exactly one. Groups with a
+    /// rucksack that has unknown items or is of odd length are left out.
+    pub badges: u32,
+}
+
+impl Report {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check every rucksack and every group of `size` elves instead of stopping at the first
+/// problem. Empty lines are skipped, like [`crate::parse_rucksacks`] does.
+pub fn validate(content: &str, alphabet: &Alphabet, size: usize) -> Result<Report, Error> {
+    if size == 0 {
+        return Err(Error::semantic("a group needs at least one elf"));
+    }
+    let mut report = Report::default();
+
+    // line numbers, the priorities of the known items of each rucksack and whether it has unknown
+    // items or odd length, so it can't be trusted
+    let mut rucksacks: Vec<(usize, Vec<u8>, bool)> = Vec::new();
+    for (i, text) in content.lines().enumerate() {
+        let line = i + 1;
+        if text.is_empty() {
+            continue;
+        }
+        let mut priorities = Vec::with_capacity(text.len());
+        let mut flagged = false;
+        for item in text.chars() {
+            match alphabet.priority(item) {
+                Some(priority) => priorities.push(priority),
+                None => {
+                    report.problems.push(Problem::UnknownItem { line, item });
+                    flagged = true;
+                }
+            }
+        }
+        if !priorities.len().is_multiple_of(2) {
+            report.problems.push(Problem::OddLength {
+                line,
+                items: priorities.len(),
+            });
+            flagged = true;
+        }
+        let (c1, c2) = priorities.split_at(priorities.len() / 2);
+        let c1: ItemSet = c1.iter().copied().collect();
+        let c2: ItemSet = c2.iter().copied().collect();
+        let doubles = c1.intersection(&c2);
+        match doubles.len() {
+            0 => report.problems.push(Problem::NoDouble { line }),
+            1 if !flagged => report.doubles += doubles.iter().map(u32::from).sum::<u32>(),
+            1 => {}
+            _ => report
+                .problems
+                .push(Problem::SeveralDoubles { line, doubles }),
+        }
+        rucksacks.push((line, priorities, flagged));
+    }
+
+    for group in rucksacks.chunks(size) {
+        let lines: Vec<usize> = group.iter().map(|(line, _, _)| *line).collect();
+        if group.len() < size {
+            report.problems.push(Problem::IncompleteGroup {
+                lines,
+                missing: size - group.len(),
+            });
+            continue;
+        }
+        let compartments: Vec<(&[u8], &[u8])> = group
+            .iter()
+            .map(|(_, priorities, _)| priorities.split_at(priorities.len() / 2))
+            .collect();
+        let flagged = group.iter().any(|(_, _, flagged)| *flagged);
+        let badges = find_shared(&compartments);
+        match badges.len() {
+            0 => report.problems.push(Problem::NoBadge { lines }),
+            1 if !flagged => report.badges += badges.iter().map(u32::from).sum::<u32>(),
+            1 => {}
+            _ => report
+                .problems
+                .push(Problem::SeveralBadges { lines, badges }),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_lists_all_problems() {
+        // given
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+                     jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+                     PmmdzqPrVvPwwTWBwg\n\
+                     \n\
+                     abcabc\n\
+                     abcd!\n\
+                     xzy\n\
+                     ttgJtRGJQctTZtZT\n";
+        let alphabet = Alphabet::default();
+
+        // when
+        let report = validate(input, &alphabet, 3).expect("expected a valid group size");
+
+        // then
+        let problems: Vec<String> = report
+            .problems
+            .iter()
+            .map(|p| p.describe(&alphabet))
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                "line 5: 3 items are in both compartments: a, b, c",
+                "line 6: '!' is not an item",
+                "line 6: no item is in both compartments",
+                "line 7: 3 items can't be split into two compartments of the same size",
+                "line 7: no item is in both compartments",
+                "lines 5, 6, 7: the elves share no item",
+                "line 8: the group is missing 2 elves",
+            ]
+        );
+        assert_eq!(report.doubles, 16 + 38 + 42 + 20);
+        assert_eq!(report.badges, 18);
+    }
+
+    #[test]
+    fn validate_leaves_out_flagged_rucksacks() {
+        // given
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+                     jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL!\n\
+                     PmmdzqPrVvPwwTWBwg\n";
+        let alphabet = Alphabet::default();
+
+        // when
+        let report = validate(input, &alphabet, 3).expect("expected a valid group size");
+
+        // then
+        assert_eq!(
+            report.problems,
+            [Problem::UnknownItem { line: 2, item: '!' }]
+        );
+        assert_eq!(report.doubles, 16 + 42);
+        assert_eq!(report.badges, 0);
+    }
+}
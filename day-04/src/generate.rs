@@ -0,0 +1,15 @@
+use aoc_common::GenerateOptions;
+
+/// Pairs of section assignments. The size is the number of pairs, the harder the puzzle, the
+/// more sections there are.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let sections = options.pick(9, 99, 999);
+    let mut assignment = || {
+        let from = rng.between(1, sections);
+        format!("{from}-{}", rng.between(from, sections))
+    };
+    (0..options.size_or(1000))
+        .map(|_| format!("{},{}\n", assignment(), assignment()))
+        .collect()
+}
@@ -0,0 +1,157 @@
+//! Day 4: Camp Cleanup
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+
+mod generate;
+mod sections;
+mod sweep;
+
+pub use generate::generate_input;
+pub use sections::{elves, reassign, Coverage, SectionSet};
+pub use sweep::{sweep, Overlaps};
+
+/// Solves both parts of day 4 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 4;
+    type Input<'a> = Vec<(Assignment, Assignment)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_pairs(input)
+    }
+    fn part1(pairs: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(find_containing_pairs(pairs).into())
+    }
+    fn part2(pairs: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(find_overlapping_pairs(pairs).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// The sections an elf has to clean, from and to are inclusive.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Assignment(pub u32, pub u32);
+
+impl Assignment {
+    pub fn contains(&self, other: &Assignment) -> bool {
+        self.0 <= other.0 && self.1 >= other.1
+    }
+    pub fn overlaps(&self, other: &Assignment) -> bool {
+        self.0 <= other.0 && self.1 >= other.0 || other.0 <= self.0 && other.1 >= self.0
+    }
+}
+
+pub fn parse_assignment(input: &str) -> Result<Assignment, Error> {
+    parse_assignment_in_line(input, input)
+}
+
+// `input` is a part of `line`, which is only needed for the error location
+fn parse_assignment_in_line(line: &str, input: &str) -> Result<Assignment, Error> {
+    let (from, to) = input
+        .split_once('-')
+        .ok_or_else(|| Error::syntax(line, input, "missing '-' in assignment"))?;
+    Ok(Assignment(
+        parse_number(line, from)?,
+        parse_number(line, to)?,
+    ))
+}
+
+pub fn parse_pair(line: &str) -> Result<(Assignment, Assignment), Error> {
+    let (first, second) = line
+        .split_once(',')
+        .ok_or_else(|| Error::syntax(line, line, "missing ',' between assignments"))?;
+    Ok((
+        parse_assignment_in_line(line, first)?,
+        parse_assignment_in_line(line, second)?,
+    ))
+}
+
+/// Parse one pair of assignments per line.
+pub fn parse_pairs(content: &str) -> Result<Vec<(Assignment, Assignment)>, Error> {
+    parse_lines(content, parse_pair)
+}
+
+/// Number of pairs where one assignment fully contains the other.
+pub fn find_containing_pairs(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs
+        .iter()
+        .filter(|(a, b)| a.contains(b) || b.contains(a))
+        .count()
+}
+
+/// Number of pairs where the assignments overlap at all.
+pub fn find_overlapping_pairs(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs.iter().filter(|(a, b)| a.overlaps(b)).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+"#;
+
+    #[test]
+    fn find_containing_pairs_works_for_example() {
+        // given
+        let pairs = parse_pairs(EXAMPLE).expect("Expected successful parsing");
+
+        // when
+        let c = find_containing_pairs(&pairs);
+
+        // then
+        assert_eq!(c, 2);
+    }
+
+    #[test]
+    fn find_overlapping_pairs_works_for_example() {
+        // given
+        let pairs = parse_pairs(EXAMPLE).expect("Expected successful parsing");
+
+        // when
+        let c = find_overlapping_pairs(&pairs);
+
+        // then
+        assert_eq!(c, 4);
+    }
+
+    #[test]
+    fn parse_pairs_reports_location_of_invalid_number() {
+        // when
+        let result = parse_pairs("2-4,6-8\n2-3,4-x5\n");
+
+        // then
+        let error = result.expect_err("expected parsing to fail");
+        let location = error.location.expect("expected location");
+        assert_eq!((location.line, location.column, location.len), (2, 7, 2));
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_pairs(input);
+            let _ = parse_pair(input);
+            let _ = parse_assignment(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -1,13 +1,40 @@
+use aoc_common::{inputs_from_args, run_main, split_text_args, take_option, Error};
+use day_04::{
+    elves, find_containing_pairs, find_overlapping_pairs, parse_pairs, reassign, sweep, Assignment,
+    Coverage, SectionSet, Solver,
+};
 use std::env;
-use std::fs::read_to_string;
-use std::path::Path;
 
-fn main() -> Result<(), String> {
-    let filename = env::args()
-        .nth(1)
-        .ok_or_else(|| "No file name given.".to_owned())?;
-    let content = read_to_string(Path::new(&filename)).map_err(|e| e.to_string())?;
-    let pairs = parse_pairs(&content)?;
+fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let more_than = take_option(&mut args, "--more-than")?
+        .map(|k| {
+            k.parse::<usize>()
+                .map_err(|_| Error::semantic(format!("'{k}' is not a number of elves")))
+        })
+        .transpose()?;
+    let team = args.iter().any(|arg| arg == "--team");
+    let sweeping = args.iter().any(|arg| arg == "--sweep");
+    args.retain(|arg| arg != "--team" && arg != "--sweep");
+    if !team && !sweeping && more_than.is_none() {
+        return run_main::<Solver>(print_answers);
+    }
+
+    let positional = split_text_args(args)?;
+    for input in inputs_from_args(&positional) {
+        let pairs = parse_pairs(&input.read()?)?;
+        if team || more_than.is_some() {
+            print_team(&pairs, more_than.unwrap_or(1));
+        }
+        if sweeping {
+            print_sweep(&pairs);
+        }
+    }
+    Ok(())
+}
+
+fn print_answers(content: &str) -> Result<(), Error> {
+    let pairs = parse_pairs(content)?;
 
     let containing = find_containing_pairs(&pairs);
     println!("There are {containing} pairs where one completely contains the other.");
@@ -18,85 +45,48 @@ fn main() -> Result<(), String> {
     Ok(())
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
-struct Assignment(u32, u32);
-
-impl Assignment {
-    fn contains(&self, other: &Assignment) -> bool {
-        self.0 <= other.0 && self.1 >= other.1
+// what the elves of all lines clean together, and how to do it without cleaning twice
+fn print_team(pairs: &[(Assignment, Assignment)], more_than: usize) {
+    let elves = elves(pairs);
+    let coverage = Coverage::new(&elves);
+    let cleaned = coverage.more_than(0);
+    let (Some(first), Some(last)) = (cleaned.ranges().first(), cleaned.ranges().last()) else {
+        println!("Apparently, there is nothing to clean");
+        return;
+    };
+    let camp = SectionSet::from(Assignment(first.0, last.1));
+
+    println!("Sections nobody cleans: {}", coverage.nobody(&camp));
+    println!("Sections exactly one elf cleans: {}", coverage.exactly(1));
+    println!(
+        "Sections more than {more_than} elves clean: {}",
+        coverage.more_than(more_than)
+    );
+
+    let reassigned = reassign(&elves);
+    let before: u64 = elves.iter().map(|a| SectionSet::from(*a).len()).sum();
+    println!(
+        "Without cleaning anything twice, the elves clean {} sections instead of {before}:",
+        cleaned.len()
+    );
+    for (elf, (old, new)) in elves.iter().zip(&reassigned).enumerate() {
+        if SectionSet::from(*old) != *new {
+            println!("    elf {} (line {}): {new}", elf + 1, elf / 2 + 1);
+        }
     }
-    fn overlaps(&self, other: &Assignment) -> bool {
-        self.0 <= other.0 && self.1 >= other.0 || other.0 <= self.0 && other.1 >= self.0
-    }
-}
-
-fn parse_assignment(input: &str) -> Result<Assignment, String> {
-    let (from, to) = input
-        .split_once('-')
-        .ok_or_else(|| format!("Unable to parse assignment '{input}': Missing '-'"))?;
-    Ok(Assignment(
-        from.parse::<u32>()
-            .map_err(|e| format!("Unable to parse assignment '{input}': {e}"))?,
-        to.parse::<u32>()
-            .map_err(|e| format!("Unable to parse assignment '{input}': {e}"))?,
-    ))
-}
-
-fn parse_pair(line: &str) -> Result<(Assignment, Assignment), String> {
-    let (first, second) = line
-        .split_once(',')
-        .ok_or_else(|| format!("Unable to parse line '{line}': missing ','"))?;
-    Ok((parse_assignment(first)?, parse_assignment(second)?))
-}
-
-fn parse_pairs(content: &str) -> Result<Vec<(Assignment, Assignment)>, String> {
-    content.lines().map(parse_pair).collect()
-}
-
-fn find_containing_pairs(pairs: &[(Assignment, Assignment)]) -> usize {
-    pairs
-        .iter()
-        .filter(|(a, b)| a.contains(b) || b.contains(a))
-        .count()
 }
 
-fn find_overlapping_pairs(pairs: &[(Assignment, Assignment)]) -> usize {
-    pairs.iter().filter(|(a, b)| a.overlaps(b)).count()
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const EXAMPLE: &str = r#"2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8
-"#;
-
-    #[test]
-    fn find_containing_pairs_works_for_example() {
-        // given
-        let pairs = parse_pairs(EXAMPLE).expect("Expected successful parsing");
-
-        // when
-        let c = find_containing_pairs(&pairs);
-
-        // then
-        assert_eq!(c, 2);
-    }
-
-    #[test]
-    fn find_overlapping_pairs_works_for_example() {
-        // given
-        let pairs = parse_pairs(EXAMPLE).expect("Expected successful parsing");
-
-        // when
-        let c = find_overlapping_pairs(&pairs);
-
-        // then
-        assert_eq!(c, 4);
+// the overlaps between the elves of all lines
+fn print_sweep(pairs: &[(Assignment, Assignment)]) {
+    let elves = elves(pairs);
+    let overlaps = sweep(&elves);
+    println!(
+        "At most {} elves clean the same section, in sections {}",
+        overlaps.max_elves, overlaps.busiest
+    );
+    println!("{} pairs of elves overlap:", overlaps.pairs.len());
+    let describe = |elf: usize| format!("elf {} (line {})", elf + 1, elf / 2 + 1);
+    for (a, b) in &overlaps.pairs {
+        println!("    {} and {}", describe(*a), describe(*b));
     }
 }
@@ -0,0 +1,256 @@
+use crate::Assignment;
+use std::fmt;
+
+/// A set of sections, kept as sorted ranges that neither overlap nor touch.
+#[derive(Clone, Default, PartialEq, Eq, Debug, Hash)]
+pub struct SectionSet {
+    ranges: Vec<Assignment>,
+}
+
+impl From<Assignment> for SectionSet {
+    fn from(assignment: Assignment) -> Self {
+        [assignment].into_iter().collect()
+    }
+}
+
+impl FromIterator<Assignment> for SectionSet {
+    /// The union of the ranges. Ranges that end before they start are empty.
+    fn from_iter<I: IntoIterator<Item = Assignment>>(ranges: I) -> Self {
+        let mut ranges: Vec<Assignment> = ranges.into_iter().filter(|r| r.0 <= r.1).collect();
+        ranges.sort_unstable();
+        let mut merged: Vec<Assignment> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if touches(last, &range) => last.1 = last.1.max(range.1),
+                _ => merged.push(range),
+            }
+        }
+        SectionSet { ranges: merged }
+    }
+}
+
+// `b` doesn't start after the section following `a`
+fn touches(a: &Assignment, b: &Assignment) -> bool {
+    b.0 as u64 <= a.1 as u64 + 1
+}
+
+impl SectionSet {
+    pub fn new() -> SectionSet {
+        SectionSet::default()
+    }
+
+    /// The ranges of the set, in increasing order.
+    pub fn ranges(&self) -> &[Assignment] {
+        &self.ranges
+    }
+
+    /// The number of sections.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| (r.1 - r.0) as u64 + 1).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < section);
+        self.ranges.get(i).is_some_and(|r| r.0 <= section)
+    }
+
+    pub fn union(&self, other: &SectionSet) -> SectionSet {
+        self.ranges.iter().chain(&other.ranges).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &SectionSet) -> SectionSet {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let (from, to) = (a.0.max(b.0), a.1.min(b.1));
+            if from <= to {
+                ranges.push(Assignment(from, to));
+            }
+            // the range that ends first can't overlap anything else
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        SectionSet { ranges }
+    }
+
+    pub fn difference(&self, other: &SectionSet) -> SectionSet {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for a in &self.ranges {
+            // the part of `a` not removed yet starts here
+            let mut from = a.0 as u64;
+            while let Some(b) = other.ranges.get(j) {
+                if b.1 < a.0 {
+                    j += 1;
+                    continue;
+                }
+                if b.0 > a.1 {
+                    break;
+                }
+                if (b.0 as u64) > from {
+                    ranges.push(Assignment(from as u32, b.0 - 1));
+                }
+                from = b.1 as u64 + 1;
+                if b.1 >= a.1 {
+                    break;
+                }
+                j += 1;
+            }
+            if from <= a.1 as u64 {
+                ranges.push(Assignment(from as u32, a.1));
+            }
+        }
+        SectionSet { ranges }
+    }
+}
+
+impl fmt::Display for SectionSet {
+    /// The ranges as `2-4, 6, 8-9`, or `none`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "none");
+        }
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match range.0 == range.1 {
+                true => write!(f, "{}", range.0)?,
+                false => write!(f, "{}-{}", range.0, range.1)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How many elves clean each section, as the sets of sections cleaned by more than 0, 1, 2, …
+/// elves.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Coverage {
+    // levels[k]: the sections cleaned by more than k elves
+    levels: Vec<SectionSet>,
+}
+
+impl Coverage {
+    pub fn new<'a>(assignments: impl IntoIterator<Item = &'a Assignment>) -> Coverage {
+        let mut coverage = Coverage::default();
+        for assignment in assignments {
+            coverage.add(*assignment);
+        }
+        coverage
+    }
+
+    pub fn add(&mut self, assignment: Assignment) {
+        let mut new = SectionSet::from(assignment);
+        // the sections already cleaned by more than k elves are now cleaned by more than k + 1
+        for level in &mut self.levels {
+            let both = level.intersection(&new);
+            *level = level.union(&new);
+            new = both;
+            if new.is_empty() {
+                return;
+            }
+        }
+        self.levels.push(new);
+    }
+
+    /// The sections cleaned by more than `k` elves.
+    pub fn more_than(&self, k: usize) -> SectionSet {
+        self.levels.get(k).cloned().unwrap_or_default()
+    }
+
+    /// The sections cleaned by exactly `k` elves, `k` has to be at least 1.
+    pub fn exactly(&self, k: usize) -> SectionSet {
+        match k.checked_sub(1) {
+            Some(fewer) => self.more_than(fewer).difference(&self.more_than(k)),
+            None => SectionSet::new(),
+        }
+    }
+
+    /// The sections of `within` that no elf cleans.
+    pub fn nobody(&self, within: &SectionSet) -> SectionSet {
+        within.difference(&self.more_than(0))
+    }
+}
+
+/// Every elf of the pairs, two per line.
+pub fn elves(pairs: &[(Assignment, Assignment)]) -> Vec<Assignment> {
+    pairs.iter().flat_map(|(a, b)| [*a, *b]).collect()
+}
+
+/// New assignments so that every section is cleaned by exactly one of the elves that cleaned it
+/// before, which is the least work that still covers everything. Elves with longer assignments
+/// keep theirs, the others keep what no elf before them cleans.
+pub fn reassign(assignments: &[Assignment]) -> Vec<SectionSet> {
+    let mut order: Vec<usize> = (0..assignments.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(assignments[i].1.saturating_sub(assignments[i].0)));
+    let mut taken = SectionSet::new();
+    let mut result = vec![SectionSet::new(); assignments.len()];
+    for i in order {
+        let own = SectionSet::from(assignments[i]);
+        result[i] = own.difference(&taken);
+        taken = taken.union(&own);
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(ranges: &[(u32, u32)]) -> SectionSet {
+        ranges
+            .iter()
+            .map(|&(from, to)| Assignment(from, to))
+            .collect()
+    }
+
+    #[test]
+    fn section_sets_support_set_operations() {
+        // given
+        let a = set(&[(1, 3), (4, 6), (10, 12)]);
+        let b = set(&[(2, 2), (5, 11), (u32::MAX, u32::MAX)]);
+
+        // when
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        let difference = a.difference(&b);
+
+        // then
+        assert_eq!(a, set(&[(1, 6), (10, 12)]));
+        assert_eq!(union.to_string(), format!("1-12, {}", u32::MAX));
+        assert_eq!(intersection.to_string(), "2, 5-6, 10-11");
+        assert_eq!(difference.to_string(), "1, 3-4, 12");
+        assert_eq!(b.difference(&a).to_string(), format!("7-9, {}", u32::MAX));
+        assert_eq!(difference.len(), 4);
+        assert!(difference.contains(4) && !difference.contains(5));
+    }
+
+    #[test]
+    fn coverage_and_reassign_work_for_example() {
+        // given
+        let pairs = crate::parse_pairs("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n")
+            .expect("expected successful parsing");
+        let elves = elves(&pairs);
+
+        // when
+        let coverage = Coverage::new(&elves);
+        let reassigned = reassign(&elves);
+
+        // then
+        assert_eq!(coverage.nobody(&set(&[(1, 10)])).to_string(), "1, 10");
+        assert_eq!(coverage.exactly(1).to_string(), "9");
+        assert_eq!(coverage.more_than(5).to_string(), "4-7");
+        let total: u64 = reassigned.iter().map(SectionSet::len).sum();
+        assert_eq!(total, coverage.more_than(0).len());
+        assert_eq!(reassigned[6].to_string(), "2-8");
+        assert_eq!(reassigned[5].to_string(), "9");
+    }
+}
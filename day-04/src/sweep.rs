@@ -0,0 +1,87 @@
+use crate::{Assignment, SectionSet};
+use std::collections::BTreeSet;
+
+/// How the assignments of all elves overlap, found by [`sweep`].
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct Overlaps {
+    /// The most elves that clean the same section.
+    pub max_elves: usize,
+    /// The sections cleaned by that many elves.
+    pub busiest: SectionSet,
+    /// Every pair of elves with overlapping assignments, as indices into the assignments. The
+    /// lower index comes first, and the pairs are sorted.
+    pub pairs: Vec<(usize, usize)>,
+}
+
+/// Walk once over the starts and ends of all assignments, in O(n log n) plus the number of
+/// overlapping pairs. Assignments that end before they start are left out.
+pub fn sweep(assignments: &[Assignment]) -> Overlaps {
+    // (section, is_start, elf); an assignment ends after its last section, and ends come before
+    // starts at the same section, so touching assignments don't overlap
+    let mut events: Vec<(u64, bool, usize)> = Vec::with_capacity(assignments.len() * 2);
+    for (elf, a) in assignments.iter().enumerate().filter(|(_, a)| a.0 <= a.1) {
+        events.push((a.0 as u64, true, elf));
+        events.push((a.1 as u64 + 1, false, elf));
+    }
+    events.sort_unstable();
+
+    let mut overlaps = Overlaps::default();
+    let mut busiest = Vec::new();
+    let mut active: BTreeSet<usize> = BTreeSet::new();
+    let mut i = 0;
+    while i < events.len() {
+        let section = events[i].0;
+        while let Some(&(_, is_start, elf)) = events.get(i).filter(|e| e.0 == section) {
+            if is_start {
+                overlaps
+                    .pairs
+                    .extend(active.iter().map(|&other| (other.min(elf), other.max(elf))));
+                active.insert(elf);
+            } else {
+                active.remove(&elf);
+            }
+            i += 1;
+        }
+        // the number of elves stays the same up to the next event
+        if active.is_empty() || active.len() < overlaps.max_elves {
+            continue;
+        }
+        if active.len() > overlaps.max_elves {
+            overlaps.max_elves = active.len();
+            busiest.clear();
+        }
+        // there is always another event while elves are active
+        let next = events[i].0;
+        busiest.push(Assignment(section as u32, (next - 1) as u32));
+    }
+
+    overlaps.busiest = busiest.into_iter().collect();
+    overlaps.pairs.sort_unstable();
+    overlaps
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sweep_finds_overlaps_across_lines() {
+        // given
+        let assignments = [
+            Assignment(2, 4),
+            Assignment(6, 8),
+            Assignment(5, 5),
+            Assignment(3, 6),
+            Assignment(8, u32::MAX),
+            Assignment(9, 1),
+        ];
+
+        // when
+        let overlaps = sweep(&assignments);
+
+        // then
+        assert_eq!(overlaps.max_elves, 2);
+        assert_eq!(overlaps.busiest.to_string(), "3-6, 8");
+        assert_eq!(overlaps.pairs, [(0, 3), (1, 3), (1, 4), (2, 3)]);
+    }
+}
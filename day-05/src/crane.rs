@@ -0,0 +1,200 @@
+use crate::{Instruction, Stack};
+use aoc_common::Error;
+
+/// A crane that carries out the instructions. Each move reports its cost, the number of times
+/// the crane has to lift crates, so one list of instructions can be compared across cranes.
+pub trait Crane {
+    fn name(&self) -> String;
+
+    /// Move the top `count` crates from stack `from` to stack `to`, counting from 0. Both stacks
+    /// exist, they differ and `from` has enough crates. Returns the cost.
+    fn lift(&mut self, stacks: &mut [Stack], from: usize, to: usize, count: usize) -> u64;
+
+    /// Check and run the `n`th instruction, returning its cost.
+    fn run_instruction(
+        &mut self,
+        stacks: &mut [Stack],
+        n: usize,
+        inst: &Instruction,
+    ) -> Result<u64, Error> {
+        check_stacks(n, inst, stacks)?;
+        let (from, to) = (inst.from - 1, inst.to - 1);
+        if inst.count > stacks[from].len() {
+            return Err(Error::semantic(format!(
+                "Instruction {n} is trying to pick up {} items from stack {}, but it only has {} items",
+                inst.count,
+                inst.from,
+                stacks[from].len()
+            )));
+        }
+        if from == to || inst.count == 0 {
+            return Ok(0);
+        }
+        Ok(self.lift(stacks, from, to, inst.count))
+    }
+
+    /// Run all instructions, returning the stacks at the end and the cost of each move.
+    fn run(
+        &mut self,
+        mut stacks: Vec<Stack>,
+        instructions: &[Instruction],
+    ) -> Result<(Vec<Stack>, Vec<u64>), Error> {
+        let costs = instructions
+            .iter()
+            .enumerate()
+            .map(|(n, inst)| self.run_instruction(&mut stacks, n + 1, inst))
+            .collect::<Result<_, _>>()?;
+        Ok((stacks, costs))
+    }
+}
+
+// make sure both stacks of the instruction exist
+fn check_stacks(n: usize, inst: &Instruction, stacks: &[Stack]) -> Result<(), Error> {
+    for (name, stack) in [("from", inst.from), ("to", inst.to)] {
+        if stack == 0 || stack > stacks.len() {
+            return Err(Error::semantic(format!(
+                "Faulty instruction {n}, referencing out-of-bounds {name}-stack {stack}/{}",
+                stacks.len()
+            )));
+        }
+    }
+    Ok(())
+}
+
+// move the top `count` crates at once, keeping or reversing their order
+fn move_block(stacks: &mut [Stack], from: usize, to: usize, count: usize, reverse: bool) {
+    let bottom = stacks[from].len() - count;
+    let mut block = stacks[from].split_off(bottom);
+    if reverse {
+        block.reverse();
+    }
+    stacks[to].append(&mut block);
+}
+
+/// Moves one crate at a time, so a block ends up upside down.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_owned()
+    }
+
+    fn lift(&mut self, stacks: &mut [Stack], from: usize, to: usize, count: usize) -> u64 {
+        move_block(stacks, from, to, count, true);
+        count as u64
+    }
+}
+
+/// Moves all crates of an instruction at once.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_owned()
+    }
+
+    fn lift(&mut self, stacks: &mut [Stack], from: usize, to: usize, count: usize) -> u64 {
+        move_block(stacks, from, to, count, false);
+        1
+    }
+}
+
+/// Moves at most `capacity` crates at once, the top ones first. With a capacity of 1 it works
+/// like the CrateMover 9000, with a capacity as large as the blocks like the CrateMover 9001.
+#[derive(Clone, Copy, Debug)]
+pub struct LimitedCrane {
+    pub capacity: usize,
+}
+
+impl Crane for LimitedCrane {
+    fn name(&self) -> String {
+        format!("crane lifting at most {} crates", self.capacity)
+    }
+
+    fn lift(&mut self, stacks: &mut [Stack], from: usize, to: usize, count: usize) -> u64 {
+        let capacity = self.capacity.max(1);
+        let mut left = count;
+        while left > 0 {
+            let lifted = left.min(capacity);
+            move_block(stacks, from, to, lifted, false);
+            left -= lifted;
+        }
+        count.div_ceil(capacity) as u64
+    }
+}
+
+/// Moves all crates of an instruction at once, but turns every other block upside down: the
+/// first block keeps its order, the second is reversed, and so on.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct AlternatingCrane {
+    reverse_next: bool,
+}
+
+impl Crane for AlternatingCrane {
+    fn name(&self) -> String {
+        "crane reversing every other block".to_owned()
+    }
+
+    fn lift(&mut self, stacks: &mut [Stack], from: usize, to: usize, count: usize) -> u64 {
+        move_block(stacks, from, to, count, self.reverse_next);
+        self.reverse_next = !self.reverse_next;
+        1
+    }
+}
+
+/// The crane called `9000`, `9001`, `limited-<capacity>` or `alternating`.
+pub fn crane(name: &str) -> Result<Box<dyn Crane>, Error> {
+    match name {
+        "9000" => Ok(Box::new(CrateMover9000)),
+        "9001" => Ok(Box::new(CrateMover9001)),
+        "alternating" => Ok(Box::<AlternatingCrane>::default()),
+        _ => match name.strip_prefix("limited-").map(str::parse) {
+            Some(Ok(capacity)) if capacity > 0 => Ok(Box::new(LimitedCrane { capacity })),
+            _ => Err(Error::semantic(format!(
+                "'{name}' is no crane, try 9000, 9001, limited-<capacity> or alternating"
+            ))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{get_stack_tops, parse_input};
+
+    #[test]
+    fn cranes_can_be_compared() {
+        // given
+        let (stacks, instructions) = parse_input(
+            "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\n\
+             move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n",
+        )
+        .expect("expected successful parsing");
+        let names = ["9000", "9001", "limited-2", "alternating"];
+
+        // when
+        let runs: Vec<(String, u64)> = names
+            .iter()
+            .map(|name| {
+                let (stacks, costs) = crane(name)
+                    .and_then(|mut crane| crane.run(stacks.clone(), &instructions))
+                    .expect("expected successful run");
+                (get_stack_tops(&stacks), costs.iter().sum())
+            })
+            .collect();
+
+        // then
+        assert_eq!(
+            runs,
+            [
+                ("CMZ".to_owned(), 7),
+                ("MCD".to_owned(), 4),
+                ("MCZ".to_owned(), 5),
+                ("MCZ".to_owned(), 4),
+            ]
+        );
+        assert!(crane("limited-0").is_err());
+    }
+}
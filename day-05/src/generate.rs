@@ -0,0 +1,48 @@
+use aoc_common::GenerateOptions;
+
+const STACKS: usize = 9;
+
+/// The drawing of nine stacks and moves that never take more crates than there are on a stack.
+/// The size is the number of moves, the harder the puzzle, the higher the stacks and the more
+/// crates are moved at once.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let (max_height, max_count) = options.pick((4, 3), (8, 10), (20, 30));
+    let stacks: Vec<Vec<char>> = (0..STACKS)
+        .map(|_| {
+            // no empty stacks at the start, so the drawing shows how many stacks there are
+            (0..rng.between(1, max_height))
+                .map(|_| char::from(b'A' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+
+    let mut lines: Vec<String> = Vec::with_capacity(options.size_or(500) + 32);
+    let top = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+    for level in (0..top).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(item) => format!("[{item}]"),
+                None => "   ".to_owned(),
+            })
+            .collect();
+        lines.push(row.join(" ").trim_end().to_owned());
+    }
+    let numbers: Vec<String> = (1..=STACKS).map(|i| format!(" {i} ")).collect();
+    lines.push(numbers.join(" "));
+    lines.push(String::new());
+
+    // only the heights matter for valid moves, and both cranes keep the same heights
+    let mut heights: Vec<i64> = stacks.iter().map(|stack| stack.len() as i64).collect();
+    for _ in 0..options.size_or(500) {
+        let non_empty: Vec<usize> = (0..STACKS).filter(|i| heights[*i] > 0).collect();
+        let from = *rng.choose(&non_empty);
+        let to = (from + 1 + rng.below(STACKS - 1)) % STACKS;
+        let count = rng.between(1, heights[from].min(max_count));
+        heights[from] -= count;
+        heights[to] += count;
+        lines.push(format!("move {count} from {} to {}", from + 1, to + 1));
+    }
+    lines.join("\n") + "\n"
+}
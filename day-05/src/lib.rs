@@ -0,0 +1,317 @@
+//! Day 5: Supply Stacks
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+use std::fmt;
+
+mod crane;
+mod generate;
+mod plan;
+mod validate;
+
+pub use crane::{crane, AlternatingCrane, Crane, CrateMover9000, CrateMover9001, LimitedCrane};
+pub use generate::generate_input;
+pub use plan::plan;
+pub use validate::{validate_input, validate_instructions, Fault, Field};
+
+/// Solves both parts of day 5 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 5;
+    type Input<'a> = (Vec<Stack>, Vec<Instruction>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1((stacks, instructions): &Self::Input<'_>) -> Result<Answer, Error> {
+        let done_stacks = run_instructions(stacks.clone(), instructions)?;
+        Ok(get_stack_tops(&done_stacks).into())
+    }
+    fn part2((stacks, instructions): &Self::Input<'_>) -> Result<Answer, Error> {
+        let done_stacks = its_over_9000(stacks.clone(), instructions)?;
+        Ok(get_stack_tops(&done_stacks).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// A stack of crates, bottom crate first.
+pub type Stack = Vec<char>;
+
+/// Move `count` crates from stack `from` to stack `to`. Stacks are numbered from 1.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Instruction {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl fmt::Display for Instruction {
+    /// The instruction as in the puzzle input, `move 1 from 2 to 1`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.count, self.from, self.to)
+    }
+}
+
+/// Parse the drawing of the initial stacks and the instructions below it.
+pub fn parse_input(input: &str) -> Result<(Vec<Stack>, Vec<Instruction>), Error> {
+    let (stack_input, inst_input) = input
+        .split_once("\n\n")
+        .ok_or_else(|| Error::semantic("Unable to split input between stacks and instructions"))?;
+    let stacks = parse_stacks(stack_input)?;
+
+    // the instructions start after the stacks and the empty line
+    let instructions: Vec<Instruction> = parse_lines(inst_input, parse_instruction)
+        .map_err(|e| e.offset_lines(stack_input.lines().count() + 1))?;
+
+    Ok((stacks, instructions))
+}
+
+/// Parse the drawing of the stacks, as the one at the top of the puzzle input.
+pub fn parse_stacks(stack_input: &str) -> Result<Vec<Stack>, Error> {
+    let mut stacks: Vec<Stack> = Vec::with_capacity(16);
+    for (line_no, line) in stack_input.lines().enumerate() {
+        // stop as soon as the stack numbers show up
+        // (of course, if the input is malformed, this may just discard everything after that)
+        if line.starts_with(" 1 ") {
+            // stacks that are empty at the start only show up here
+            let count = line.split_whitespace().count();
+            if stacks.len() < count {
+                stacks.resize_with(count, || Vec::with_capacity(26));
+            }
+            break;
+        }
+        // for the stack representation, it is actually easier to work on a byte slice
+        for (i, chunk) in line
+            .as_bytes()
+            .chunks(4)
+            .map(|c| if c.len() == 4 { &c[..3] } else { c })
+            .enumerate()
+        {
+            if stacks.len() < i + 1 {
+                stacks.push(Vec::with_capacity(26));
+            }
+            if chunk != b"   " {
+                let item_error = |msg: &str| {
+                    let span = line.get(i * 4..i * 4 + chunk.len()).unwrap_or(line);
+                    Error::syntax(line, span, msg).at_line(line_no + 1)
+                };
+                let item = chunk
+                    .strip_prefix(b"[")
+                    .ok_or_else(|| item_error("item is missing open bracket"))?
+                    .strip_suffix(b"]")
+                    .ok_or_else(|| item_error("item is missing close bracket"))?;
+                let item = *item
+                    .first()
+                    .ok_or_else(|| item_error("item is missing an identifier"))?;
+                stacks[i].push(char::from(item));
+            }
+        }
+    }
+    for stack in &mut stacks {
+        stack.reverse();
+    }
+    Ok(stacks)
+}
+
+pub fn parse_instruction(line: &str) -> Result<Instruction, Error> {
+    let (c, loc) = line
+        .split_once(" from ")
+        .ok_or_else(|| Error::syntax(line, line, "missing ' from '"))?;
+    let c = c
+        .strip_prefix("move ")
+        .ok_or_else(|| Error::syntax(line, c, "missing 'move '"))?;
+    let count: usize = parse_number(line, c)?;
+
+    let (from_str, to_str) = loc
+        .split_once(" to ")
+        .ok_or_else(|| Error::syntax(line, loc, "missing ' to '"))?;
+    let from: usize = parse_number(line, from_str)?;
+    let to: usize = parse_number(line, to_str)?;
+
+    Ok(Instruction { count, from, to })
+}
+
+/// Run the instructions with a CrateMover 9000, which moves one crate at a time.
+pub fn run_instructions(
+    stacks: Vec<Stack>,
+    instructions: &[Instruction],
+) -> Result<Vec<Stack>, Error> {
+    Ok(CrateMover9000.run(stacks, instructions)?.0)
+}
+
+/// Run the `n`th instruction with a CrateMover 9000, for watching the stacks step by step.
+pub fn run_instruction(stacks: &mut [Stack], n: usize, inst: &Instruction) -> Result<(), Error> {
+    CrateMover9000.run_instruction(stacks, n, inst).map(|_| ())
+}
+
+/// Run the instructions with a CrateMover 9001, which moves several crates at once.
+pub fn its_over_9000(
+    stacks: Vec<Stack>,
+    instructions: &[Instruction],
+) -> Result<Vec<Stack>, Error> {
+    Ok(CrateMover9001.run(stacks, instructions)?.0)
+}
+
+/// The crates on top of each stack. Empty stacks are represented by a space.
+pub fn get_stack_tops(stacks: &[Stack]) -> String {
+    stacks
+        .iter()
+        .map(|s| s.last().copied().unwrap_or(' '))
+        .collect()
+}
+
+/// Draw the stacks like the puzzle input does, with the numbers of the stacks below them.
+pub fn render_stacks(stacks: &[Stack]) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut drawing = String::new();
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(item) => format!("[{item}]"),
+                None => "   ".to_owned(),
+            })
+            .collect();
+        drawing += row.join(" ").trim_end();
+        drawing.push('\n');
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!("{n:^3}")).collect();
+    drawing += numbers.join(" ").trim_end();
+    drawing.push('\n');
+    drawing
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::{fuzz, Difficulty};
+
+    const EXAMPLE: &str = r#"    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+"#;
+
+    #[test]
+    fn run_instructions_works_for_example() {
+        // given
+        let (stacks, instructions) = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let result = run_instructions(stacks, &instructions);
+
+        // then
+        let result_stacks = result.expect("expected successful run");
+        assert_eq!(&get_stack_tops(&result_stacks), "CMZ");
+    }
+    #[test]
+    fn its_over_9000_works_for_example() {
+        // given
+        let (stacks, instructions) = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let result = its_over_9000(stacks, &instructions);
+
+        // then
+        let result_stacks = result.expect("expected successful run");
+        assert_eq!(&get_stack_tops(&result_stacks), "MCD");
+    }
+
+    #[test]
+    fn render_stacks_round_trips_with_parse_input() {
+        // given
+        let (stacks, instructions) = parse_input(EXAMPLE).expect("expected successful parsing");
+        let moved =
+            run_instructions(stacks.clone(), &instructions[..2]).expect("expected successful run");
+
+        // when
+        let drawing = render_stacks(&stacks);
+        let moved_drawing = render_stacks(&moved);
+
+        // then
+        let listed: Vec<String> = instructions.iter().map(|i| format!("{i}\n")).collect();
+        assert_eq!(format!("{drawing}\n{}", listed.concat()), EXAMPLE);
+        assert_eq!(
+            moved_drawing,
+            "        [Z]\n        [N]\n    [C] [D]\n    [M] [P]\n 1   2   3\n"
+        );
+        let (parsed, _) = parse_input(&format!("{moved_drawing}\nmove 1 from 1 to 2\n"))
+            .expect("expected successful parsing");
+        assert_eq!(parsed, moved);
+        let empty = vec![vec!['A'], vec![], vec![]];
+        let (parsed, _) = parse_input(&format!("{}\n", render_stacks(&empty)))
+            .expect("expected successful parsing");
+        assert_eq!(parsed, empty);
+    }
+
+    #[test]
+    fn generated_inputs_survive_printing_and_parsing() {
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            for seed in 0..10 {
+                // given
+                let input = generate_input(&GenerateOptions {
+                    size: Some(20),
+                    seed,
+                    difficulty,
+                });
+                let parsed = parse_input(&input).expect("expected successful parsing");
+
+                // when
+                let listed: Vec<String> = parsed.1.iter().map(|i| format!("{i}\n")).collect();
+                let printed = format!("{}\n{}", render_stacks(&parsed.0), listed.concat());
+
+                // then
+                assert_eq!(
+                    parse_input(&printed),
+                    Ok(parsed),
+                    "seed {seed}, {difficulty:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parse_input_reports_line_of_faulty_instruction() {
+        // given
+        let input = EXAMPLE.replace("move 2 from 2 to 1", "move 2 frm 2 to 1");
+
+        // when
+        let result = parse_input(&input);
+
+        // then
+        let error = result.expect_err("expected parsing to fail");
+        assert_eq!(
+            error.to_string(),
+            r#"syntax error in line 8, column 1: missing ' from '
+  |
+8 | move 2 frm 2 to 1
+  | ^^^^^^^^^^^^^^^^^"#
+        );
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_instruction(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -1,14 +1,48 @@
+use aoc_common::{
+    inputs_from_args, run_main_with_render, split_text_args, take_option, Error, Input,
+};
+use day_05::{
+    crane, get_stack_tops, its_over_9000, parse_input, parse_stacks, plan, render_stacks,
+    run_instructions, validate_input, Crane, Solver,
+};
 use std::env;
-use std::fs::read_to_string;
-use std::path::Path;
 
-fn main() -> Result<(), String> {
-    let filename = env::args()
-        .nth(1)
-        .ok_or_else(|| "No file name given.".to_owned())?;
-    let content = read_to_string(Path::new(&filename)).map_err(|e| e.to_string())?;
+fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let cranes = take_option(&mut args, "--crane")?;
+    let target = take_option(&mut args, "--plan")?;
+    let steps = args.iter().any(|arg| arg == "--steps");
+    let checking = args.iter().any(|arg| arg == "--check");
+    args.retain(|arg| arg != "--steps" && arg != "--check");
+    if cranes.is_none() && target.is_none() && !steps && !checking {
+        return run_main_with_render::<Solver>(print_answers, print_stacks);
+    }
 
-    let (initial_stacks, instructions) = parse_input(&content)?;
+    let names = cranes.unwrap_or_else(|| "9000".to_owned());
+    let positional = split_text_args(args)?;
+    for input in inputs_from_args(&positional) {
+        let content = input.read()?;
+        if checking {
+            print_faults(&content)?;
+            continue;
+        }
+        if let Some(target) = &target {
+            print_plan(&content, &Input::from_arg(target).read()?)?;
+            continue;
+        }
+        for name in names.split(',') {
+            let mut crane = crane(name)?;
+            match steps {
+                true => print_steps(&content, crane.as_mut())?,
+                false => print_costs(&content, crane.as_mut())?,
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_answers(content: &str) -> Result<(), Error> {
+    let (initial_stacks, instructions) = parse_input(content)?;
 
     let done_stacks = run_instructions(initial_stacks.clone(), &instructions)?;
     println!(
@@ -25,198 +59,68 @@ fn main() -> Result<(), String> {
     Ok(())
 }
 
-type Stack = Vec<char>;
-
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-struct Instruction {
-    count: usize,
-    from: usize,
-    to: usize,
-}
-
-fn parse_input(input: &str) -> Result<(Vec<Stack>, Vec<Instruction>), String> {
-    let (stack_input, inst_input) = input
-        .split_once("\n\n")
-        .ok_or_else(|| "Unable to split input between stacks and instructions".to_owned())?;
-    let mut stacks: Vec<Stack> = Vec::with_capacity(16);
-    for line in stack_input.lines() {
-        // stop as soon as the stack numbers show up
-        // (of course, if the input is malformed, this may just discard everything after that)
-        if line.starts_with(" 1 ") {
-            break;
-        }
-        // for the stack representation, it is actually easier to work on a byte slice
-        for (i, chunk) in line
-            .as_bytes()
-            .chunks(4)
-            .map(|c| if c.len() == 4 { &c[..3] } else { c })
-            .enumerate()
-        {
-            if stacks.len() < i + 1 {
-                stacks.push(Vec::with_capacity(26));
-            }
-            if chunk != b"   " {
-                let item = chunk
-                    .strip_prefix(b"[")
-                    .ok_or_else(|| format!("item {i} in line '{line} is missing open bracket"))?
-                    .strip_suffix(b"]")
-                    .ok_or_else(|| format!("item {i} in line '{line} is missing open bracket"))?;
-                let item = *item
-                    .first()
-                    .ok_or_else(|| format!("item {i} in line {line} is missing an identifier"))?;
-                stacks[i].push(char::from(item));
-            }
-        }
-    }
-    for stack in &mut stacks {
-        stack.reverse();
-    }
-
-    let instructions = inst_input
-        .lines()
-        .map(parse_instruction)
-        .collect::<Result<Vec<Instruction>, String>>()?;
-
-    Ok((stacks, instructions))
-}
-
-fn parse_instruction(line: &str) -> Result<Instruction, String> {
-    let (c, loc) = line
-        .split_once(" from ")
-        .ok_or_else(|| format!("unable to parse instruction '{line}': missing ' from '"))?;
-    let count: usize = c
-        .strip_prefix("move ")
-        .ok_or_else(|| format!("unable to parse instruction '{line}': mossing 'move '"))?
-        .parse()
-        .map_err(|e| format!("unable to parse number in instruction '{line}': {e}"))?;
+// the stacks at the end, for both cranes
+fn print_stacks(content: &str) -> Result<(), Error> {
+    let (initial_stacks, instructions) = parse_input(content)?;
 
-    let (from_str, to_str) = loc
-        .split_once(" to ")
-        .ok_or_else(|| format!("unable to parse instruction '{line}': Missing ' to '"))?;
-    let from: usize = from_str
-        .parse()
-        .map_err(|e| format!("unable to parse source in line '{line}': {e}"))?;
-    let to: usize = to_str
-        .parse()
-        .map_err(|e| format!("unable to parse source in line '{line}': {e}"))?;
+    let done_stacks = run_instructions(initial_stacks.clone(), &instructions)?;
+    println!("With the CrateMover 9000:");
+    print!("{}", render_stacks(&done_stacks));
 
-    Ok(Instruction { count, from, to })
-}
+    let done_9001 = its_over_9000(initial_stacks, &instructions)?;
+    println!("\nWith the CrateMover 9001:");
+    print!("{}", render_stacks(&done_9001));
 
-fn run_instructions(
-    mut stacks: Vec<Stack>,
-    instructions: &[Instruction],
-) -> Result<Vec<Stack>, String> {
-    for inst in instructions {
-        if inst.from == 0 || inst.from > stacks.len() {
-            return Err(format!(
-                "Faulty instruction, referencing out-of-bounds from-stack {}/{}",
-                inst.from,
-                stacks.len()
-            ));
-        }
-        if inst.to == 0 || inst.to > stacks.len() {
-            return Err(format!(
-                "Faulty instruction, referencing out-of-bounds to-stack {}/{}",
-                inst.from,
-                stacks.len()
-            ));
-        }
-        let from = inst.from - 1;
-        let to = inst.to - 1;
-        for _ in 0..inst.count {
-            let item = stacks[from]
-                .pop()
-                .ok_or_else(|| format!("Trying to take something from empty stack {from}"))?;
-            stacks[to].push(item);
-        }
-    }
-    Ok(stacks)
+    Ok(())
 }
 
-fn its_over_9000(
-    mut stacks: Vec<Stack>,
-    instructions: &[Instruction],
-) -> Result<Vec<Stack>, String> {
-    let mut temp: Stack = Vec::with_capacity(32);
-    for inst in instructions {
-        if inst.from == 0 || inst.from > stacks.len() {
-            return Err(format!(
-                "Faulty instruction, referencing out-of-bounds from-stack {}/{}",
-                inst.from,
-                stacks.len()
-            ));
-        }
-        if inst.to == 0 || inst.to > stacks.len() {
-            return Err(format!(
-                "Faulty instruction, referencing out-of-bounds to-stack {}/{}",
-                inst.from,
-                stacks.len()
-            ));
-        }
-        let from = inst.from - 1;
-        let to = inst.to - 1;
-        let count = inst.count;
-
-        if count > stacks[from].len() {
-            return Err(format!(
-                "trying to pick up {count} items from stack {from}, but it only has {0} items",
-                stacks[from].len()
-            ));
-        }
-        let bottom = stacks[from].len() - count;
-        temp.extend_from_slice(&stacks[from][bottom..stacks[from].len()]);
-        stacks[to].extend_from_slice(&temp);
-        stacks[from].resize(bottom, '☹');
-        temp.clear();
+// the stacks after every instruction, with its cost
+fn print_steps(content: &str, crane: &mut dyn Crane) -> Result<(), Error> {
+    let (mut stacks, instructions) = parse_input(content)?;
+    println!("With the {}:", crane.name());
+    print!("{}", render_stacks(&stacks));
+    for (n, inst) in instructions.iter().enumerate() {
+        let cost = crane.run_instruction(&mut stacks, n + 1, inst)?;
+        println!("\n{inst} ({cost} lifts)");
+        print!("{}", render_stacks(&stacks));
     }
-    Ok(stacks)
+    Ok(())
 }
 
-fn get_stack_tops(stacks: &[Stack]) -> String {
-    stacks
-        .iter()
-        .map(|s| s.last().copied().unwrap_or(' '))
-        .collect()
+fn print_costs(content: &str, crane: &mut dyn Crane) -> Result<(), Error> {
+    let (stacks, instructions) = parse_input(content)?;
+    let (done, costs) = crane.run(stacks, &instructions)?;
+    let total: u64 = costs.iter().sum();
+    println!(
+        "The {} leaves '{}' on top after {total} lifts",
+        crane.name(),
+        get_stack_tops(&done)
+    );
+    Ok(())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const EXAMPLE: &str = r#"    [D]
-[N] [C]
-[Z] [M] [P]
- 1   2   3
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2
-"#;
-
-    #[test]
-    fn run_instructions_works_for_example() {
-        // given
-        let (stacks, instructions) = parse_input(EXAMPLE).expect("expected successful parsing");
-
-        // when
-        let result = run_instructions(stacks, &instructions);
-
-        // then
-        let result_stacks = result.expect("expected successful run");
-        assert_eq!(&get_stack_tops(&result_stacks), "CMZ");
+fn print_faults(content: &str) -> Result<(), Error> {
+    let faults = validate_input(content)?;
+    for fault in &faults {
+        println!("{fault}");
     }
-    #[test]
-    fn its_over_9000_works_for_example() {
-        // given
-        let (stacks, instructions) = parse_input(EXAMPLE).expect("expected successful parsing");
-
-        // when
-        let result = its_over_9000(stacks, &instructions);
+    match faults.len() {
+        0 => println!("All instructions can be carried out."),
+        1 => println!("One instruction can't be carried out."),
+        n => println!("{n} instructions can't be carried out."),
+    }
+    Ok(())
+}
 
-        // then
-        let result_stacks = result.expect("expected successful run");
-        assert_eq!(&get_stack_tops(&result_stacks), "MCD");
+// a puzzle input with the stacks of `content` and the moves that turn them into `target`
+fn print_plan(content: &str, target: &str) -> Result<(), Error> {
+    let (stacks, _) = parse_input(content)?;
+    let target = parse_stacks(target)?;
+    let instructions = plan(&stacks, &target)?;
+    print!("{}", render_stacks(&stacks));
+    println!();
+    for inst in instructions {
+        println!("{inst}");
     }
+    Ok(())
 }
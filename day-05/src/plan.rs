@@ -0,0 +1,197 @@
+use crate::{Instruction, Stack};
+use aoc_common::Error;
+
+/// Instructions for the CrateMover 9000 that turn the `start` stacks into the `target` stacks.
+/// Crates are moved one by one: the next crate a stack needs is dug out where it is buried the
+/// least, and the crates above it go onto stacks that have to be cleared anyway. If every
+/// unfinished stack has wrong crates, the one with the fewest is cleared completely. The plan is
+/// short, but not always the shortest. Moves of several crates between the same stacks are
+/// joined into one instruction.
+pub fn plan(start: &[Stack], target: &[Stack]) -> Result<Vec<Instruction>, Error> {
+    if start.len() != target.len() {
+        return Err(Error::semantic(format!(
+            "there are {} stacks at the start, but {} in the end",
+            start.len(),
+            target.len()
+        )));
+    }
+    let sorted = |stacks: &[Stack]| -> Vec<char> {
+        let mut crates: Vec<char> = stacks.concat();
+        crates.sort_unstable();
+        crates
+    };
+    if sorted(start) != sorted(target) {
+        return Err(Error::semantic(
+            "the crates at the start are not the ones in the end",
+        ));
+    }
+
+    // no crate that is where it belongs is ever moved again, and each crate put there takes at
+    // most one clearing and one digging, so this is never reached unless there is a bug
+    let crates: usize = start.iter().map(Vec::len).sum();
+    let limit = crates * (2 * crates + 1);
+
+    let n = start.len();
+    let mut stacks = start.to_vec();
+    let mut plan: Vec<Instruction> = Vec::new();
+    let mut lift = |stacks: &mut [Stack], from: usize, to: usize| {
+        let item = stacks[from]
+            .pop()
+            .expect("only crates that are there are moved");
+        stacks[to].push(item);
+        match plan.last_mut() {
+            Some(last) if (last.from, last.to) == (from + 1, to + 1) => last.count += 1,
+            _ => plan.push(Instruction {
+                count: 1,
+                from: from + 1,
+                to: to + 1,
+            }),
+        }
+    };
+
+    let mut steps = 0;
+    while stacks != target {
+        // every step moves at least one crate
+        steps += 1;
+        if steps > limit {
+            return Err(Error::semantic(format!(
+                "no plan found within {limit} moves"
+            )));
+        }
+        // the crates at the bottom of each stack that are already where they belong
+        let placed: Vec<usize> = (0..n)
+            .map(|i| {
+                (stacks[i].iter().zip(&target[i]))
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            })
+            .collect();
+        let has_wrong_crates = |i: usize| stacks[i].len() > placed[i];
+
+        // stacks that only need more crates, where the next crate is and how deep
+        let next = (0..n)
+            .filter(|&i| !has_wrong_crates(i) && placed[i] < target[i].len())
+            .filter_map(|i| {
+                let item = target[i][placed[i]];
+                (0..n)
+                    .filter(|&j| j != i)
+                    .filter_map(|j| {
+                        let free = &stacks[j][placed[j]..];
+                        let p = free.iter().rposition(|c| *c == item)?;
+                        Some((free.len() - 1 - p, j))
+                    })
+                    .min()
+                    .map(|(depth, j)| (depth, i, j))
+            })
+            .min();
+
+        let Some((depth, i, j)) = next else {
+            // every unfinished stack has wrong crates, clear the one with the fewest completely,
+            // so it needs more crates afterwards; clearing just a crate may only shift the
+            // fewest wrong crates to the other stack and back
+            let i = (0..n)
+                .filter(|&i| has_wrong_crates(i))
+                .min_by_key(|&i| stacks[i].len() - placed[i])
+                .expect("unfinished stacks have wrong crates");
+            let k = (0..n)
+                .filter(|&k| k != i)
+                .max_by_key(|&k| (has_wrong_crates(k), std::cmp::Reverse(k)))
+                .ok_or_else(|| Error::semantic("a single stack can't be rearranged"))?;
+            for _ in placed[i]..stacks[i].len() {
+                lift(&mut stacks, i, k);
+            }
+            continue;
+        };
+        if depth == 0 {
+            lift(&mut stacks, j, i);
+            continue;
+        }
+        // dig: the crate on top of it goes elsewhere, preferably onto wrong crates
+        let k = (0..n)
+            .filter(|&k| k != i && k != j)
+            .max_by_key(|&k| (has_wrong_crates(k), std::cmp::Reverse(k)))
+            .ok_or_else(|| Error::semantic("digging out a crate needs a third stack"))?;
+        lift(&mut stacks, j, k);
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_stacks, run_instructions};
+    use aoc_common::Rng;
+
+    #[test]
+    fn plan_reaches_the_target() {
+        // given
+        let start = parse_stacks("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n")
+            .expect("expected successful parsing");
+        let target =
+            parse_stacks("        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3\n")
+                .expect("expected successful parsing");
+
+        // when
+        let instructions = plan(&start, &target).expect("expected a plan");
+        let back = plan(&target, &start).expect("expected a plan");
+
+        // then
+        let done = run_instructions(start.clone(), &instructions).expect("expected valid moves");
+        assert_eq!(done, target);
+        let listed: Vec<String> = instructions.iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            listed,
+            [
+                "move 1 from 2 to 3",
+                "move 2 from 1 to 3",
+                "move 1 from 2 to 1"
+            ]
+        );
+        let done = run_instructions(target, &back).expect("expected valid moves");
+        assert_eq!(done, start);
+        assert!(plan(&start, &[vec!['A'], vec![], vec![]]).is_err());
+    }
+
+    #[test]
+    fn plan_finishes_for_random_stacks() {
+        // given
+        let mut rng = Rng::new(5);
+        let mut cases = vec![(
+            vec![
+                vec!['Z', 'M', 'W'],
+                vec!['E', 'W', 'A'],
+                vec!['J', 'F', 'S', 'O'],
+            ],
+            vec![
+                vec!['F', 'Z', 'W'],
+                vec!['O', 'A'],
+                vec!['J', 'W', 'S', 'E', 'M'],
+            ],
+        )];
+        for _ in 0..500 {
+            let n = rng.between(3, 6) as usize;
+            let mut crates: Vec<char> = (0..rng.below(15))
+                .map(|_| char::from(b'A' + rng.below(6) as u8))
+                .collect();
+            let mut stacks = || {
+                rng.shuffle(&mut crates);
+                let mut stacks: Vec<Stack> = vec![Vec::new(); n];
+                for c in &crates {
+                    stacks[rng.below(n)].push(*c);
+                }
+                stacks
+            };
+            cases.push((stacks(), stacks()));
+        }
+
+        for (start, target) in cases {
+            // when
+            let instructions = plan(&start, &target).expect("expected a plan");
+
+            // then
+            let done =
+                run_instructions(start.clone(), &instructions).expect("expected valid moves");
+            assert_eq!(done, target, "from {start:?}");
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{parse_input, Instruction, Stack};
+use aoc_common::Error;
+
+/// The part of an instruction that is wrong.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Field {
+    Count,
+    From,
+    To,
+}
+
+/// A faulty instruction, found by [`validate_instructions`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Fault {
+    /// Index of the instruction, counting from 0.
+    pub instruction: usize,
+    pub field: Field,
+    pub message: String,
+}
+
+/// Check all instructions without running them. Only the number of crates on each stack is
+/// followed, which is the same for every crane. After a faulty instruction the check goes on
+/// as if it was left out, or moved as many crates as there were.
+pub fn validate_instructions(stacks: &[Stack], instructions: &[Instruction]) -> Vec<Fault> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    let mut faults = Vec::new();
+    for (i, inst) in instructions.iter().enumerate() {
+        let mut fault = |field, message| {
+            faults.push(Fault {
+                instruction: i,
+                field,
+                message,
+            })
+        };
+        let mut exists = true;
+        for (field, stack) in [(Field::From, inst.from), (Field::To, inst.to)] {
+            if stack == 0 || stack > heights.len() {
+                let stacks = heights.len();
+                fault(
+                    field,
+                    format!("there is no stack {stack}, only 1 to {stacks}"),
+                );
+                exists = false;
+            }
+        }
+        if !exists {
+            continue;
+        }
+        let (from, to) = (inst.from - 1, inst.to - 1);
+        if inst.count > heights[from] {
+            fault(
+                Field::Count,
+                format!(
+                    "stack {} has only {} crates, not {}",
+                    inst.from, heights[from], inst.count
+                ),
+            );
+        }
+        let moved = inst.count.min(heights[from]);
+        heights[from] -= moved;
+        heights[to] += moved;
+    }
+    faults
+}
+
+/// Parse the puzzle input and check all of its instructions, with the line and the part of the
+/// line of each faulty one.
+pub fn validate_input(input: &str) -> Result<Vec<Error>, Error> {
+    let (stacks, instructions) = parse_input(input)?;
+    // parsing made sure the drawing and the instructions are there
+    let (drawing, lines) = input.split_once("\n\n").unwrap_or_default();
+    let offset = drawing.lines().count() + 1;
+    let lines: Vec<&str> = lines.lines().collect();
+    Ok(validate_instructions(&stacks, &instructions)
+        .into_iter()
+        .map(|fault| {
+            let line = lines[fault.instruction];
+            Error::semantic(fault.message)
+                .with_span(line, field_span(line, fault.field))
+                .at_line(offset + fault.instruction + 1)
+        })
+        .collect())
+}
+
+// the number of the field in a line like `move 1 from 2 to 3`
+fn field_span(line: &str, field: Field) -> &str {
+    let count = line.strip_prefix("move ").unwrap_or(line);
+    let (count, from) = count.split_once(" from ").unwrap_or((count, ""));
+    let (from, to) = from.split_once(" to ").unwrap_or((from, ""));
+    match field {
+        Field::Count => count,
+        Field::From => from,
+        Field::To => to,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_input_reports_every_faulty_line() {
+        // given
+        let input = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\n\
+                     move 1 from 2 to 1\nmove 4 from 1 to 3\nmove 2 from 2 to 4\n\
+                     move 1 from 0 to 2\nmove 2 from 3 to 1\n";
+
+        // when
+        let faults = validate_input(input).expect("expected successful parsing");
+
+        // then
+        let found: Vec<(usize, usize, String)> = faults
+            .iter()
+            .map(|e| {
+                let location = e.location.clone().expect("expected a location");
+                (location.line, location.column, e.message.clone())
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (7, 6, "stack 1 has only 3 crates, not 4".to_owned()),
+                (8, 18, "there is no stack 4, only 1 to 3".to_owned()),
+                (9, 13, "there is no stack 0, only 1 to 3".to_owned()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use aoc_common::GenerateOptions;
+
+/// A datastream buffer. The size is its length, the harder the puzzle, the later the markers
+/// show up: before them, the buffer only uses a few different characters.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let len = options.size_or(4096).max(18);
+    let (packet_start, message_start) =
+        options.pick((0, 0), (len / 4, len / 2), (len / 2, len - 14));
+    let mut alphabet: Vec<u8> = (b'a'..=b'z').collect();
+    rng.shuffle(&mut alphabet);
+    let mut buffer: Vec<u8> = Vec::with_capacity(len);
+    // 3 different characters can't form a start-of-packet marker, 13 no start-of-message
+    // marker, so the markers are where they are put explicitly
+    while buffer.len() < packet_start {
+        buffer.push(*rng.choose(&alphabet[..3]));
+    }
+    let mut marker = alphabet[..13].to_vec();
+    rng.shuffle(&mut marker);
+    buffer.extend_from_slice(&marker[..4]);
+    while buffer.len() < message_start {
+        buffer.push(*rng.choose(&alphabet[..13]));
+    }
+    rng.shuffle(&mut alphabet);
+    buffer.extend_from_slice(&alphabet[..14]);
+    while buffer.len() < len {
+        buffer.push(*rng.choose(&alphabet));
+    }
+    String::from_utf8_lossy(&buffer).into_owned()
+}
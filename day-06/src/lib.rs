@@ -0,0 +1,125 @@
+//! Day 6: Tuning Trouble
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 6 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 6;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(input)
+    }
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, Error> {
+        start_of_packet_offset(input)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("No start of packet marker found."))
+    }
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, Error> {
+        start_of_msg_offset(input)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("no start of message marker found."))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// Number of characters read until the first four different characters in a row.
+pub fn start_of_packet_offset(input: &str) -> Option<usize> {
+    // yes, there are probably more efficient ways to do this. This one should be sufficient though
+    // and is still O(N)
+    input
+        .as_bytes()
+        .windows(4)
+        .enumerate()
+        .filter(|(_, w)| {
+            w[0] != w[1]
+                && w[0] != w[2]
+                && w[0] != w[3]
+                && w[1] != w[2]
+                && w[1] != w[3]
+                && w[2] != w[3]
+        })
+        .map(|(i, _)| i + 4)
+        .next()
+}
+
+/// Number of characters read until the first fourteen different characters in a row.
+pub fn start_of_msg_offset(input: &str) -> Option<usize> {
+    input
+        .as_bytes()
+        .windows(14)
+        .enumerate()
+        .filter(|(_, w)| {
+            let mut seen: [bool; 256] = [false; 256];
+            for c in *w {
+                if seen[*c as usize] {
+                    return false;
+                }
+                seen[*c as usize] = true;
+            }
+            true
+        })
+        .map(|(i, _)| i + 14)
+        .next()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn start_of_packet_offset_works_for_examples() {
+        assert_eq!(
+            start_of_packet_offset("mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
+            Some(7)
+        );
+        assert_eq!(
+            start_of_packet_offset("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+            Some(5)
+        );
+        assert_eq!(
+            start_of_packet_offset("nppdvjthqldpwncqszvftbrmjlhg"),
+            Some(6)
+        );
+        assert_eq!(
+            start_of_packet_offset("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
+            Some(10)
+        );
+        assert_eq!(
+            start_of_packet_offset("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn start_of_msg_offset_works_for_examples() {
+        assert_eq!(
+            start_of_msg_offset("mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
+            Some(19)
+        );
+        assert_eq!(
+            start_of_msg_offset("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+            Some(23)
+        );
+        assert_eq!(
+            start_of_msg_offset("nppdvjthqldpwncqszvftbrmjlhg"),
+            Some(23)
+        );
+        assert_eq!(
+            start_of_msg_offset("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
+            Some(29)
+        );
+        assert_eq!(
+            start_of_msg_offset("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
+            Some(26)
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use aoc_common::{GenerateOptions, Rng};
+
+// the used space has to be more than 40000000, so something needs to be deleted, but it can't be
+// more than the size of the disk
+const MIN_USED: u64 = 41_000_000;
+const MAX_USED: u64 = 69_000_000;
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    files: Vec<(String, u64)>,
+    dirs: Vec<Dir>,
+}
+
+/// Terminal output of exploring a file system that is too full for the update. The size is the
+/// number of directories, the harder the puzzle, the deeper they are nested.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let max_depth = options.pick(3, 6, 12);
+    let mut root = Dir::default();
+    // the path to each directory as indices of the subdirectories, the root first
+    let mut paths: Vec<Vec<usize>> = vec![vec![]];
+    for _ in 0..options.size_or(180) {
+        let parent_path = rng.choose(&paths).clone();
+        if parent_path.len() >= max_depth {
+            continue;
+        }
+        let parent = dir_at(&mut root, &parent_path);
+        let name = unique_name(&mut rng, parent, "");
+        parent.dirs.push(Dir {
+            name,
+            ..Dir::default()
+        });
+        paths.push([parent_path, vec![parent.dirs.len() - 1]].concat());
+    }
+
+    let mut total = 0;
+    for path in &paths {
+        for _ in 0..rng.between(0, 4) {
+            let size = rng.between(1000, 300_000) as u64;
+            add_file(&mut rng, dir_at(&mut root, path), size);
+            total += size;
+        }
+    }
+    if total < MIN_USED {
+        let size = MIN_USED - total + rng.between(0, 1_000_000) as u64;
+        let path = rng.choose(&paths).clone();
+        add_file(&mut rng, dir_at(&mut root, &path), size);
+    } else if total > MAX_USED {
+        shrink(&mut root, MAX_USED as f64 / total as f64);
+    }
+
+    let mut lines = vec!["$ cd /".to_owned()];
+    explore(&mut rng, &root, &mut lines);
+    lines.join("\n") + "\n"
+}
+
+fn dir_at<'a>(root: &'a mut Dir, path: &[usize]) -> &'a mut Dir {
+    path.iter().fold(root, |dir, i| &mut dir.dirs[*i])
+}
+
+fn unique_name(rng: &mut Rng, dir: &Dir, extension: &str) -> String {
+    loop {
+        let len = rng.between(1, 8);
+        let name: String = (0..len)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect::<String>()
+            + extension;
+        let taken = dir.dirs.iter().any(|d| d.name == name)
+            || dir.files.iter().any(|(file, _)| *file == name);
+        if !taken {
+            return name;
+        }
+    }
+}
+
+fn add_file(rng: &mut Rng, dir: &mut Dir, size: u64) {
+    let extension = *rng.choose(&["", "", ".txt", ".dat", ".log"]);
+    let name = unique_name(rng, dir, extension);
+    dir.files.push((name, size));
+}
+
+fn shrink(dir: &mut Dir, factor: f64) {
+    for (_, size) in &mut dir.files {
+        *size = ((*size as f64 * factor) as u64).max(1);
+    }
+    for sub in &mut dir.dirs {
+        shrink(sub, factor);
+    }
+}
+
+// list the directory and change into each subdirectory, depth first
+fn explore(rng: &mut Rng, dir: &Dir, lines: &mut Vec<String>) {
+    lines.push("$ ls".to_owned());
+    let mut entries: Vec<String> = dir
+        .dirs
+        .iter()
+        .map(|sub| format!("dir {}", sub.name))
+        .chain(
+            dir.files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect();
+    rng.shuffle(&mut entries);
+    lines.extend(entries);
+    for sub in &dir.dirs {
+        lines.push(format!("$ cd {}", sub.name));
+        explore(rng, sub, lines);
+        lines.push("$ cd ..".to_owned());
+    }
+}
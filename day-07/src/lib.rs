@@ -0,0 +1,209 @@
+//! Day 7: No Space Left On Device
+
+use aoc_common::{parse_number, Answer, Error, GenerateOptions, Solution};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 7 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 7;
+    type Input<'a> = FsNode<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        construct_directory_tree(input)
+    }
+    fn part1(dir_tree: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(sum_small_dirs(dir_tree).into())
+    }
+    fn part2(dir_tree: &Self::Input<'_>) -> Result<Answer, Error> {
+        if dir_tree.size < 40_000_000 {
+            // there already is enough space, so we don't need to delete anything
+            return Ok(Answer::from(0u64));
+        }
+        find_smallest_directory_to_delete(dir_tree, dir_tree.size - 40_000_000)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("We can't free enough space!"))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum FType<'a> {
+    Dir(Vec<FsNode<'a>>),
+    File,
+}
+
+/// A file or directory. The size of a directory is the total size of its content.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct FsNode<'a> {
+    pub size: u64,
+    pub name: &'a str,
+    pub ftype: FType<'a>,
+}
+
+/// Reconstruct the directory tree from the terminal output.
+pub fn construct_directory_tree(input: &str) -> Result<FsNode<'_>, Error> {
+    let mut lines = input.lines().enumerate();
+    match lines.next() {
+        Some((_, "$ cd /")) => {}
+        Some((_, line)) => {
+            return Err(Error::syntax(line, line, "Expected first line to be '$ cd /'").at_line(1))
+        }
+        None => return Err(Error::semantic("Expected first line to be '$ cd /'")),
+    }
+    construct_subtree("/", &mut lines)
+}
+
+/// Construct the tree below `dir_name`. `lines` yields the line index along with the line,
+/// for the error messages.
+pub fn construct_subtree<'a, I: Iterator<Item = (usize, &'a str)>>(
+    dir_name: &'a str,
+    lines: &mut I,
+) -> Result<FsNode<'a>, Error> {
+    let mut last_command: &str = "";
+    let mut size: u64 = 0;
+    let mut children: Vec<FsNode> = Vec::with_capacity(16);
+    while let Some((i, line)) = lines.next() {
+        if let Some(command) = line.strip_prefix("$ ") {
+            last_command = command;
+            if command == "cd .." {
+                return Ok(FsNode {
+                    size,
+                    name: dir_name,
+                    ftype: FType::Dir(children),
+                });
+            } else if let Some(subdir_name) = command.strip_prefix("cd ") {
+                let subdir = construct_subtree(subdir_name, lines)?;
+                size += subdir.size;
+                children.push(subdir);
+            }
+        } else if last_command == "ls" {
+            let (info, name) = line.split_once(' ').ok_or_else(|| {
+                Error::syntax(line, line, "Unable to parse directory entry").at_line(i + 1)
+            })?;
+            // we ignore directories in the listing because we just implicitly list them when
+            // we change to them
+            if info != "dir" {
+                let file_size: u64 = parse_number(line, info).map_err(|e| e.at_line(i + 1))?;
+                size += file_size;
+                children.push(FsNode {
+                    size: file_size,
+                    name,
+                    ftype: FType::File,
+                });
+            }
+        } else {
+            return Err(Error::syntax(line, line, "unexpected line").at_line(i + 1));
+        }
+    }
+    Ok(FsNode {
+        size,
+        name: dir_name,
+        ftype: FType::Dir(children),
+    })
+}
+
+/// Total size of all directories with a size of at most 100000.
+pub fn sum_small_dirs(node: &FsNode) -> u64 {
+    if let FType::Dir(children) = &node.ftype {
+        children.iter().map(sum_small_dirs).sum::<u64>()
+            + if node.size <= 100000 { node.size } else { 0 }
+    } else {
+        0
+    }
+}
+
+/// Size of the smallest directory that frees up at least `space_required` when deleted.
+pub fn find_smallest_directory_to_delete(root: &FsNode, space_required: u64) -> Option<u64> {
+    if let FType::Dir(children) = &root.ftype {
+        children
+            .iter()
+            .filter_map(|child| find_smallest_directory_to_delete(child, space_required))
+            .min()
+            .or(if root.size >= space_required {
+                Some(root.size)
+            } else {
+                None
+            })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+"#;
+
+    #[test]
+    fn sum_small_dirs_works_correctly() {
+        // given
+        let tree = construct_directory_tree(EXAMPLE).expect("Expected successfull tree building");
+
+        // when
+        let sum = sum_small_dirs(&tree);
+
+        // then
+        assert_eq!(sum, 95437);
+    }
+
+    #[test]
+    fn find_smallest_directory_to_delete_works_for_example() {
+        // given
+        let tree = construct_directory_tree(EXAMPLE).expect("Expected successfull tree building");
+
+        // when
+        let size = find_smallest_directory_to_delete(&tree, 8381165);
+
+        // then
+        assert_eq!(size, Some(24933642));
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,19 @@
+use aoc_common::GenerateOptions;
+
+/// A square patch of trees. The size is the width of the patch, the harder the puzzle, the more
+/// the heights vary from one tree to the next.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let size = options.size_or(99).max(1);
+    let step = options.pick(1, 3, 9);
+    let mut lines: Vec<String> = Vec::with_capacity(size);
+    let mut row: Vec<i64> = (0..size).map(|_| rng.between(0, 9)).collect();
+    for _ in 0..size {
+        // each tree is similar to the one above it, which makes for hills and valleys
+        for height in &mut row {
+            *height = (*height + rng.between(-step, step)).clamp(0, 9);
+        }
+        lines.push(row.iter().map(|h| h.to_string()).collect());
+    }
+    lines.join("\n") + "\n"
+}
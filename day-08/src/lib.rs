@@ -0,0 +1,204 @@
+//! Day 8: Treetop Tree House
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+use aoc_grid::{Dir4, Grid, Point};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 8 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 8;
+    type Input<'a> = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_grid(input)
+    }
+    fn part1(grid: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(count_visible_trees(&find_visible_trees(grid)).into())
+    }
+    fn part2(grid: &Self::Input<'_>) -> Result<Answer, Error> {
+        max_scenic_rating(grid)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("Where have all the trees gone?"))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// Parse the heights of the trees.
+pub fn parse_grid(input: &str) -> Result<Grid<u8>, Error> {
+    Grid::parse(input, |c| c.to_digit(10).map(|d| d as u8))
+}
+
+/// From which directions a tree can be seen, and the highest tree so far in each direction.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct VisibleFrom {
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+    pub left: bool,
+    pub tmax: u8,
+    pub rmax: u8,
+    pub bmax: u8,
+    pub lmax: u8,
+}
+
+impl VisibleFrom {
+    pub fn with_top(&self, visible: bool, max: u8) -> VisibleFrom {
+        VisibleFrom {
+            top: visible,
+            tmax: max,
+            ..*self
+        }
+    }
+    pub fn with_right(&self, visible: bool, max: u8) -> VisibleFrom {
+        VisibleFrom {
+            right: visible,
+            rmax: max,
+            ..*self
+        }
+    }
+    pub fn with_bottom(&self, visible: bool, max: u8) -> VisibleFrom {
+        VisibleFrom {
+            bottom: visible,
+            bmax: max,
+            ..*self
+        }
+    }
+    pub fn with_left(&self, visible: bool, max: u8) -> VisibleFrom {
+        VisibleFrom {
+            left: visible,
+            lmax: max,
+            ..*self
+        }
+    }
+
+    pub fn visible(&self) -> bool {
+        self.top || self.bottom || self.right || self.left
+    }
+}
+
+pub fn find_visible_trees(grid: &Grid<u8>) -> Grid<VisibleFrom> {
+    let width = grid.width() as i64;
+    let height = grid.height() as i64;
+    let p = Point::new;
+    let mut visible = Grid::new(grid.width(), grid.height(), VisibleFrom::default());
+    for i in 0..width {
+        visible[p(i, 0)] = VisibleFrom::default().with_top(true, grid[p(i, 0)]);
+        visible[p(i, height - 1)] =
+            VisibleFrom::default().with_bottom(true, grid[p(i, height - 1)]);
+    }
+    for i in 0..height {
+        visible[p(0, i)] = visible[p(0, i)].with_left(true, grid[p(0, i)]);
+        visible[p(width - 1, i)] = visible[p(width - 1, i)].with_right(true, grid[p(width - 1, i)]);
+    }
+    for y in 1..(height - 1) {
+        for x in 1..(width - 1) {
+            visible[p(x, y)] = visible[p(x, y)].with_top(
+                visible[p(x, y - 1)].tmax < grid[p(x, y)],
+                grid[p(x, y)].max(visible[p(x, y - 1)].tmax),
+            );
+            visible[p(x, height - 1 - y)] = visible[p(x, height - 1 - y)].with_bottom(
+                visible[p(x, height - y)].bmax < grid[p(x, height - 1 - y)],
+                grid[p(x, height - 1 - y)].max(visible[p(x, height - y)].bmax),
+            );
+            visible[p(x, y)] = visible[p(x, y)].with_left(
+                visible[p(x - 1, y)].lmax < grid[p(x, y)],
+                grid[p(x, y)].max(visible[p(x - 1, y)].lmax),
+            );
+            visible[p(width - 1 - x, y)] = visible[p(width - 1 - x, y)].with_right(
+                visible[p(width - x, y)].rmax < grid[p(width - 1 - x, y)],
+                grid[p(width - 1 - x, y)].max(visible[p(width - x, y)].rmax),
+            );
+        }
+    }
+    visible
+}
+
+pub fn count_visible_trees(grid: &Grid<VisibleFrom>) -> usize {
+    grid.cells().iter().filter(|tree| tree.visible()).count()
+}
+
+/// The product of the viewing distances in all four directions.
+pub fn scenic_rating(grid: &Grid<u8>, p: Point) -> u32 {
+    let tree_height = grid[p];
+    Dir4::ALL
+        .into_iter()
+        .map(|dir| {
+            // the view ends at the edge or at the first tree at least as high as this one
+            let mut trees: u32 = 0;
+            for other in grid.ray(p, dir) {
+                trees += 1;
+                if *other >= tree_height {
+                    break;
+                }
+            }
+            trees
+        })
+        .product()
+}
+
+pub fn max_scenic_rating(grid: &Grid<u8>) -> Option<u32> {
+    grid.points().map(|p| scenic_rating(grid, p)).max()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"30373
+25512
+65332
+33549
+35390
+"#;
+
+    #[test]
+    fn find_visible_trees_works_for_example() {
+        // given
+        let trees = parse_grid(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let visible = find_visible_trees(&trees);
+        let count = count_visible_trees(&visible);
+
+        // then
+        assert_eq!(count, 21);
+    }
+
+    #[test]
+    fn max_scenic_rating_works_for_example() {
+        // given
+        let trees = parse_grid(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let rating = max_scenic_rating(&trees);
+
+        // then
+        assert_eq!(rating, Some(8));
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_grid(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,14 @@
+use aoc_common::GenerateOptions;
+
+/// Motions of the head of the rope. The size is the number of motions, the harder the puzzle,
+/// the longer each motion.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let max_steps = options.pick(5, 19, 50);
+    (0..options.size_or(2000))
+        .map(|_| {
+            let dir = rng.choose(&["U", "R", "D", "L"]);
+            format!("{dir} {}\n", rng.between(1, max_steps))
+        })
+        .collect()
+}
@@ -0,0 +1,189 @@
+//! Day 9: Rope Bridge
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+use std::collections::HashSet;
+
+mod generate;
+mod visualize;
+
+pub use generate::generate_input;
+pub use visualize::RopeSimulation;
+
+/// Solves both parts of day 9 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 9;
+    type Input<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_instructions(input)
+    }
+    fn part1(instructions: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(count_tail_tiles(instructions).into())
+    }
+    fn part2(instructions: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(count_long_tail_tiles(instructions).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Dir {
+    U,
+    R,
+    D,
+    L,
+}
+
+/// Direction and number of steps the head moves.
+pub type Instruction = (Dir, i32);
+
+pub fn parse_instruction(line: &str) -> Result<Instruction, Error> {
+    let (ds, is) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::syntax(line, line, "Invalid instruction"))?;
+    let dir = match ds {
+        "U" => Dir::U,
+        "R" => Dir::R,
+        "D" => Dir::D,
+        "L" => Dir::L,
+        _ => return Err(Error::syntax(line, ds, format!("Invalid direction '{ds}'"))),
+    };
+    let n: i32 = parse_number(line, is)?;
+
+    Ok((dir, n))
+}
+
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, Error> {
+    parse_lines(input, parse_instruction)
+}
+
+pub type V2 = (i32, i32);
+
+/// Number of positions the tail of a rope with two knots visits.
+pub fn count_tail_tiles(instructions: &[Instruction]) -> usize {
+    count_rope_tail_tiles::<2>(instructions)
+}
+
+/// Number of positions the tail of a rope with ten knots visits.
+pub fn count_long_tail_tiles(instructions: &[Instruction]) -> usize {
+    count_rope_tail_tiles::<10>(instructions)
+}
+
+fn count_rope_tail_tiles<const N: usize>(instructions: &[Instruction]) -> usize {
+    let mut tail_trail: HashSet<V2> = HashSet::with_capacity(instructions.len());
+    let mut rope: [V2; N] = [(0, 0); N];
+    tail_trail.insert(rope[N - 1]);
+    for (dir, n) in instructions {
+        for _ in 0..*n {
+            step_rope(&mut rope, *dir);
+            tail_trail.insert(rope[N - 1]);
+        }
+    }
+    tail_trail.len()
+}
+
+/// Move the head of the rope (the first knot) one step, and the other knots after it.
+pub fn step_rope(rope: &mut [V2], dir: Dir) {
+    let v: V2 = match dir {
+        Dir::U => (0, -1),
+        Dir::R => (1, 0),
+        Dir::D => (0, 1),
+        Dir::L => (-1, 0),
+    };
+    rope[0].0 += v.0;
+    rope[0].1 += v.1;
+    for i in 1..rope.len() {
+        rope[i] = move_tail(rope[i - 1], rope[i]);
+    }
+}
+
+/// Where a knot moves when the knot in front of it has moved to `head`.
+pub fn move_tail(head: V2, tail: V2) -> V2 {
+    let dx = (head.0 - tail.0).abs();
+    let dy = (head.1 - tail.1).abs();
+    if (dy > 0 && dx > 1) || (dy > 1 && dx > 0) {
+        (
+            tail.0 + (head.0 - tail.0).signum(),
+            tail.1 + (head.1 - tail.1).signum(),
+        )
+    } else if dx > 1 {
+        (tail.0 + (head.0 - tail.0).signum(), tail.1)
+    } else if dy > 1 {
+        (tail.0, tail.1 + (head.1 - tail.1).signum())
+    } else {
+        tail
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+"#;
+
+    const LONG_EXAMPLE: &str = r#"R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+"#;
+
+    #[test]
+    fn count_tail_tiles_works_for_example() {
+        // given
+        let instructions = parse_instructions(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let count = count_tail_tiles(&instructions);
+
+        // then
+        assert_eq!(count, 13);
+    }
+
+    #[test]
+    fn count_long_tail_tiles_works_for_example() {
+        // given
+        let instructions = parse_instructions(LONG_EXAMPLE).expect("expected successfil parsing");
+
+        // when
+        let count = count_long_tail_tiles(&instructions);
+
+        // then
+        assert_eq!(count, 36);
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_instructions(input);
+            let _ = parse_instruction(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
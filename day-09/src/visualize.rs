@@ -0,0 +1,144 @@
+use crate::{step_rope, Instruction, V2};
+use aoc_tui::Simulation;
+use std::collections::HashSet;
+
+/// The long rope of part two, moving one step of the head at a time.
+#[derive(Clone, Debug)]
+pub struct RopeSimulation<'a> {
+    instructions: &'a [Instruction],
+    // the instruction being executed and how many of its steps are done
+    next: usize,
+    done: i32,
+    rope: [V2; 10],
+    tail_trail: HashSet<V2>,
+}
+
+impl RopeSimulation<'_> {
+    pub fn new(instructions: &[Instruction]) -> RopeSimulation<'_> {
+        RopeSimulation {
+            instructions,
+            next: 0,
+            done: 0,
+            rope: [(0, 0); 10],
+            tail_trail: HashSet::from([(0, 0)]),
+        }
+    }
+
+    // the smallest rectangle with the start, the rope and the trail, as top left and bottom right
+    fn bounds(&self) -> (V2, V2) {
+        self.rope.iter().chain(&self.tail_trail).fold(
+            ((0, 0), (0, 0)),
+            |((min_x, min_y), (max_x, max_y)), (x, y)| {
+                (
+                    (min_x.min(*x), min_y.min(*y)),
+                    (max_x.max(*x), max_y.max(*y)),
+                )
+            },
+        )
+    }
+}
+
+impl Simulation for RopeSimulation<'_> {
+    fn step(&mut self) -> bool {
+        // instructions without steps are skipped right away
+        while self
+            .instructions
+            .get(self.next)
+            .is_some_and(|(_, n)| self.done >= *n)
+        {
+            self.next += 1;
+            self.done = 0;
+        }
+        let Some((dir, _)) = self.instructions.get(self.next) else {
+            return false;
+        };
+        step_rope(&mut self.rope, *dir);
+        self.tail_trail.insert(self.rope[self.rope.len() - 1]);
+        self.done += 1;
+        true
+    }
+
+    /// Knots are drawn like in the puzzle description, the head as `H`, the others numbered,
+    /// the start as `s` and the tiles the tail visited as `#`.
+    fn render(&self) -> String {
+        let ((min_x, min_y), (max_x, max_y)) = self.bounds();
+        let width = (max_x - min_x + 1) as usize;
+        let mut rows = vec![vec!['.'; width]; (max_y - min_y + 1) as usize];
+        let mut draw = |(x, y): V2, c: char| rows[(y - min_y) as usize][(x - min_x) as usize] = c;
+        for p in &self.tail_trail {
+            draw(*p, '#');
+        }
+        draw((0, 0), 's');
+        // knots in front cover the ones behind them
+        for (i, knot) in self.rope.iter().enumerate().rev() {
+            draw(
+                *knot,
+                if i == 0 {
+                    'H'
+                } else {
+                    (b'0' + i as u8) as char
+                },
+            );
+        }
+        rows.into_iter()
+            .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+            .collect()
+    }
+
+    fn status(&self) -> String {
+        let instruction = match self.instructions.get(self.next) {
+            Some((dir, n)) => format!("{dir:?} {n}"),
+            None => "-".to_owned(),
+        };
+        format!(
+            "instruction {} of {} ({instruction}), the tail visited {} tiles",
+            (self.next + 1).min(self.instructions.len()),
+            self.instructions.len(),
+            self.tail_trail.len()
+        )
+    }
+
+    fn focus(&self) -> Option<(usize, usize)> {
+        let ((min_x, min_y), _) = self.bounds();
+        let (x, y) = self.rope[0];
+        Some(((y - min_y) as usize, (x - min_x) as usize))
+    }
+
+    fn colour(&self, c: char) -> Option<(u8, u8, u8)> {
+        match c {
+            'H' => Some((230, 60, 60)),
+            '1'..='9' => Some((240, 160, 60)),
+            '#' => Some((120, 120, 140)),
+            's' => Some((80, 200, 80)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{count_long_tail_tiles, parse_instructions};
+
+    #[test]
+    fn rope_simulation_matches_count_of_tail_tiles() {
+        // given
+        let instructions = parse_instructions("R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n")
+            .expect("expected successful parsing");
+        let mut simulation = RopeSimulation::new(&instructions);
+
+        // when
+        let mut steps = 0;
+        while simulation.step() {
+            steps += 1;
+        }
+
+        // then
+        assert_eq!(steps, 96);
+        assert_eq!(
+            simulation.tail_trail.len(),
+            count_long_tail_tiles(&instructions)
+        );
+        assert_eq!(simulation.render().lines().count(), 21);
+    }
+}
@@ -0,0 +1,29 @@
+use aoc_common::GenerateOptions;
+
+// the CRT draws 240 pixels, one per cycle
+const CYCLES: usize = 240;
+
+/// A program for the CPU that runs for at least the 240 cycles the CRT needs, keeping the
+/// sprite on the screen most of the time. The size is the number of instructions, the harder
+/// the puzzle, the further the sprite jumps.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let max_jump = options.pick(3, 10, 40);
+    let mut x: i64 = 1;
+    let mut cycles = 0;
+    let mut lines: Vec<String> = Vec::with_capacity(options.size_or(146));
+    while lines.len() < options.size_or(146) || cycles < CYCLES {
+        if rng.chance(0.3) {
+            lines.push("noop".to_owned());
+            cycles += 1;
+        } else {
+            let v = rng.between(-max_jump, max_jump);
+            // jumps that would leave the screen go the other way
+            let v = if (-1..=40).contains(&(x + v)) { v } else { -v };
+            x += v;
+            lines.push(format!("addx {v}"));
+            cycles += 2;
+        }
+    }
+    lines.join("\n") + "\n"
+}
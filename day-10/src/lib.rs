@@ -0,0 +1,322 @@
+//! Day 10: Cathode-Ray Tube
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 10 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 10;
+    type Input<'a> = Vec<Inst>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_instructions(input)
+    }
+    fn part1(instructions: &Self::Input<'_>) -> Result<Answer, Error> {
+        let (signal_strength_sum, _) = run_and_inspect(instructions);
+        Ok(signal_strength_sum.into())
+    }
+    fn part2(instructions: &Self::Input<'_>) -> Result<Answer, Error> {
+        let (_, screen) = run_and_inspect(instructions);
+        Ok(render_screen(&screen).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Inst {
+    Noop,
+    Addx(i64),
+}
+
+pub fn parse_instruction(line: &str) -> Result<Inst, Error> {
+    if line == "noop" {
+        Ok(Inst::Noop)
+    } else {
+        let (operator, operand) = line
+            .split_once(' ')
+            .ok_or_else(|| Error::syntax(line, line, "Unable to parse instruction"))?;
+        if operator == "addx" {
+            let opv: i64 = parse_number(line, operand)?;
+            Ok(Inst::Addx(opv))
+        } else {
+            Err(Error::syntax(
+                line,
+                operator,
+                format!("unknown operator '{operator}'"),
+            ))
+        }
+    }
+}
+
+pub fn parse_instructions(input: &str) -> Result<Vec<Inst>, Error> {
+    parse_lines(input, parse_instruction)
+}
+
+/// Run the program and return the sum of the signal strengths and the pixels drawn on the CRT.
+pub fn run_and_inspect(instructions: &[Inst]) -> (i64, Vec<bool>) {
+    let mut x: i64 = 1;
+    let mut sig_strength: i64 = 0;
+    let mut cycle_count: i64 = 0;
+    let mut screen: Vec<bool> = vec![false; 240];
+
+    for inst in instructions {
+        if cycle_count > 240 {
+            break;
+        }
+        match inst {
+            Inst::Noop => {
+                if cycle_count % 40 == 19 {
+                    sig_strength += x * (20 + 40 * (cycle_count / 40));
+                }
+                if let Some(pixel) = screen.get_mut(cycle_count as usize) {
+                    *pixel |= cycle_count % 40 >= x - 1 && cycle_count % 40 <= x + 1;
+                }
+                cycle_count += 1;
+            }
+            Inst::Addx(v) => {
+                if cycle_count % 40 == 18 || cycle_count % 40 == 19 {
+                    sig_strength += x * (20 + 40 * (cycle_count / 40));
+                }
+                if let Some(pixel) = screen.get_mut(cycle_count as usize) {
+                    *pixel |= cycle_count % 40 >= x - 1 && cycle_count % 40 <= x + 1;
+                }
+                if let Some(pixel) = screen.get_mut((cycle_count + 1) as usize) {
+                    *pixel |= (cycle_count + 1) % 40 >= x - 1 && (cycle_count + 1) % 40 <= x + 1;
+                }
+                x += v;
+                cycle_count += 2;
+            }
+        };
+    }
+    (sig_strength, screen)
+}
+
+pub fn print_screen(pixels: &[bool]) {
+    print!("{}", render_screen(pixels));
+}
+
+/// Render the CRT pixels as text, one line per row.
+pub fn render_screen(pixels: &[bool]) -> String {
+    let mut screen = String::with_capacity(pixels.len() * 3 + pixels.len() / 40);
+    for row in pixels.chunks_exact(40) {
+        for p in row {
+            screen.push(if *p { '█' } else { '░' });
+        }
+        screen.push('\n');
+    }
+    screen
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    #[test]
+    fn run_and_inspect_works_for_example() {
+        // given
+        let instructions = parse_instructions(EXAMPLE).expect("expected successful parsing");
+        let expected_screen: Vec<bool> = EXAMPLE_SCREEN
+            .chars()
+            .filter_map(|c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(expected_screen.len(), 240);
+
+        // when
+        let (sum, screen) = run_and_inspect(&instructions);
+
+        // then
+        assert_eq!(sum, 13140);
+        println!("actual:");
+        print_screen(&screen);
+        println!("expected:");
+        print_screen(&expected_screen);
+        assert_eq!(screen, expected_screen);
+    }
+
+    const EXAMPLE: &str = r#"addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+"#;
+
+    const EXAMPLE_SCREEN: &str = r#"##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"#;
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_instructions(input);
+            let _ = parse_instruction(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
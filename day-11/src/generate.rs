@@ -0,0 +1,121 @@
+use aoc_common::{GenerateOptions, Rng};
+
+// each monkey tests for its own prime, so their product stays small enough that squaring a worry
+// level below it still fits into an u64
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+struct Monkey {
+    items: Vec<u64>,
+    // None squares the old value
+    operation: (char, Option<u64>),
+    test_mod: u64,
+    test_true: usize,
+    test_false: usize,
+}
+
+/// Monkeys that throw items at each other, never at themselves, and with worry levels that don't
+/// overflow within the 20 rounds of part one. The size is the number of monkeys (between 2 and
+/// 9, each monkey needs its own prime), the harder the puzzle, the more items they start with.
+/// Two monkeys throw their items back and forth too often for one of them to square them.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let n = options.size_or(8).clamp(2, PRIMES.len());
+    let max_items = options.pick(3, 8, 20);
+    for attempt in 0.. {
+        // few monkeys pass the items around so often that squaring or even multiplying
+        // overflows, so they get tamer the longer it takes to find monkeys that don't
+        let multiplying = if attempt < 200 { 0.5 } else { 0.0 };
+        let monkeys = random_monkeys(&mut rng, n, max_items, attempt < 100, multiplying);
+        if !overflows(&monkeys) {
+            return format_monkeys(&monkeys);
+        }
+    }
+    unreachable!("monkeys that only add never overflow")
+}
+
+fn random_monkeys(
+    rng: &mut Rng,
+    n: usize,
+    max_items: i64,
+    squaring: bool,
+    multiplying: f64,
+) -> Vec<Monkey> {
+    let mut primes = PRIMES;
+    rng.shuffle(&mut primes);
+    let squaring = squaring.then(|| rng.below(n));
+    (0..n)
+        .map(|i| {
+            let operation = if Some(i) == squaring {
+                ('*', None)
+            } else if !rng.chance(multiplying) {
+                ('+', Some(rng.between(1, 8) as u64))
+            } else {
+                ('*', Some(rng.between(2, 19) as u64))
+            };
+            let test_true = (i + 1 + rng.below(n - 1)) % n;
+            // with only two monkeys, both targets are the other monkey
+            let mut test_false = (i + 1 + rng.below(n - 1)) % n;
+            while n > 2 && test_false == test_true {
+                test_false = (i + 1 + rng.below(n - 1)) % n;
+            }
+            Monkey {
+                items: (0..rng.between(1, max_items))
+                    .map(|_| rng.between(50, 99) as u64)
+                    .collect(),
+                operation,
+                test_mod: primes[i],
+                test_true,
+                test_false,
+            }
+        })
+        .collect()
+}
+
+// play the 20 rounds of part one, where worry levels are only divided by 3
+fn overflows(monkeys: &[Monkey]) -> bool {
+    let mut items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+    for _ in 0..20 {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[i]) {
+                let operand = monkey.operation.1.unwrap_or(item);
+                let level = match monkey.operation.0 {
+                    '+' => item.checked_add(operand),
+                    _ => item.checked_mul(operand),
+                };
+                let Some(level) = level.map(|level| level / 3) else {
+                    return true;
+                };
+                let target = if level % monkey.test_mod == 0 {
+                    monkey.test_true
+                } else {
+                    monkey.test_false
+                };
+                items[target].push(level);
+            }
+        }
+    }
+    false
+}
+
+fn format_monkeys(monkeys: &[Monkey]) -> String {
+    let blocks: Vec<String> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| {
+            let items: Vec<String> = monkey.items.iter().map(|item| item.to_string()).collect();
+            let operand = match monkey.operation.1 {
+                Some(v) => v.to_string(),
+                None => "old".to_owned(),
+            };
+            format!(
+                "Monkey {i}:\n  Starting items: {}\n  Operation: new = old {} {operand}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                items.join(", "),
+                monkey.operation.0,
+                monkey.test_mod,
+                monkey.test_true,
+                monkey.test_false,
+            )
+        })
+        .collect();
+    blocks.join("\n")
+}
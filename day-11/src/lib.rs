@@ -0,0 +1,304 @@
+//! Day 11: Monkey in the Middle
+
+use aoc_common::{parse_number, Answer, Error, GenerateOptions, Solution};
+use std::mem::swap;
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 11 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 11;
+    type Input<'a> = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_monkeys(input)
+    }
+    fn part1(monkeys: &Self::Input<'_>) -> Result<Answer, Error> {
+        let monkeys_after_20 = run_rounds(monkeys.clone(), 20, 3)?;
+        Ok(monkey_business(&monkeys_after_20).into())
+    }
+    fn part2(monkeys: &Self::Input<'_>) -> Result<Answer, Error> {
+        let monkeys_unlimited_10000 = run_rounds(monkeys.clone(), 10000, 1)?;
+        Ok(monkey_business(&monkeys_unlimited_10000).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Op {
+    Add,
+    Mul,
+}
+
+impl Op {
+    pub fn run(self, left: u64, right: u64) -> u64 {
+        match self {
+            Op::Add => left + right,
+            Op::Mul => left * right,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub operator: Op,
+    // if operand is None, the old value needs to be used
+    pub operand: Option<u64>,
+    pub test_mod: u64,
+    pub test_true: usize,
+    pub test_false: usize,
+    pub inspections: usize,
+}
+
+pub fn parse_monkey(block: &str) -> Result<Monkey, Error> {
+    let mut lines = block.lines().enumerate();
+    let mut next_line = |what: &str| {
+        lines
+            .next()
+            .ok_or_else(|| Error::semantic(format!("Expected line with {what}, found nothing")))
+    };
+    // skip the check for the monkey's ID, assume they are always indexed in order.
+    // Just do a quick sanity check
+    let (i, line) = next_line("monkey id")?;
+    if !line.starts_with("Monkey ") {
+        return Err(
+            Error::syntax(line, line, "Monkey block did not start with 'Monkey '!").at_line(i + 1),
+        );
+    }
+
+    let (i, line) = next_line("starting items")?;
+    let items: Vec<u64> = strip_field(line, "  Starting items: ", "starting items")
+        .and_then(|items| items.split(", ").map(|n| parse_number(line, n)).collect())
+        .map_err(|e| e.at_line(i + 1))?;
+
+    let (i, line) = next_line("inspection operation")?;
+    let (operator, operand) = parse_operation(line).map_err(|e| e.at_line(i + 1))?;
+
+    let (i, line) = next_line("test condition")?;
+    let test_mod: u64 = strip_field(line, "  Test: divisible by ", "test condition")
+        .and_then(|n| parse_number(line, n))
+        .map_err(|e| e.at_line(i + 1))?;
+
+    let (i, line) = next_line("action on fulfilled test condition")?;
+    let test_true: usize = strip_field(line, "    If true: throw to monkey ", "action")
+        .and_then(|n| parse_number(line, n))
+        .map_err(|e| e.at_line(i + 1))?;
+
+    let (i, line) = next_line("action on unfulfilled test condition")?;
+    let test_false: usize = strip_field(line, "    If false: throw to monkey ", "action")
+        .and_then(|n| parse_number(line, n))
+        .map_err(|e| e.at_line(i + 1))?;
+
+    Ok(Monkey {
+        items,
+        operator,
+        operand,
+        test_mod,
+        test_true,
+        test_false,
+        inspections: 0,
+    })
+}
+
+fn strip_field<'a>(line: &'a str, prefix: &str, what: &str) -> Result<&'a str, Error> {
+    line.strip_prefix(prefix)
+        .ok_or_else(|| Error::syntax(line, line, format!("Unable to parse line as {what}")))
+}
+
+fn parse_operation(line: &str) -> Result<(Op, Option<u64>), Error> {
+    let (operator, operand) = strip_field(line, "  Operation: new = old ", "operation")?
+        .split_once(' ')
+        .ok_or_else(|| Error::syntax(line, line, "Unable to split operator and operand"))?;
+    let operator = match operator {
+        "+" => Op::Add,
+        "*" => Op::Mul,
+        _ => {
+            return Err(Error::syntax(
+                line,
+                operator,
+                format!("Unexpected operator: '{operator}'"),
+            ));
+        }
+    };
+    let operand: Option<u64> = if operand == "old" {
+        None
+    } else {
+        Some(parse_number(line, operand)?)
+    };
+    Ok((operator, operand))
+}
+
+pub fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, Error> {
+    let mut line_offset = 0;
+    let mut monkeys = Vec::with_capacity(8);
+    for block in input.split("\n\n") {
+        if !block.is_empty() {
+            monkeys.push(parse_monkey(block).map_err(|e| e.offset_lines(line_offset))?);
+        }
+        line_offset += block.lines().count() + 1;
+    }
+    Ok(monkeys)
+}
+
+/// Let each monkey inspect and throw all of its items once.
+pub fn next_round(
+    mut monkeys: Vec<Monkey>,
+    worry_level_divisor: u64,
+    worry_level_mod: u64,
+) -> Result<Vec<Monkey>, Error> {
+    for monkey_i in 0..monkeys.len() {
+        // this is assuming that a monkey never throws items to itself
+        for item_i in 0..monkeys[monkey_i].items.len() {
+            let monkey = &monkeys[monkey_i];
+            let worry_level = (monkey.operator.run(
+                monkey.items[item_i],
+                monkey.operand.unwrap_or(monkey.items[item_i]),
+            ) / worry_level_divisor)
+                % worry_level_mod;
+            let target_i = if worry_level.is_multiple_of(monkey.test_mod) {
+                monkey.test_true
+            } else {
+                monkey.test_false
+            };
+            if target_i == monkey_i {
+                // sanity check for assumption above
+                return Err(Error::semantic(format!(
+                    "Apparently, monkey {monkey_i} throws things to itself ☹"
+                )));
+            }
+            monkeys
+                .get_mut(target_i)
+                .ok_or_else(|| {
+                    Error::semantic(format!(
+                        "Monkey {monkey_i} tried to throw something to missing monkey {target_i}"
+                    ))
+                })?
+                .items
+                .push(worry_level);
+        }
+        monkeys[monkey_i].inspections += monkeys[monkey_i].items.len();
+        monkeys[monkey_i].items.clear();
+    }
+    Ok(monkeys)
+}
+
+/// Run the given number of rounds. After each inspection, the worry level is divided by `worry_level_divisor`.
+pub fn run_rounds(
+    mut monkeys: Vec<Monkey>,
+    rounds: u64,
+    worry_level_divisor: u64,
+) -> Result<Vec<Monkey>, Error> {
+    // worry_level_divisor and worry_level_mod don't play nice together and I have no patience to
+    // figure out why, so I will only effectively use the modulo if there is a divisor != 1
+    let worry_level_mod: u64 = if worry_level_divisor == 1 {
+        monkeys.iter().map(|m| m.test_mod).product()
+    } else {
+        u64::MAX
+    };
+    for _ in 0..rounds {
+        monkeys = next_round(monkeys, worry_level_divisor, worry_level_mod)?;
+    }
+    Ok(monkeys)
+}
+
+/// Product of the inspection counts of the two most active monkeys.
+pub fn monkey_business(monkeys: &[Monkey]) -> usize {
+    let mut first = 0;
+    let mut second = 0;
+    for monkey in monkeys {
+        if monkey.inspections > second {
+            second = monkey.inspections;
+            if second > first {
+                swap(&mut first, &mut second);
+            }
+        }
+    }
+    first * second
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    #[test]
+    fn run_rounds_works_for_example() {
+        // given
+        let monkeys = parse_monkeys(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let result = run_rounds(monkeys, 20, 3);
+
+        // then
+        let after_20 = result.expect("expected successful run");
+        assert_eq!(monkey_business(&after_20), 10605);
+    }
+
+    #[test]
+    fn run_rounds_works_for_unlimited_worry_level() {
+        // given
+        let monkeys = parse_monkeys(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let result = run_rounds(monkeys, 10000, 1);
+
+        // then
+        let after_20 = result.expect("expected successful run");
+        assert_eq!(monkey_business(&after_20), 2713310158);
+    }
+
+    const EXAMPLE: &str = r#"Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+"#;
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_monkeys(input);
+            let _ = parse_monkey(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
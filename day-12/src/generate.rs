@@ -0,0 +1,51 @@
+use crate::{parse_input, shortest_hiking_trail, shortest_path_length};
+use aoc_common::GenerateOptions;
+use aoc_grid::{Grid, Point};
+
+/// A hill with the best signal on its top, with rocks that are too steep to climb in the way.
+/// There is always a path from the start to the top. The size is the width of the map (at least
+/// 40, it takes 25 steps to climb from a to z), the harder the puzzle, the more rocks there are.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let width = options.size_or(159).max(40);
+    let height = width / 4;
+    let rocks = options.pick(0.05, 0.2, 0.3);
+    loop {
+        let top = Point::new(
+            rng.between(0, width as i64 - 1),
+            rng.between(0, height as i64 - 1),
+        );
+        let farthest = [0, width as i64 - 1]
+            .into_iter()
+            .flat_map(|x| [0, height as i64 - 1].map(|y| Point::new(x, y).manhattan(top)))
+            .max()
+            .unwrap_or(0);
+        // the height goes up by one every `spread` steps towards the top
+        let spread = (farthest / 25).max(1);
+        let mut map = Grid::from_fn(width, height, |p| {
+            let base = 25 - (p.manhattan(top) / spread).min(25) as i64;
+            let h = if rng.chance(rocks) {
+                base + rng.between(2, 5)
+            } else {
+                base
+            };
+            char::from(b'a' + h.min(25) as u8)
+        });
+        map[top] = 'E';
+        let lowest: Vec<Point> = map.points().filter(|p| map[*p] == 'a').collect();
+        if lowest.is_empty() {
+            continue;
+        }
+        map[*rng.choose(&lowest)] = 'S';
+
+        let input = map.render(|c| *c);
+        let Ok((start, end, grid)) = parse_input(&input) else {
+            continue;
+        };
+        if shortest_path_length(&grid, start, end).is_some()
+            && shortest_hiking_trail(&grid, end).is_some()
+        {
+            return input;
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Day 12: Hill Climbing Algorithm
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+use aoc_grid::{Dir4, Grid, Point};
+use aoc_search::{bfs, Graph};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 12 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 12;
+    type Input<'a> = (Point, Point, Heightmap);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1((startpos, endpos, grid): &Self::Input<'_>) -> Result<Answer, Error> {
+        shortest_path_length(grid, *startpos, *endpos)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("There is no way to get up there. Good luck."))
+    }
+    fn part2((_, endpos, grid): &Self::Input<'_>) -> Result<Answer, Error> {
+        shortest_hiking_trail(grid, *endpos)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("There is no hiking trail."))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// The heightmap, with heights from 0 (a) to 25 (z).
+pub type Heightmap = Grid<u8>;
+
+/// Positions next to `p` that are at most one step higher.
+pub fn neighbours(grid: &Heightmap, p: Point) -> impl Iterator<Item = Point> + '_ {
+    let max_height = grid.get(p).map(|h| h + 1).unwrap_or(0);
+    grid.neighbours4(p).filter(move |n| grid[*n] <= max_height)
+}
+
+/// Parse the heightmap and return the start position, the end position and the grid.
+pub fn parse_input(input: &str) -> Result<(Point, Point, Heightmap), Error> {
+    let chars = Grid::parse(input, |c| {
+        Some(c).filter(|c| c.is_ascii_lowercase() || *c == 'S' || *c == 'E')
+    })?;
+    let start = chars
+        .position(|c| *c == 'S')
+        .ok_or_else(|| Error::semantic("Unable to find start marker 'S' in input"))?;
+    let end = chars
+        .position(|c| *c == 'E')
+        .ok_or_else(|| Error::semantic("Unable to find end marker 'E' in input"))?;
+    let grid = chars.map(|c| match c {
+        'S' => 0,
+        'E' => 25,
+        c => *c as u8 - b'a',
+    });
+
+    Ok((start, end, grid))
+}
+
+/// Walking up the hill towards `end`, one step at a time.
+struct Hike<'a> {
+    grid: &'a Heightmap,
+    end: Point,
+}
+
+impl Graph for Hike<'_> {
+    type Node = Point;
+    type Key = Point;
+
+    fn key(&self, p: &Point) -> Point {
+        *p
+    }
+    fn successors(&self, p: &Point) -> impl Iterator<Item = (Point, u64)> {
+        neighbours(self.grid, *p).map(|n| (n, 1))
+    }
+    fn is_goal(&self, p: &Point) -> bool {
+        *p == self.end
+    }
+}
+
+/// The shortest path from `start` to `end`, with both ends included.
+pub fn shortest_path(grid: &Heightmap, start: Point, end: Point) -> Option<Vec<Point>> {
+    bfs(&Hike { grid, end }, [start]).map(|found| found.path)
+}
+
+/// Number of steps of the shortest path from `start` to `end`.
+pub fn shortest_path_length(grid: &Heightmap, start: Point, end: Point) -> Option<u32> {
+    shortest_path(grid, start, end).map(|path| path.len() as u32 - 1)
+}
+
+/// The shortest path from any lowest point to `end`, with both ends included.
+pub fn shortest_hiking_path(grid: &Heightmap, end: Point) -> Option<Vec<Point>> {
+    // starting at all lowest points at once finds the closest one
+    let starts = grid.iter().filter(|(_, h)| **h == 0).map(|(p, _)| p);
+    bfs(&Hike { grid, end }, starts).map(|found| found.path)
+}
+
+/// Number of steps of the shortest path from any lowest point to `end`.
+pub fn shortest_hiking_trail(grid: &Heightmap, end: Point) -> Option<u32> {
+    shortest_hiking_path(grid, end).map(|path| path.len() as u32 - 1)
+}
+
+/// Draw the path over the heightmap: every step is an arrow pointing to the next one, the end of
+/// the path is `E` and the heights off the path are their letters.
+pub fn render_path(grid: &Heightmap, path: &[Point]) -> String {
+    let mut chars = grid.map(|h| (b'a' + h) as char);
+    for step in path.windows(2) {
+        if let Some(dir) = Dir4::ALL.into_iter().find(|d| step[0].step(*d) == step[1]) {
+            chars[step[0]] = dir.arrow();
+        }
+    }
+    if let Some(end) = path.last() {
+        chars[*end] = 'E';
+    }
+    chars.render(|c| *c)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+"#;
+
+    #[test]
+    fn shortest_path_length_works_for_example() {
+        // given
+        let (start, end, grid) = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let len = shortest_path_length(&grid, start, end);
+
+        // then
+        assert_eq!(len, Some(31));
+    }
+
+    #[test]
+    fn shortest_hiking_trail_works_for_example() {
+        // given
+        let (_, end, grid) = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let len = shortest_hiking_trail(&grid, end);
+
+        // then
+        assert_eq!(len, Some(29));
+    }
+
+    #[test]
+    fn render_path_draws_arrows_for_example() {
+        // given
+        let (start, end, grid) = parse_input(EXAMPLE).expect("expected successful parsing");
+        let path = shortest_path(&grid, start, end).expect("expected a path");
+
+        // when
+        let rendered = render_path(&grid, &path);
+
+        // then
+        let expected = r#">>vv<<<<
+abvvv<<^
+acvv>E^^
+acv>>>^^
+ab>>>>>^
+"#;
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,79 @@
+use aoc_common::{GenerateOptions, Rng};
+
+/// Pairs of packets. Half of the pairs are almost the same, so comparing them needs to look
+/// deeper. The size is the number of pairs, the harder the puzzle, the deeper the lists are
+/// nested.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let depth = options.pick(2, 4, 6);
+    let pairs: Vec<String> = (0..options.size_or(150))
+        .map(|_| {
+            let first = random_packet(&mut rng, depth);
+            let second = if rng.chance(0.5) {
+                change_a_number(&mut rng, &first)
+            } else {
+                random_packet(&mut rng, depth)
+            };
+            format!("{first}\n{second}\n")
+        })
+        .collect();
+    pairs.join("\n")
+}
+
+fn random_packet(rng: &mut Rng, depth: u32) -> String {
+    let items: Vec<String> = (0..rng.between(0, 5))
+        .map(|_| {
+            if depth > 0 && rng.chance(0.3) {
+                random_packet(rng, depth - 1)
+            } else {
+                rng.between(0, 10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+// the same packet, with one of the numbers in it replaced
+fn change_a_number(rng: &mut Rng, packet: &str) -> String {
+    let numbers: Vec<usize> = packet
+        .char_indices()
+        .filter(|(i, c)| {
+            c.is_ascii_digit() && !packet[..*i].ends_with(|p: char| p.is_ascii_digit())
+        })
+        .map(|(i, _)| i)
+        .collect();
+    let Some(start) = numbers.get(rng.below(numbers.len().max(1))) else {
+        return packet.to_owned();
+    };
+    let end = packet[*start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(packet.len(), |len| start + len);
+    format!(
+        "{}{}{}",
+        &packet[..*start],
+        rng.between(0, 10),
+        &packet[end..]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_packet;
+
+    #[test]
+    fn packets_survive_printing_and_parsing() {
+        // given
+        let mut rng = Rng::new(13);
+
+        for _ in 0..500 {
+            let packet = random_packet(&mut rng, 6);
+
+            // when
+            let parsed = parse_packet(&packet).expect("expected successful parsing");
+
+            // then
+            assert_eq!(parsed.to_string(), packet);
+        }
+    }
+}
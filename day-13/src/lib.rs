@@ -0,0 +1,319 @@
+//! Day 13: Distress Signal
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+use std::cmp::Ordering;
+use std::fmt;
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 13 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 13;
+    type Input<'a> = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1(pairs: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(ordered_pairs_index_sum(pairs).into())
+    }
+    fn part2(pairs: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(find_decoder_key(pairs).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+#[derive(Clone, Eq, Debug)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(left), Packet::Int(right)) => left.cmp(right),
+            (Packet::List(left), Packet::List(right)) => left.cmp(right),
+            (Packet::Int(left), Packet::List(right)) => {
+                (&[Packet::Int(*left)] as &[Packet]).cmp(right as &[Packet])
+            }
+            (Packet::List(left), Packet::Int(right)) => {
+                (left as &[Packet]).cmp(&[Packet::Int(*right)] as &[Packet])
+            }
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+/// Packets are written the way they are parsed, e.g. `[1,[2,3]]`.
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Packet::Int(v) => write!(f, "{v}"),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl Packet {
+    pub fn push(&mut self, p: Packet) -> Result<(), Error> {
+        match self {
+            Packet::List(sub) => sub.push(p),
+            _ => return Err(Error::semantic("tried to push to int packet")),
+        };
+        Ok(())
+    }
+}
+
+pub fn parse_packet(line: &str) -> Result<Packet, Error> {
+    let mut stack: Vec<Packet> = Vec::with_capacity(line.len());
+    for (i, c) in line.char_indices() {
+        let span = &line[i..i + c.len_utf8()];
+        let unexpected = |msg: &str| Error::syntax(line, span, msg);
+        let push = |top: &mut Packet, p: Packet| top.push(p).map_err(|e| e.with_span(line, span));
+        match c {
+            '[' => {
+                stack.push(Packet::List(vec![]));
+            }
+            ']' => {
+                let sub = stack
+                    .pop()
+                    .ok_or_else(|| unexpected("unexpected closing bracket"))?;
+                match sub {
+                    Packet::List(_) => {
+                        if let Some(top) = stack.last_mut() {
+                            push(top, sub)?;
+                        } else {
+                            return Ok(sub);
+                        }
+                    }
+                    Packet::Int(_) => {
+                        let mut parent = stack
+                            .pop()
+                            .ok_or_else(|| unexpected("unexpected closing bracket"))?;
+                        push(&mut parent, sub)?;
+                        if let Some(top) = stack.last_mut() {
+                            push(top, parent)?;
+                        } else {
+                            return Ok(parent);
+                        }
+                    }
+                }
+            }
+            ',' => {
+                let sub = stack.pop().ok_or_else(|| unexpected("unexpected comma"))?;
+                match sub {
+                    Packet::Int(_) => {
+                        if let Some(top) = stack.last_mut() {
+                            push(top, sub)?;
+                        } else {
+                            return Err(unexpected(
+                                "Found comma, but nothing to append the int to",
+                            ));
+                        }
+                    }
+                    // we don't check if the comma was misplaced (for now)
+                    _ => stack.push(sub),
+                };
+            }
+            _ => {
+                let d = c
+                    .to_digit(10)
+                    .ok_or_else(|| unexpected(&format!("Unexpected char in input: '{c}'")))?;
+                if let Some(Packet::Int(i)) = stack.last_mut() {
+                    *i = i
+                        .checked_mul(10)
+                        .and_then(|i| i.checked_add(d))
+                        .ok_or_else(|| unexpected("number is too large"))?;
+                } else {
+                    stack.push(Packet::Int(d));
+                }
+            }
+        }
+    }
+    if stack.len() != 1 {
+        Err(Error::syntax(
+            line,
+            &line[line.len()..],
+            format!(
+                "unexpected end of line, {} elements on the stack: {:?}",
+                stack.len(),
+                stack
+            ),
+        ))
+    } else {
+        Ok(stack.pop().unwrap())
+    }
+}
+
+pub fn parse_pair(lines: &str) -> Result<(Packet, Packet), Error> {
+    let (first, second) = lines
+        .split_once('\n')
+        .ok_or_else(|| Error::syntax(lines, lines, "expected packets to show up in pairs"))?;
+    Ok((
+        parse_packet(first).map_err(|e| e.at_line(1))?,
+        parse_packet(second.trim()).map_err(|e| e.at_line(2))?,
+    ))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<(Packet, Packet)>, Error> {
+    let mut line_offset = 0;
+    let mut pairs = Vec::with_capacity(256);
+    for block in input.split("\n\n") {
+        pairs.push(parse_pair(block).map_err(|e| e.at_line(1).offset_lines(line_offset))?);
+        line_offset += block.lines().count() + 1;
+    }
+    Ok(pairs)
+}
+
+/// Sum of the (1-based) indices of pairs that are in the right order.
+pub fn ordered_pairs_index_sum(pairs: &[(Packet, Packet)]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (first, second))| first < second)
+        .map(|(i, _)| i + 1)
+        .sum::<usize>()
+}
+
+/// Product of the positions of the divider packets if all packets were sorted.
+pub fn find_decoder_key(pairs: &[(Packet, Packet)]) -> usize {
+    let divider2 = Packet::List(vec![Packet::List(vec![Packet::Int(2)])]);
+    let divider6 = Packet::List(vec![Packet::List(vec![Packet::Int(6)])]);
+    // we don't even have to sort this, we just need to calculate the number of smaller packets
+    let i_div2 = pairs
+        .iter()
+        .flat_map(|(a, b)| [a, b])
+        .filter(|p| *p < &divider2)
+        .count()
+        + 1;
+    let i_div6 = pairs
+        .iter()
+        .flat_map(|(a, b)| [a, b])
+        .filter(|p| *p < &divider6)
+        .count()
+        + 2;
+    i_div2 * i_div6
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    #[test]
+    fn packet_cmp_works_as_specified() {
+        assert_eq!(Packet::Int(2).cmp(&Packet::Int(3)), Ordering::Less);
+        assert_eq!(
+            Packet::List(vec![]).cmp(&Packet::List(vec![Packet::Int(0)])),
+            Ordering::Less
+        );
+        assert_eq!(
+            Packet::List(vec![Packet::Int(0)]).cmp(&Packet::List(vec![Packet::Int(1)])),
+            Ordering::Less
+        );
+        assert_eq!(
+            Packet::List(vec![Packet::Int(0)]).cmp(&Packet::Int(1)),
+            Ordering::Less
+        );
+        assert_eq!(
+            Packet::Int(0).cmp(&Packet::List(vec![Packet::Int(1)])),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn ordered_pairs_index_sum_works_for_example() {
+        // given
+        let pairs = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let sum = ordered_pairs_index_sum(&pairs);
+
+        // then
+        assert_eq!(sum, 13);
+    }
+
+    #[test]
+    fn find_decoder_key_works_for_example() {
+        // given
+        let pairs = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let key = find_decoder_key(&pairs);
+
+        // then
+        assert_eq!(key, 140);
+    }
+
+    const EXAMPLE: &str = r#"[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+"#;
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_pair(input);
+            let _ = parse_packet(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,39 @@
+use aoc_common::GenerateOptions;
+
+/// Paths of rock below the point where the sand enters: cups, which hold sand, and single lines.
+/// The size is the number of paths, the harder the puzzle, the deeper the cave.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let depth = options.pick(40, 170, 400);
+    // the sand spreads out as far as it falls, so the rocks do so as well
+    let spread = depth / 2;
+    // a wide cup at the bottom, right below the sand, so some sand comes to rest in part 1
+    let mut paths = vec![cup(
+        500 - spread / 2,
+        depth - spread / 4,
+        spread,
+        spread / 4,
+    )];
+    for _ in 1..options.size_or(150) {
+        let x = 500 + rng.between(-spread, spread);
+        let y = rng.between(2, depth);
+        let width = rng.between(1, 8);
+        let height = rng.between(1, 8);
+        paths.push(match rng.below(3) {
+            0 => cup(x, y.min(depth - height), width, height),
+            1 => format!("{x},{y} -> {},{y}", x + width),
+            _ => format!("{x},{y} -> {x},{}", (y + height).min(depth)),
+        });
+    }
+    paths.iter().map(|path| path.clone() + "\n").collect()
+}
+
+// a cup with its top left corner at (x, y)
+fn cup(x: i64, y: i64, width: i64, height: i64) -> String {
+    let bottom = y + height;
+    format!(
+        "{x},{y} -> {x},{bottom} -> {},{bottom} -> {},{y}",
+        x + width,
+        x + width
+    )
+}
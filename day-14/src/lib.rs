@@ -0,0 +1,268 @@
+//! Day 14: Regolith Reservoir
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+use aoc_grid::{Point, SparseGrid};
+
+mod generate;
+mod visualize;
+
+pub use generate::generate_input;
+pub use visualize::SandSimulation;
+
+/// Solves both parts of day 14 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 14;
+    type Input<'a> = (Cave, i64);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        init_cave(&parse_input(input)?)
+    }
+    fn part1((initial_cave, height): &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(drop_until_overflow(initial_cave.clone(), *height).into())
+    }
+    fn part2((initial_cave, height): &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(drop_to_floor_until_block(initial_cave.clone(), *height).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+pub type RockPath = Vec<Point>;
+
+/// The largest coordinate of a rock. Real caves are a few hundred tiles wide and deep, this keeps
+/// a broken input from filling the memory with rocks.
+pub const MAX_COORDINATE: i64 = 10_000;
+
+/// Parse a line of points. Consecutive points have to be on a horizontal or vertical line.
+pub fn parse_path(line: &str) -> Result<RockPath, Error> {
+    let mut path = RockPath::new();
+    for pair in line.split(" -> ") {
+        let (xs, ys) = pair
+            .split_once(',')
+            .ok_or_else(|| Error::syntax(line, pair, "Unable to split pair"))?;
+        let x: i64 = parse_coordinate(line, xs)?;
+        let y: i64 = parse_coordinate(line, ys)?;
+        let p = Point::new(x, y);
+        if let Some(last) = path.last().filter(|last| last.x != x && last.y != y) {
+            return Err(Error::syntax(
+                line,
+                pair,
+                format!("line {last} -> {p} is not parallel to any axis"),
+            ));
+        }
+        path.push(p);
+    }
+    Ok(path)
+}
+
+fn parse_coordinate(line: &str, span: &str) -> Result<i64, Error> {
+    let v: i64 = parse_number(line, span)?;
+    if !(0..=MAX_COORDINATE).contains(&v) {
+        return Err(Error::syntax(
+            line,
+            span,
+            format!("coordinate out of range 0..={MAX_COORDINATE}"),
+        ));
+    }
+    Ok(v)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<RockPath>, Error> {
+    parse_lines(input, parse_path)
+}
+
+/// What fills a tile of the cave. Empty tiles are not stored at all.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+pub type Cave = SparseGrid<Tile>;
+
+/// Where sand enters the cave.
+pub const SAND_ORIGIN: Point = Point::new(500, 0);
+
+// sand tries to fall straight down first, then diagonally to the left and then to the right
+const FALL: [Point; 3] = [Point::new(0, 1), Point::new(-1, 1), Point::new(1, 1)];
+
+/// Draw the rock paths into the cave. Also returns the height of the floor.
+pub fn init_cave(paths: &[RockPath]) -> Result<(Cave, i64), Error> {
+    let height = paths
+        .iter()
+        .flat_map(|path| path.iter().map(|p| p.y))
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let mut cave = Cave::with_capacity((height * height) as usize);
+
+    for path in paths {
+        for step in path.windows(2) {
+            draw_line(&mut cave, step[0], step[1])?;
+        }
+    }
+    Ok((cave, height))
+}
+
+fn sort(a: i64, b: i64) -> (i64, i64) {
+    if a > b {
+        (b, a)
+    } else {
+        (a, b)
+    }
+}
+
+/// Draw a horizontal or vertical line of rock. Other lines are an error.
+pub fn draw_line(cave: &mut Cave, a: Point, b: Point) -> Result<(), Error> {
+    if a.x == b.x {
+        let (yfrom, yto) = sort(a.y, b.y);
+        for y in yfrom..=yto {
+            cave.insert(Point::new(a.x, y), Tile::Rock);
+        }
+    } else if a.y == b.y {
+        let (xfrom, xto) = sort(a.x, b.x);
+        for x in xfrom..=xto {
+            cave.insert(Point::new(x, a.y), Tile::Rock);
+        }
+    } else {
+        return Err(Error::semantic(format!(
+            "line {a} -> {b} is not parallel to any axis"
+        )));
+    }
+    Ok(())
+}
+
+// the next position of a falling unit of sand, if it can move at all
+fn fall(cave: &Cave, p: Point) -> Option<Point> {
+    FALL.into_iter()
+        .map(|offset| p + offset)
+        .find(|next| !cave.contains(*next))
+}
+
+/// Drop one unit of sand. Returns true if the sand settled inside the grid, false if it fell
+/// into the abyss or the sand origin is already occupied.
+pub fn drop_sand(cave: &mut Cave, height: i64) -> bool {
+    if cave.contains(SAND_ORIGIN) {
+        return false;
+    }
+    let mut p = SAND_ORIGIN;
+    while p.y < height {
+        match fall(cave, p) {
+            Some(next) => p = next,
+            None => break,
+        }
+    }
+    if p.y < height {
+        cave.insert(p, Tile::Sand);
+        true
+    } else {
+        false
+    }
+}
+
+/// Number of units of sand that come to rest before sand starts falling into the abyss.
+pub fn drop_until_overflow(mut cave: Cave, height: i64) -> usize {
+    let intial_blocks = cave.len();
+    while drop_sand(&mut cave, height) {}
+    cave.len() - intial_blocks
+}
+
+/// Drop one unit of sand onto the floor. Returns true if the sand could be placed.
+pub fn drop_sand_with_floor(cave: &mut Cave, height: i64) -> bool {
+    if cave.contains(SAND_ORIGIN) {
+        return false;
+    }
+    let mut p = SAND_ORIGIN;
+    while p.y + 1 < height {
+        match fall(cave, p) {
+            Some(next) => p = next,
+            None => break,
+        }
+    }
+    cave.insert(p, Tile::Sand);
+    true
+}
+
+/// Number of units of sand that come to rest on the floor until the source is blocked.
+pub fn drop_to_floor_until_block(mut cave: Cave, height: i64) -> usize {
+    let initial_blocks = cave.len();
+    while drop_sand_with_floor(&mut cave, height) {}
+    cave.len() - initial_blocks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+"#;
+
+    #[test]
+    fn drop_until_overflow_works_for_example() {
+        // given
+        let paths = parse_input(EXAMPLE).expect("expeced successful parsing");
+        let (grid, height) = init_cave(&paths).expect("expected axis-parallel lines");
+
+        // when
+        let count = drop_until_overflow(grid, height);
+
+        // then
+        assert_eq!(count, 24);
+    }
+
+    #[test]
+    fn drop_to_floor_until_block_works_for_example() {
+        // given
+        let paths = parse_input(EXAMPLE).expect("expeced successful parsing");
+        let (grid, height) = init_cave(&paths).expect("expected axis-parallel lines");
+
+        // when
+        let count = drop_to_floor_until_block(grid, height);
+
+        // then
+        assert_eq!(count, 93);
+    }
+
+    #[test]
+    fn diagonal_lines_are_rejected() {
+        // given
+        let line = "498,4 -> 498,6 -> 496,8";
+
+        // when
+        let parsed = parse_path(line);
+        let drawn = init_cave(&[vec![Point::new(498, 4), Point::new(496, 6)]]);
+
+        // then
+        let location = parsed
+            .expect_err("expected a syntax error")
+            .location
+            .expect("expected a location");
+        assert_eq!(location.column, 19);
+        assert!(drawn.is_err());
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_path(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
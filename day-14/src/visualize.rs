@@ -0,0 +1,108 @@
+use crate::{drop_sand, drop_sand_with_floor, Cave, Tile, SAND_ORIGIN};
+use aoc_grid::{Bounds, Point};
+use aoc_tui::Simulation;
+
+/// Sand falling into the cave, one unit at a time. Once sand flows into the abyss, the floor of
+/// part two shows up and the sand keeps falling until it blocks the source.
+#[derive(Clone, Debug)]
+pub struct SandSimulation {
+    cave: Cave,
+    height: i64,
+    rocks: usize,
+    // the units of sand that settled in part one, once there is a floor
+    settled_without_floor: Option<usize>,
+}
+
+impl SandSimulation {
+    pub fn new(cave: Cave, height: i64) -> SandSimulation {
+        SandSimulation {
+            rocks: cave.len(),
+            cave,
+            height,
+            settled_without_floor: None,
+        }
+    }
+
+    fn sand(&self) -> usize {
+        self.cave.len() - self.rocks
+    }
+}
+
+impl Simulation for SandSimulation {
+    fn step(&mut self) -> bool {
+        if self.settled_without_floor.is_none() {
+            if drop_sand(&mut self.cave, self.height) {
+                return true;
+            }
+            self.settled_without_floor = Some(self.sand());
+        }
+        drop_sand_with_floor(&mut self.cave, self.height)
+    }
+
+    /// Rocks are drawn as `#`, sand as `o` and the source of the sand as `+`.
+    fn render(&self) -> String {
+        let Some(bounds) = Bounds::<Point>::of(self.cave.points().chain([SAND_ORIGIN])) else {
+            return String::new();
+        };
+        let bounds = if self.settled_without_floor.is_some() {
+            Bounds::new(bounds.min, Point::new(bounds.max.x, self.height - 1))
+        } else {
+            bounds
+        };
+        let mut s = self.cave.render(bounds, '.', |tile| match tile {
+            Tile::Rock => '#',
+            Tile::Sand => 'o',
+        });
+        if !self.cave.contains(SAND_ORIGIN) {
+            let origin = (SAND_ORIGIN - bounds.min).x as usize;
+            s.replace_range(origin..=origin, "+");
+        }
+        if self.settled_without_floor.is_some() {
+            s += &"#".repeat(bounds.width() as usize);
+            s.push('\n');
+        }
+        s
+    }
+
+    fn status(&self) -> String {
+        match self.settled_without_floor {
+            None => format!("{} units of sand settled", self.sand()),
+            Some(settled) => format!(
+                "{settled} units of sand settled without floor, {} with floor",
+                self.sand()
+            ),
+        }
+    }
+
+    fn colour(&self, c: char) -> Option<(u8, u8, u8)> {
+        match c {
+            '#' => Some((140, 120, 110)),
+            'o' => Some((230, 200, 90)),
+            '+' => Some((230, 60, 60)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{init_cave, parse_input};
+
+    #[test]
+    fn sand_simulation_runs_both_parts() {
+        // given
+        let paths = parse_input("498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n")
+            .expect("expected successful parsing");
+        let (cave, height) = init_cave(&paths).expect("expected axis-parallel lines");
+        let mut simulation = SandSimulation::new(cave, height);
+
+        // when
+        while simulation.step() {}
+
+        // then
+        assert_eq!(simulation.settled_without_floor, Some(24));
+        assert_eq!(simulation.sand(), 93);
+        assert!(simulation.render().ends_with("#####################\n"));
+    }
+}
@@ -0,0 +1,44 @@
+use crate::SEARCH_LIMIT;
+use aoc_common::GenerateOptions;
+
+/// Sensors that cover the whole search area except for a single blind spot, where the distress
+/// beacon is. Four sensors at the corners of a square around the blind spot just miss it and
+/// cover everything else, the others are for decoration and never reach the blind spot. The size
+/// is the number of decorative sensors, the harder the puzzle, the closer they come to the blind
+/// spot.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let reach = options.pick(0.3, 0.7, 1.0);
+    let blind_spot = (rng.between(0, SEARCH_LIMIT), rng.between(0, SEARCH_LIMIT));
+    let dist = |(x1, y1): (i64, i64), (x2, y2): (i64, i64)| (x1 - x2).abs() + (y1 - y2).abs();
+
+    let mut sensors: Vec<((i64, i64), (i64, i64))> = Vec::with_capacity(options.size_or(24) + 4);
+    for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        let sensor = (
+            blind_spot.0 + dx * SEARCH_LIMIT,
+            blind_spot.1 + dy * SEARCH_LIMIT,
+        );
+        // one step closer to the sensor than the blind spot
+        let beacon = (blind_spot.0 + dx, blind_spot.1);
+        sensors.push((sensor, beacon));
+    }
+    for _ in 0..options.size_or(24) {
+        let sensor = (rng.between(0, SEARCH_LIMIT), rng.between(0, SEARCH_LIMIT));
+        let max_range = ((dist(sensor, blind_spot) - 1) as f64 * reach) as i64;
+        if max_range < 1 {
+            continue;
+        }
+        let range = rng.between(1, max_range);
+        let dx = rng.between(-range, range);
+        let dy = (range - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+        sensors.push((sensor, (sensor.0 + dx, sensor.1 + dy)));
+    }
+    rng.shuffle(&mut sensors);
+
+    sensors
+        .iter()
+        .map(|((sx, sy), (bx, by))| {
+            format!("Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}\n")
+        })
+        .collect()
+}
@@ -0,0 +1,209 @@
+//! Day 15: Beacon Exclusion Zone
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 15 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 15;
+    type Input<'a> = Vec<(P, P)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1(sensors: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(covered_cells_in_row(sensors, 2000000).into())
+    }
+    fn part2(sensors: &Self::Input<'_>) -> Result<Answer, Error> {
+        find_tuning_frequency(sensors)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("Unable to find blind spot"))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+pub type P = (i64, i64);
+
+/// Parse a sensor and the closest beacon to it.
+pub fn parse_sensor(line: &str) -> Result<(P, P), Error> {
+    let (sensor, beacon) = line
+        .split_once(": closest beacon is at x=")
+        .ok_or_else(|| Error::syntax(line, line, "malformed sensor/beacon line"))?;
+
+    let (sensor_x, sensor_y) = sensor
+        .split_once(", y=")
+        .ok_or_else(|| Error::syntax(line, sensor, "malformed sensor coordinates"))?;
+    let sensor_x = sensor_x
+        .strip_prefix("Sensor at x=")
+        .ok_or_else(|| Error::syntax(line, sensor_x, "sensor x is missing a correct prefix"))?;
+    let sensor_x: i64 = parse_number(line, sensor_x)?;
+    let sensor_y: i64 = parse_number(line, sensor_y)?;
+
+    let (beacon_x, beacon_y) = beacon
+        .split_once(", y=")
+        .ok_or_else(|| Error::syntax(line, beacon, "malformed beacon coordinates"))?;
+    let beacon_x: i64 = parse_number(line, beacon_x)?;
+    let beacon_y: i64 = parse_number(line, beacon_y)?;
+
+    Ok(((sensor_x, sensor_y), (beacon_x, beacon_y)))
+}
+
+pub fn parse_input(content: &str) -> Result<Vec<(P, P)>, Error> {
+    parse_lines(content, parse_sensor)
+}
+
+fn dist((x1, y1): P, (x2, y2): P) -> i64 {
+    (x1 - x2).abs() + (y1 - y2).abs()
+}
+
+fn row_ranges(sensors: &[(P, P)], row: i64, ranges: &mut Vec<(i64, bool)>) {
+    ranges.clear();
+    ranges.extend(
+        sensors
+            .iter()
+            .filter_map(|(sensor, beacon)| {
+                let range = dist(*sensor, *beacon);
+                let row_dist = (sensor.1 - row).abs();
+                if range >= row_dist {
+                    let row_range = range - row_dist;
+                    Some([(sensor.0 - row_range, false), (sensor.0 + row_range, true)])
+                } else {
+                    None
+                }
+            })
+            .flatten(),
+    );
+
+    ranges.sort_unstable_by(|(v1, is_to1), (v2, is_to2)| {
+        let ord = v1.cmp(v2);
+        if ord.is_eq() {
+            is_to1.cmp(is_to2)
+        } else {
+            ord
+        }
+    });
+}
+
+/// Number of cells in `row` where no beacon can be.
+pub fn covered_cells_in_row(sensors: &[(P, P)], row: i64) -> i64 {
+    let mut ranges = Vec::with_capacity(sensors.len() * 2);
+    row_ranges(sensors, row, &mut ranges);
+
+    let mut depth: usize = 0;
+    let mut from = i64::MIN;
+    let mut count: i64 = 0;
+    for (v, is_to) in ranges {
+        if is_to {
+            depth -= 1;
+            if depth == 0 {
+                count += v - from;
+            }
+        } else {
+            if depth == 0 {
+                from = v;
+            }
+            depth += 1;
+        }
+    }
+    count
+}
+
+/// The maximum x and y coordinate of the distress beacon.
+pub const SEARCH_LIMIT: i64 = 4_000_000;
+
+/// Find the only position within the search area that no sensor covers.
+pub fn find_tuning_frequency(sensors: &[(P, P)]) -> Option<i64> {
+    let mut ranges = Vec::with_capacity(sensors.len() * 2);
+    // let's half-ass this. Checking each row should be reasonably fast with the approach chosen
+    // for part 1, so doing it 4M times should not be a problem.
+    // also: just take the first uncovered cell as result.
+    for y in 0..=SEARCH_LIMIT {
+        row_ranges(sensors, y, &mut ranges);
+        let mut depth: usize = 0;
+        let mut x = 0;
+        for (c, is_to) in ranges.iter().copied() {
+            if is_to {
+                depth -= 1;
+                x = c;
+            } else {
+                if depth == 0 && x + 1 < c {
+                    return Some((x + 1) * SEARCH_LIMIT + y);
+                }
+                depth += 1;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+"#;
+
+    #[test]
+    fn covered_cells_in_row_works_for_example() {
+        // given
+        let sensors = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let count = covered_cells_in_row(&sensors, 10);
+
+        // then
+        assert_eq!(count, 26);
+    }
+
+    #[test]
+    fn find_tuning_frequency_works_for_example() {
+        // given
+        let sensors = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let freq = find_tuning_frequency(&sensors);
+
+        // then
+        assert_eq!(freq, Some(56000011));
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_sensor(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
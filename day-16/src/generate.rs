@@ -0,0 +1,129 @@
+use aoc_common::{GenerateOptions, Rng};
+use std::collections::HashSet;
+
+const MAX_SIZE: usize = 15;
+
+/// A connected network of valves. Valves without flow rate (except for the start `AA`) are only
+/// corridors with exactly two tunnels, and `AA` has at least three tunnels, like in the real
+/// puzzle. The size is the number of valves with a flow rate, from 3 up to the 15 of a real input:
+/// the search for part 2 takes seconds with 15 such valves and minutes with 20. The harder the
+/// puzzle, the more tunnels there are between them.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let n = options.size_or(MAX_SIZE).clamp(3, MAX_SIZE);
+    let extra_tunnels = options.pick(0, n / 2, n);
+
+    // AA is valve 0, the valves with a flow rate come after it
+    let mut names: Vec<String> = vec!["AA".to_owned()];
+    let mut taken: HashSet<String> = HashSet::from(["AA".to_owned()]);
+    let mut flow_rates: Vec<u32> = vec![0];
+    for _ in 0..n {
+        names.push(new_name(&mut rng, &mut taken));
+        flow_rates.push(rng.between(1, 25) as u32);
+    }
+
+    // a spanning tree keeps everything connected, AA gets the first three valves
+    let mut pairs: HashSet<(usize, usize)> = HashSet::new();
+    for i in 1..=n {
+        let other = if i <= 3 { 0 } else { rng.below(i) };
+        pairs.insert((other, i));
+    }
+    for _ in 0..extra_tunnels {
+        let a = rng.below(n + 1);
+        let b = rng.below(n + 1);
+        if a != b {
+            pairs.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    let mut neighbours: Vec<Vec<usize>> = vec![Vec::new(); n + 1];
+    let mut pairs: Vec<(usize, usize)> = pairs.into_iter().collect();
+    pairs.sort_unstable();
+    for (a, b) in pairs {
+        // some tunnels lead through a few broken valves
+        let mut previous = a;
+        for _ in 0..rng.between(0, 3) {
+            names.push(new_name(&mut rng, &mut taken));
+            flow_rates.push(0);
+            neighbours.push(Vec::new());
+            let corridor = names.len() - 1;
+            neighbours[previous].push(corridor);
+            neighbours[corridor].push(previous);
+            previous = corridor;
+        }
+        neighbours[previous].push(b);
+        neighbours[b].push(previous);
+    }
+
+    let mut lines: Vec<String> = (0..names.len())
+        .map(|i| {
+            let tunnels: Vec<&str> = neighbours[i].iter().map(|j| names[*j].as_str()).collect();
+            let tunnels = match tunnels[..] {
+                [single] => format!("tunnel leads to valve {single}"),
+                _ => format!("tunnels lead to valves {}", tunnels.join(", ")),
+            };
+            format!(
+                "Valve {} has flow rate={}; {tunnels}",
+                names[i], flow_rates[i]
+            )
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+fn new_name(rng: &mut Rng, taken: &mut HashSet<String>) -> String {
+    loop {
+        let name: String = (0..2)
+            .map(|_| char::from(b'A' + rng.below(26) as u8))
+            .collect();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Solver;
+    use aoc_common::{solve, Difficulty};
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            for size in [3, 8, 12] {
+                // given
+                let input = generate_input(&GenerateOptions {
+                    size: Some(size),
+                    seed: size as u64,
+                    difficulty,
+                });
+
+                // when
+                let result = solve::<Solver>(&input);
+
+                // then
+                let result = result.unwrap_or_else(|e| panic!("size {size}, {difficulty:?}: {e}"));
+                assert!(result.part1.is_ok(), "size {size}, {difficulty:?}");
+                assert!(result.part2.is_ok(), "size {size}, {difficulty:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn size_is_capped_at_a_real_input() {
+        // given
+        let options = GenerateOptions {
+            size: Some(50),
+            ..Default::default()
+        };
+
+        // when
+        let input = generate_input(&options);
+
+        // then
+        let working = input.lines().filter(|l| !l.contains("rate=0;")).count();
+        assert_eq!(working, MAX_SIZE);
+    }
+}
@@ -0,0 +1,475 @@
+//! Day 16: Proboscidea Volcanium
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+use aoc_search::{branch_and_bound, distances, Graph, Optimisation};
+use std::collections::HashMap;
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 16 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 16;
+    type Input<'a> = HashMap<&'a str, Valve<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        simplify_broken_valves(parse_input(input)?).ok_or_else(|| {
+            Error::semantic(
+                "Unable to simplify broken valves, something must be wrong with the input",
+            )
+        })
+    }
+    fn part1(valves: &Self::Input<'_>) -> Result<Answer, Error> {
+        find_max_release(valves)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("There must be something wrong with our map…"))
+    }
+    fn part2(valves: &Self::Input<'_>) -> Result<Answer, Error> {
+        find_max_release_with_support(valves)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("No really, there must be something wrong."))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// A valve and its tunnels to other valves, with the time it takes to walk each tunnel.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Valve<'s> {
+    pub id: &'s str,
+    pub flow_rate: u32,
+    pub neighbours: Vec<(&'s str, u32)>,
+}
+
+pub fn parse_valve(line: &str) -> Result<Valve<'_>, Error> {
+    let (valve, connections) = line
+        .split_once(';')
+        .ok_or_else(|| Error::syntax(line, line, "unable to split line"))?;
+
+    let (id, flow_rate) = valve
+        .split_once(" has flow rate=")
+        .ok_or_else(|| Error::syntax(line, valve, "unable to split ID from flow rate"))?;
+    let id = id
+        .strip_prefix("Valve ")
+        .ok_or_else(|| Error::syntax(line, id, "invalid prefix in valve ID"))?;
+    let flow_rate: u32 = parse_number(line, flow_rate)?;
+
+    let neighbours: Vec<(&str, u32)> = connections
+        .strip_prefix(" tunnels lead to valves ")
+        .or_else(|| connections.strip_prefix(" tunnel leads to valve "))
+        .ok_or_else(|| Error::syntax(line, connections, "invalid prefix for neighbours"))?
+        .split(", ")
+        .map(|id| (id, 1))
+        .collect();
+
+    Ok(Valve {
+        id,
+        flow_rate,
+        neighbours,
+    })
+}
+
+/// Remove valves with a flow rate of 0 and connect their neighbours directly.
+/// Returns None if there are broken references in the input.
+/// Assumes this is an undirected graph.
+pub fn simplify_broken_valves<'s>(
+    mut valves: HashMap<&'s str, Valve<'s>>,
+) -> Option<HashMap<&'s str, Valve<'s>>> {
+    let broken_valves: Vec<&str> = valves
+        .values()
+        .filter(|valve| valve.flow_rate == 0)
+        .map(|valve| valve.id)
+        .collect();
+    for valve_id in broken_valves {
+        let broken_valve = valves.remove(valve_id)?;
+        // assumption here is that all broken valves have exactly two neighbours. I they haven't
+        // just re-insert the broken valve
+        if broken_valve.neighbours.len() != 2 {
+            valves.insert(broken_valve.id, broken_valve);
+            continue;
+        }
+        let (n0, d0) = broken_valve.neighbours[0];
+        let (n1, d1) = broken_valve.neighbours[1];
+
+        let first_valve = valves.get_mut(n0)?;
+        first_valve
+            .neighbours
+            .retain(|(id, _)| *id != broken_valve.id);
+        first_valve.neighbours.push((n1, d0 + d1));
+
+        let second_valve = valves.get_mut(n1)?;
+        second_valve
+            .neighbours
+            .retain(|(id, _)| *id != broken_valve.id);
+        second_valve.neighbours.push((n0, d0 + d1));
+    }
+    Some(valves)
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+struct State<'s> {
+    pos: &'s str,
+    elephant_pos: &'s str,
+    time_left: u32,
+    elephant_time_left: u32,
+    valves_open: Vec<&'s str>,
+}
+
+impl<'s> State<'s> {
+    // wil return None if not enough time is left
+    fn with_move_and_open_valve(
+        &self,
+        valve: &Valve<'s>,
+        dist: u32,
+        human: bool,
+    ) -> Option<(Self, u32)> {
+        let (time_left, pos) = if human {
+            (self.time_left.checked_sub(1 + dist)?, valve.id)
+        } else {
+            (self.time_left, self.pos)
+        };
+
+        let (elephant_time_left, elephant_pos) = if !human {
+            (self.elephant_time_left.checked_sub(1 + dist)?, valve.id)
+        } else {
+            (self.elephant_time_left, self.elephant_pos)
+        };
+
+        let mut valves_open: Vec<&'s str> = Vec::with_capacity(self.valves_open.len() + 1);
+        let p = self.valves_open.partition_point(|id| *id < valve.id);
+        if p < self.valves_open.len() {
+            valves_open.extend_from_slice(&self.valves_open[..p]);
+            valves_open.push(valve.id);
+            valves_open.extend_from_slice(&self.valves_open[p..])
+        } else {
+            valves_open.extend_from_slice(&self.valves_open);
+            valves_open.push(valve.id);
+        }
+
+        let points = valve.flow_rate * if human { time_left } else { elephant_time_left };
+
+        Some((
+            State {
+                pos,
+                elephant_pos,
+                time_left,
+                elephant_time_left,
+                valves_open,
+            },
+            points,
+        ))
+    }
+}
+
+/// Parse all valves, indexed by their ID.
+pub fn parse_input(input: &str) -> Result<HashMap<&str, Valve<'_>>, Error> {
+    parse_lines(input, |line| {
+        parse_valve(line).map(|valve| (valve.id, valve))
+    })
+}
+
+/// The most pressure one can release alone in 30 minutes.
+pub fn find_max_release(valves: &HashMap<&str, Valve>) -> Option<u32> {
+    find_max_release_for_initial_state(
+        valves,
+        State {
+            pos: "AA",
+            elephant_pos: "AA",
+            time_left: 30,
+            elephant_time_left: 0,
+            valves_open: vec![],
+        },
+    )
+}
+
+/// Walking through the tunnels between the valves.
+struct Tunnels<'a, 's>(&'a HashMap<&'s str, Valve<'s>>);
+
+impl<'s> Graph for Tunnels<'_, 's> {
+    type Node = &'s str;
+    type Key = &'s str;
+
+    fn key(&self, id: &&'s str) -> &'s str {
+        id
+    }
+    fn successors(&self, id: &&'s str) -> impl Iterator<Item = (&'s str, u64)> {
+        self.0
+            .get(id)
+            .into_iter()
+            .flat_map(|valve| valve.neighbours.iter())
+            .map(|(n_id, dist)| (*n_id, u64::from(*dist)))
+    }
+}
+
+/// Shortest distances between all pairs of valves.
+pub fn distances_between_valves<'s>(
+    valves: &HashMap<&'s str, Valve<'s>>,
+) -> HashMap<(&'s str, &'s str), u32> {
+    let tunnels = Tunnels(valves);
+    valves
+        .keys()
+        .flat_map(|from| {
+            distances(&tunnels, from)
+                .into_iter()
+                .map(move |(to, dist)| ((*from, to), dist as u32))
+        })
+        .collect()
+}
+
+/// The most pressure one can release in 26 minutes with the help of an elephant.
+pub fn find_max_release_with_support(valves: &HashMap<&str, Valve>) -> Option<u32> {
+    find_max_release_for_initial_state(
+        valves,
+        State {
+            pos: "AA",
+            elephant_pos: "AA",
+            time_left: 26,
+            elephant_time_left: 26,
+            valves_open: vec![],
+        },
+    )
+}
+
+/// Opening valves one after another, alone or with the elephant. Nodes are the state and the
+/// pressure released by the valves opened so far until the time runs out.
+struct Release<'a, 's> {
+    valves: &'a HashMap<&'s str, Valve<'s>>,
+    valve_dists: HashMap<(&'s str, &'s str), u32>,
+}
+
+impl<'s> Optimisation for Release<'_, 's> {
+    type Node = (State<'s>, u32);
+    type Key = ((&'s str, u32), (&'s str, u32), Vec<&'s str>);
+
+    fn key(&self, (state, _): &Self::Node) -> Self::Key {
+        // this symmetry was a last minute addition: it does not matter who of us is where, so
+        // both are sorted to find more states that are actually the same
+        let human = (state.pos, state.time_left);
+        let elephant = (state.elephant_pos, state.elephant_time_left);
+        (
+            human.min(elephant),
+            human.max(elephant),
+            state.valves_open.clone(),
+        )
+    }
+
+    fn successors(
+        &self,
+        (current, current_points): &Self::Node,
+    ) -> impl Iterator<Item = Self::Node> {
+        let mut next: Vec<Self::Node> = Vec::with_capacity(self.valves.len());
+        if current.time_left >= current.elephant_time_left {
+            for valve in self
+                .valves
+                .values()
+                .filter(|v| v.flow_rate > 0 && !current.valves_open.contains(&v.id))
+            {
+                if let Some(dist) = self.valve_dists.get(&(current.pos, valve.id)) {
+                    if let Some((opened, points)) =
+                        current.with_move_and_open_valve(valve, *dist, true)
+                    {
+                        next.push((opened, current_points + points));
+                    }
+                }
+            }
+        }
+        if next.is_empty() {
+            for valve in self.valves.values().filter(|v| {
+                v.flow_rate > 0
+                    && v.id != current.elephant_pos
+                    && !current.valves_open.contains(&v.id)
+            }) {
+                if let Some(dist) = self.valve_dists.get(&(current.elephant_pos, valve.id)) {
+                    if let Some((opened, points)) =
+                        current.with_move_and_open_valve(valve, *dist, false)
+                    {
+                        next.push((opened, current_points + points));
+                    }
+                }
+            }
+        }
+        next.into_iter()
+    }
+
+    fn value(&self, (_, points): &Self::Node) -> u64 {
+        u64::from(*points)
+    }
+
+    fn bound(&self, (state, points): &Self::Node) -> u64 {
+        // as if all closed valves could be opened right away
+        let time_left = state
+            .time_left
+            .max(state.elephant_time_left)
+            .saturating_sub(1);
+        let closed_flow: u32 = self
+            .valves
+            .values()
+            .filter(|v| !state.valves_open.contains(&v.id))
+            .map(|v| v.flow_rate)
+            .sum();
+        u64::from(points + closed_flow * time_left)
+    }
+}
+
+fn find_max_release_for_initial_state(valves: &HashMap<&str, Valve>, state: State) -> Option<u32> {
+    let release = Release {
+        valves,
+        valve_dists: distances_between_valves(valves),
+    };
+    let best = branch_and_bound(&release, (state, 0));
+    Some(best.value as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II
+"#;
+
+    #[test]
+    fn find_max_release_works_for_example() {
+        // given
+        let valves = parse_input(EXAMPLE).expect("expected successful parsing");
+        let simplified_valves =
+            simplify_broken_valves(valves).expect("expected successful simplification");
+
+        // when
+        let result = find_max_release(&simplified_valves);
+
+        // then
+        assert_eq!(result, Some(1651));
+    }
+
+    #[test]
+    fn find_max_release_with_support_works_for_example() {
+        // given
+        let valves = parse_input(EXAMPLE).expect("expected successful parsing");
+        let simplified_valves =
+            simplify_broken_valves(valves).expect("expected successful simplification");
+
+        // when
+        let result = find_max_release_with_support(&simplified_valves);
+
+        // then
+        assert_eq!(result, Some(1707));
+    }
+
+    #[test]
+    fn distances_between_valves_works_for_small_graph() {
+        // given
+        let valves: HashMap<&str, Valve> = [
+            (
+                "A",
+                Valve {
+                    id: "A",
+                    flow_rate: 1,
+                    neighbours: vec![("B", 2)],
+                },
+            ),
+            (
+                "B",
+                Valve {
+                    id: "B",
+                    flow_rate: 1,
+                    neighbours: vec![("A", 2), ("C", 3)],
+                },
+            ),
+            (
+                "C",
+                Valve {
+                    id: "C",
+                    flow_rate: 1,
+                    neighbours: vec![("B", 3), ("D", 5), ("E", 50)],
+                },
+            ),
+            (
+                "D",
+                Valve {
+                    id: "D",
+                    flow_rate: 1,
+                    neighbours: vec![("C", 5), ("E", 1)],
+                },
+            ),
+            (
+                "E",
+                Valve {
+                    id: "E",
+                    flow_rate: 1,
+                    neighbours: vec![("C", 50), ("D", 1)],
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        // when
+        let pairs = distances_between_valves(&valves);
+
+        // then
+        let expected_pairs: HashMap<(&str, &str), u32> = [
+            (("A", "A"), 0),
+            (("A", "B"), 2),
+            (("A", "C"), 5),
+            (("A", "D"), 10),
+            (("A", "E"), 11),
+            (("B", "A"), 2),
+            (("B", "B"), 0),
+            (("B", "C"), 3),
+            (("B", "D"), 8),
+            (("B", "E"), 9),
+            (("C", "A"), 5),
+            (("C", "B"), 3),
+            (("C", "C"), 0),
+            (("C", "D"), 5),
+            (("C", "E"), 6),
+            (("D", "A"), 10),
+            (("D", "B"), 8),
+            (("D", "C"), 5),
+            (("D", "D"), 0),
+            (("D", "E"), 1),
+            (("E", "A"), 11),
+            (("E", "B"), 9),
+            (("E", "C"), 6),
+            (("E", "D"), 1),
+            (("E", "E"), 0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(pairs.len(), expected_pairs.len());
+        assert_eq!(pairs, expected_pairs);
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_valve(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,12 @@
+use aoc_common::GenerateOptions;
+
+/// A jet pattern. The size is its length, the harder the puzzle, the more evenly the jets push
+/// to both sides, which keeps the rocks from piling up at one wall.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let left = options.pick(0.6, 0.55, 0.5);
+    let pattern: String = (0..options.size_or(10091).max(1))
+        .map(|_| if rng.chance(left) { '<' } else { '>' })
+        .collect();
+    pattern + "\n"
+}
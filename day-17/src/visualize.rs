@@ -0,0 +1,104 @@
+use crate::{drop_rock, free_top_layers, CAVE_WIDTH, SHAPES};
+use aoc_grid::Dir4;
+use aoc_tui::Simulation;
+
+/// Rocks falling into the chamber one at a time, until the given number of rocks came to rest.
+#[derive(Clone, Debug)]
+pub struct RockSimulation<'a> {
+    jet_pattern: &'a [Dir4],
+    max_rocks: usize,
+    rocks: usize,
+    stack: Vec<u8>,
+    pattern_index: usize,
+}
+
+impl RockSimulation<'_> {
+    pub fn new(jet_pattern: &[Dir4], max_rocks: usize) -> RockSimulation<'_> {
+        RockSimulation {
+            jet_pattern,
+            max_rocks,
+            rocks: 0,
+            stack: Vec::with_capacity(max_rocks * 4),
+            pattern_index: 0,
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.stack.len() - free_top_layers(&self.stack)
+    }
+}
+
+impl Simulation for RockSimulation<'_> {
+    fn step(&mut self) -> bool {
+        if self.rocks == self.max_rocks || self.jet_pattern.is_empty() {
+            return false;
+        }
+        let (shape, width) = SHAPES[self.rocks % SHAPES.len()];
+        let stack = std::mem::take(&mut self.stack);
+        (self.stack, self.pattern_index) =
+            drop_rock(stack, self.pattern_index, self.jet_pattern, shape, width);
+        self.rocks += 1;
+        true
+    }
+
+    /// The chamber from the top of the tower down to the floor, rocks are drawn as `#`.
+    fn render(&self) -> String {
+        let mut s = String::with_capacity((self.height() + 1) * (CAVE_WIDTH as usize + 3));
+        for row in self.stack[..self.height()].iter().rev() {
+            s.push('|');
+            for x in 0..CAVE_WIDTH {
+                s.push(if row & (0b10000000 >> x) != 0 {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            s += "|\n";
+        }
+        s.push('+');
+        s += &"-".repeat(CAVE_WIDTH as usize);
+        s += "+\n";
+        s
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "rock {} of {}, the tower is {} units high, next jet {} of {}",
+            self.rocks,
+            self.max_rocks,
+            self.height(),
+            self.pattern_index + 1,
+            self.jet_pattern.len()
+        )
+    }
+
+    fn colour(&self, c: char) -> Option<(u8, u8, u8)> {
+        match c {
+            '#' => Some((200, 110, 60)),
+            '|' | '+' | '-' => Some((120, 120, 140)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_jet_pattern;
+
+    #[test]
+    fn rock_simulation_stacks_rocks() {
+        // given
+        let pattern = parse_jet_pattern(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+        let mut simulation = RockSimulation::new(&pattern, 2);
+
+        // when
+        while simulation.step() {}
+
+        // then
+        assert_eq!(
+            simulation.render(),
+            "|...#...|\n|..###..|\n|...#...|\n|..####.|\n+-------+\n"
+        );
+    }
+}
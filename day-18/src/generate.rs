@@ -0,0 +1,48 @@
+use aoc_common::GenerateOptions;
+use aoc_grid::Point3;
+
+/// A roughly round lava droplet. The size is its diameter (at most 120, the coordinates have to
+/// fit into an i8), the harder the puzzle, the more air pockets are trapped inside.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let size = options.size_or(20).clamp(1, 120) as i64;
+    let pockets = options.pick(0, size / 2, size * 2);
+    let center = Point3::new(size / 2, size / 2, size / 2);
+    let radius = size / 2;
+
+    let mut cubes: Vec<Point3> = Vec::new();
+    for z in 0..=size {
+        for y in 0..=size {
+            for x in 0..=size {
+                let p = Point3::new(x, y, z);
+                // the surface is a bit bumpy, to make the outside more interesting
+                if distance(p, center) <= radius as f64 + rng.between(-1, 1) as f64 {
+                    cubes.push(p);
+                }
+            }
+        }
+    }
+    for _ in 0..pockets {
+        let pocket = Point3::new(
+            rng.between(0, size),
+            rng.between(0, size),
+            rng.between(0, size),
+        );
+        let pocket_radius = rng.between(0, 2) as f64;
+        // only pockets well inside of the droplet stay closed
+        if distance(pocket, center) + pocket_radius < radius as f64 - 2.0 {
+            cubes.retain(|p| distance(*p, pocket) > pocket_radius);
+        }
+    }
+    rng.shuffle(&mut cubes);
+
+    cubes
+        .iter()
+        .map(|p| format!("{},{},{}\n", p.x, p.y, p.z))
+        .collect()
+}
+
+fn distance(a: Point3, b: Point3) -> f64 {
+    let d = a - b;
+    ((d.x * d.x + d.y * d.y + d.z * d.z) as f64).sqrt()
+}
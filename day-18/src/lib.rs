@@ -0,0 +1,171 @@
+//! Day 18: Boiling Boulders
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+use aoc_grid::{Bounds, Grid3, Point3};
+use aoc_search::{reachable, Graph};
+use std::collections::HashSet;
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 18 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 18;
+    type Input<'a> = HashSet<Point3>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1(cubes: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(surface_area(cubes).into())
+    }
+    fn part2(cubes: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(reachable_surface_area(cubes).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+pub fn parse_line(line: &str) -> Result<Point3, Error> {
+    let mut ords = line.split(',');
+    let mut next_ord = |name: &str| {
+        let ord = ords.next().ok_or_else(|| {
+            Error::syntax(
+                line,
+                &line[line.len()..],
+                format!("expected {name} coordinate"),
+            )
+        })?;
+        // the droplet is small, which keeps the flooded box in part two small as well
+        parse_number::<i8>(line, ord).map(i64::from)
+    };
+    let x = next_ord("x")?;
+    let y = next_ord("y")?;
+    let z = next_ord("z")?;
+
+    Ok(Point3::new(x, y, z))
+}
+
+pub fn parse_input(input: &str) -> Result<HashSet<Point3>, Error> {
+    parse_lines(input, parse_line)
+}
+
+/// Number of cube sides that are not connected to another cube.
+pub fn surface_area(cubes: &HashSet<Point3>) -> u32 {
+    // let's use a primitive approach, who knows what will come later
+    cubes
+        .iter()
+        .map(|cube| {
+            cube.neighbours6()
+                .iter()
+                .filter(|neighbour| !cubes.contains(neighbour))
+                .count() as u32
+        })
+        .sum::<u32>()
+}
+
+/// Water flowing around the lava droplet.
+struct Water {
+    lava: Grid3<bool>,
+}
+
+impl Graph for Water {
+    type Node = Point3;
+    type Key = Point3;
+
+    fn key(&self, p: &Point3) -> Point3 {
+        *p
+    }
+    fn successors(&self, p: &Point3) -> impl Iterator<Item = (Point3, u64)> {
+        self.lava
+            .neighbours6(*p)
+            .filter(|n| !self.lava[*n])
+            .map(|n| (n, 1))
+    }
+}
+
+/// Number of cube sides that can be reached from outside the lava droplet.
+pub fn reachable_surface_area(cubes: &HashSet<Point3>) -> u32 {
+    // also a primitive approach, but it should be doable: flood the box around the droplet, with
+    // one layer of space around it so the water can flow everywhere
+    let Some(bounds) = Bounds::<Point3>::of(cubes.iter().copied()) else {
+        return 0;
+    };
+    let mut lava = Grid3::new(bounds.grow(1), false);
+    for cube in cubes {
+        lava[*cube] = true;
+    }
+    let water = Water { lava };
+    reachable(&water, water.lava.bounds().min)
+        .into_iter()
+        .map(|p| water.lava.neighbours6(p).filter(|n| water.lava[*n]).count() as u32)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+"#;
+
+    #[test]
+    fn surface_area_works_for_example() {
+        // given
+        let cubes = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let a = surface_area(&cubes);
+
+        // then
+        assert_eq!(a, 64);
+    }
+
+    #[test]
+    fn reachable_surface_area_works_for_example() {
+        // given
+        let cubes = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let a = reachable_surface_area(&cubes);
+
+        // then
+        assert_eq!(a, 58);
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_line(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
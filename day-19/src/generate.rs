@@ -0,0 +1,21 @@
+use aoc_common::GenerateOptions;
+
+/// Blueprints for the robot factory. The size is the number of blueprints, the harder the
+/// puzzle, the more the costs vary.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let (max_ore, max_clay, max_obsidian) = options.pick((3, 10, 12), (4, 20, 20), (5, 24, 24));
+    (1..=options.size_or(30))
+        .map(|id| {
+            format!(
+                "Blueprint {id}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+                rng.between(2, max_ore),
+                rng.between(2, max_ore),
+                rng.between(2, max_ore),
+                rng.between(4, max_clay),
+                rng.between(2, max_ore),
+                rng.between(7, max_obsidian),
+            )
+        })
+        .collect()
+}
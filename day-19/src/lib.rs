@@ -0,0 +1,390 @@
+//! Day 19: Not Enough Minerals
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+use aoc_search::{branch_and_bound, Optimisation};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 19 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 19;
+    type Input<'a> = Vec<Blueprint>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_blueprints(input)
+    }
+    fn part1(blueprints: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(blueprints.iter().map(quality_level).sum::<u32>().into())
+    }
+    fn part2(blueprints: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(blueprints
+            .iter()
+            .take(3)
+            .map(|bp| opened_geodes(bp, 32))
+            .product::<u32>()
+            .into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Resources {
+    pub ore: u32,
+    pub clay: u32,
+    pub obsidian: u32,
+}
+
+/// The costs of each kind of robot.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Blueprint {
+    pub id: u32,
+    pub ore_bot: Resources,
+    pub clay_bot: Resources,
+    pub obsi_bot: Resources,
+    pub geode_bot: Resources,
+}
+
+pub fn parse_blueprint(line: &str) -> Result<Blueprint, Error> {
+    // a regex would be really convenient here. But I don't want to use external dependencies.
+    let (id_part, cost_part) = line
+        .split_once(": Each ore robot costs ")
+        .ok_or_else(|| Error::syntax(line, line, "Unable to split cost part from ID part"))?;
+    let id_part = id_part
+        .strip_prefix("Blueprint ")
+        .ok_or_else(|| Error::syntax(line, id_part, "Unexpected prefix in id part"))?;
+    let id: u32 = parse_number(line, id_part)?;
+
+    let (ore_bot_cost, rest) = cost_part
+        .split_once(" ore. Each clay robot costs ")
+        .ok_or_else(|| Error::syntax(line, cost_part, "Unable to split ore bot cost from rest"))?;
+    let ore_bot = Resources {
+        ore: parse_number(line, ore_bot_cost)?,
+        clay: 0,
+        obsidian: 0,
+    };
+
+    let (clay_bot_cost, rest) = rest
+        .split_once(" ore. Each obsidian robot costs ")
+        .ok_or_else(|| Error::syntax(line, rest, "Unable to split clay bot cost from rest"))?;
+    let clay_bot = Resources {
+        ore: parse_number(line, clay_bot_cost)?,
+        clay: 0,
+        obsidian: 0,
+    };
+
+    let (obsi_bot_cost, geode_bot_cost) = rest
+        .split_once(" clay. Each geode robot costs ")
+        .ok_or_else(|| {
+            Error::syntax(
+                line,
+                rest,
+                "Unable to split obsidian bot cost from geode bot cost",
+            )
+        })?;
+    let (obsi_bot_ore, obsi_bot_clay) = obsi_bot_cost.split_once(" ore and ").ok_or_else(|| {
+        Error::syntax(
+            line,
+            obsi_bot_cost,
+            "Unable to split obsidian bot ore cost from obsidian bot clay cost",
+        )
+    })?;
+    let obsi_bot = Resources {
+        ore: parse_number(line, obsi_bot_ore)?,
+        clay: parse_number(line, obsi_bot_clay)?,
+        obsidian: 0,
+    };
+
+    let geode_bot_cost = geode_bot_cost.strip_suffix(" obsidian.").ok_or_else(|| {
+        Error::syntax(line, geode_bot_cost, "Unexpected suffix for geode bot cost")
+    })?;
+    let (geode_bot_ore, geode_bot_obsi) =
+        geode_bot_cost.split_once(" ore and ").ok_or_else(|| {
+            Error::syntax(
+                line,
+                geode_bot_cost,
+                "Unable to split geode bot ore cost from geode bot obsidian cost",
+            )
+        })?;
+    let geode_bot = Resources {
+        ore: parse_number(line, geode_bot_ore)?,
+        clay: 0,
+        obsidian: parse_number(line, geode_bot_obsi)?,
+    };
+
+    Ok(Blueprint {
+        id,
+        ore_bot,
+        clay_bot,
+        obsi_bot,
+        geode_bot,
+    })
+}
+
+pub fn parse_blueprints(input: &str) -> Result<Vec<Blueprint>, Error> {
+    parse_lines(input, parse_blueprint)
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+struct State {
+    res: Resources,
+    ore_bots: u32,
+    clay_bots: u32,
+    obsi_bots: u32,
+    geode_bots: u32,
+}
+
+/// Number of geodes openable in 24 minutes, multiplied by the blueprint ID.
+pub fn quality_level(blueprint: &Blueprint) -> u32 {
+    opened_geodes(blueprint, 24) * blueprint.id
+}
+
+/// A state with the geodes opened so far and the time left.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+struct Plan {
+    state: State,
+    geodes: u32,
+    time_left: u32,
+}
+
+/// Deciding which robot to build next, skipping the minutes in which we just wait for resources.
+struct Factory<'a> {
+    blueprint: &'a Blueprint,
+    max_ore_cost: u32,
+}
+
+impl Factory<'_> {
+    // the plan after waiting for and building a robot, if there is enough time to make use of it
+    fn build(
+        &self,
+        current: &Plan,
+        cost: Resources,
+        time_left: u32,
+        add_bot: impl Fn(&mut State),
+    ) -> Option<Plan> {
+        let Plan { state, geodes, .. } = current;
+        let time = time_to_build(state, cost, time_left)?;
+        let mut next = State {
+            res: Resources {
+                ore: state.res.ore + time * state.ore_bots - cost.ore,
+                clay: state.res.clay + time * state.clay_bots - cost.clay,
+                obsidian: state.res.obsidian + time * state.obsi_bots - cost.obsidian,
+            },
+            ..state.clone()
+        };
+        add_bot(&mut next);
+        Some(Plan {
+            state: next,
+            geodes: geodes + time * state.geode_bots,
+            time_left: current.time_left - time,
+        })
+    }
+}
+
+impl Optimisation for Factory<'_> {
+    type Node = Plan;
+    type Key = (State, u32);
+
+    fn key(&self, plan: &Plan) -> (State, u32) {
+        (plan.state.clone(), plan.time_left)
+    }
+
+    fn successors(&self, plan: &Plan) -> impl Iterator<Item = Plan> {
+        let blueprint = self.blueprint;
+        let time_left = plan.time_left;
+        let mut next: Vec<Plan> = Vec::with_capacity(4);
+        if time_left > 1 {
+            // it does not make sense to build more ore bots than the amount of ore we can spent
+            // in the same time
+            if plan.state.ore_bots < self.max_ore_cost {
+                next.extend(
+                    self.build(plan, blueprint.ore_bot, time_left - 2, |s| s.ore_bots += 1),
+                );
+            }
+            next.extend(self.build(plan, blueprint.clay_bot, time_left - 2, |s| {
+                s.clay_bots += 1
+            }));
+            next.extend(self.build(plan, blueprint.obsi_bot, time_left - 2, |s| {
+                s.obsi_bots += 1
+            }));
+            next.extend(self.build(plan, blueprint.geode_bot, time_left, |s| s.geode_bots += 1));
+        }
+        next.into_iter()
+    }
+
+    fn value(&self, plan: &Plan) -> u64 {
+        u64::from(plan.geodes + plan.time_left * plan.state.geode_bots)
+    }
+
+    fn bound(&self, plan: &Plan) -> u64 {
+        // as if we could build another geode bot every minute from now on
+        let t = u64::from(plan.time_left);
+        self.value(plan) + t * t.saturating_sub(1) / 2
+    }
+}
+
+/// The most geodes that can be opened with this blueprint in the given time.
+pub fn opened_geodes(blueprint: &Blueprint, max_time: u32) -> u32 {
+    let max_ore_cost = blueprint
+        .ore_bot
+        .ore
+        .max(blueprint.clay_bot.ore)
+        .max(blueprint.obsi_bot.ore)
+        .max(blueprint.geode_bot.ore);
+    let factory = Factory {
+        blueprint,
+        max_ore_cost,
+    };
+
+    let start = Plan {
+        state: State {
+            res: Resources {
+                ore: 0,
+                clay: 0,
+                obsidian: 0,
+            },
+            ore_bots: 1,
+            clay_bots: 0,
+            obsi_bots: 0,
+            geode_bots: 0,
+        },
+        geodes: 0,
+        time_left: max_time,
+    };
+
+    branch_and_bound(&factory, start).value as u32
+}
+
+fn time_to_build(state: &State, cost: Resources, time_left: u32) -> Option<u32> {
+    let ore_time = if state.res.ore >= cost.ore {
+        0
+    } else {
+        let to_produce = cost.ore - state.res.ore;
+        to_produce
+            .checked_div(state.ore_bots)
+            .map(|cost| cost + u32::from(to_produce.checked_rem(state.ore_bots) != Some(0)))?
+    };
+
+    let clay_time = if state.res.clay >= cost.clay {
+        0
+    } else {
+        let to_produce = cost.clay - state.res.clay;
+        to_produce
+            .checked_div(state.clay_bots)
+            .map(|cost| cost + u32::from(to_produce.checked_rem(state.clay_bots) != Some(0)))?
+    };
+
+    let obsi_time = if state.res.obsidian >= cost.obsidian {
+        0
+    } else {
+        let to_produce = cost.obsidian - state.res.obsidian;
+        to_produce
+            .checked_div(state.obsi_bots)
+            .map(|cost| cost + u32::from(to_produce.checked_rem(state.obsi_bots) != Some(0)))?
+    };
+
+    let time = ore_time.max(clay_time).max(obsi_time) + 1;
+
+    // if no time is left after building it, it does not help
+    if time < time_left {
+        Some(time)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    #[test]
+    fn quality_level_works_for_first_example() {
+        // given
+        let blueprint = Blueprint {
+            id: 1,
+            ore_bot: Resources {
+                ore: 4,
+                clay: 0,
+                obsidian: 0,
+            },
+            clay_bot: Resources {
+                ore: 2,
+                clay: 0,
+                obsidian: 0,
+            },
+            obsi_bot: Resources {
+                ore: 3,
+                clay: 14,
+                obsidian: 0,
+            },
+            geode_bot: Resources {
+                ore: 2,
+                clay: 0,
+                obsidian: 7,
+            },
+        };
+
+        // when
+        let ql = quality_level(&blueprint);
+
+        assert_eq!(ql, 9);
+    }
+
+    #[test]
+    fn quality_level_works_for_second_example() {
+        // given
+        let blueprint = Blueprint {
+            id: 1,
+            ore_bot: Resources {
+                ore: 2,
+                clay: 0,
+                obsidian: 0,
+            },
+            clay_bot: Resources {
+                ore: 3,
+                clay: 0,
+                obsidian: 0,
+            },
+            obsi_bot: Resources {
+                ore: 3,
+                clay: 8,
+                obsidian: 0,
+            },
+            geode_bot: Resources {
+                ore: 3,
+                clay: 0,
+                obsidian: 12,
+            },
+        };
+
+        // when
+        let ql = quality_level(&blueprint);
+
+        assert_eq!(ql, 12);
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[&generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_blueprints(input);
+            let _ = parse_blueprint(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,19 @@
+use aoc_common::GenerateOptions;
+
+/// An encrypted file with exactly one 0, which the grove coordinates are relative to. The size is
+/// the number of values, the harder the puzzle, the further each value moves.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let max = options.pick(100, 10_000, 1_000_000);
+    let n = options.size_or(5000).max(2);
+    let zero = rng.below(n);
+    (0..n)
+        .map(|i| {
+            let mut v = 0;
+            while v == 0 && i != zero {
+                v = rng.between(-max, max);
+            }
+            format!("{v}\n")
+        })
+        .collect()
+}
@@ -0,0 +1,199 @@
+//! Day 20: Grove Positioning System
+
+use aoc_common::{parse_lines, parse_number, Answer, Error, GenerateOptions, Solution};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 20 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 20;
+    type Input<'a> = Vec<isize>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1(ciphertext: &Self::Input<'_>) -> Result<Answer, Error> {
+        grove_coordinate_sum(&mix(ciphertext, 1))
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("Unable to find grove coordinate sum in the plaintext."))
+    }
+    fn part2(ciphertext: &Self::Input<'_>) -> Result<Answer, Error> {
+        let ciphertext_with_key: Vec<isize> =
+            ciphertext.iter().map(|v| *v * DECRYPTION_KEY).collect();
+        grove_coordinate_sum(&mix(&ciphertext_with_key, 10))
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("Unable to find grove coordinate sum in the plaintext."))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+pub const DECRYPTION_KEY: isize = 811589153;
+
+pub fn parse_input(input: &str) -> Result<Vec<isize>, Error> {
+    parse_lines(input, |line| parse_number(line, line))
+}
+
+/// Sum of the numbers 1000, 2000 and 3000 positions after the 0.
+pub fn grove_coordinate_sum(plaintext: &[isize]) -> Option<isize> {
+    let zero = plaintext
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v == 0)
+        .map(|(i, _)| i)
+        .next()?;
+    Some(
+        plaintext[(zero + 1000) % plaintext.len()]
+            + plaintext[(zero + 2000) % plaintext.len()]
+            + plaintext[(zero + 3000) % plaintext.len()],
+    )
+}
+
+/// Mix the ciphertext the given number of times.
+pub fn mix(ciphertext: &[isize], iterations: usize) -> Vec<isize> {
+    let mut positions: Vec<usize> = (0..ciphertext.len()).collect();
+    let mut plaintext: Vec<(isize, usize)> = ciphertext
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (*v, i))
+        .collect();
+
+    for _ in 0..iterations {
+        for pos_i in 0..positions.len() {
+            let i = positions[pos_i];
+            let (value, _) = plaintext[i];
+            if value == 0 {
+                continue;
+            }
+
+            let target =
+                (i as isize + value - 1).rem_euclid(ciphertext.len() as isize - 1) as usize + 1;
+
+            if target > i {
+                for j in i..target {
+                    let (_, p1) = plaintext[j];
+                    let (_, p2) = plaintext[j + 1];
+                    positions[p1] = j + 1;
+                    positions[p2] = j;
+                    plaintext.swap(j, j + 1);
+                }
+            } else {
+                for j in 0..(i - target) {
+                    let (_, p1) = plaintext[i - j];
+                    let (_, p2) = plaintext[i - j - 1];
+                    positions[p1] = i - j - 1;
+                    positions[p2] = i - j;
+                    plaintext.swap(i - j, i - j - 1);
+                }
+            }
+        }
+    }
+    plaintext.iter().map(|(v, _)| v).copied().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    #[test]
+    fn mix_works_for_example() {
+        // given
+        let ciphertext: &[isize] = &[1, 2, -3, 3, -2, 0, 4];
+
+        // when
+        let plaintext = mix(ciphertext, 1);
+
+        // then
+        assert_eq!(&plaintext, &[1, 2, -3, 4, 0, 3, -2]);
+    }
+
+    #[test]
+    fn mix_works_for_simplified_double_overflowing_example() {
+        // given
+        let ciphertext: &[isize] = &[0, 0, 7, 0, 0];
+
+        // when
+        let plaintext = mix(ciphertext, 1);
+
+        // then
+        assert_eq!(&plaintext, &[0, 7, 0, 0, 0]);
+    }
+
+    #[test]
+    fn mix_works_for_simplified_negative_double_overflowing_example() {
+        // given
+        let ciphertext: &[isize] = &[0, 0, -7, 0, 0];
+
+        // when
+        let plaintext = mix(ciphertext, 1);
+
+        // then
+        assert_eq!(&plaintext, &[0, 0, 0, -7, 0]);
+    }
+
+    #[test]
+    fn mix_works_for_part2_example() {
+        // given
+        let ciphertext: &[isize] = &[
+            811589153,
+            1623178306,
+            -2434767459,
+            2434767459,
+            -1623178306,
+            0,
+            3246356612,
+        ];
+
+        // when
+        let plaintext = mix(ciphertext, 10);
+
+        // then
+        assert_eq!(
+            &plaintext,
+            &[
+                0,
+                -2434767459,
+                1623178306,
+                3246356612,
+                -1623178306,
+                2434767459,
+                811589153
+            ]
+        );
+    }
+
+    #[test]
+    fn grove_coordinate_sum_works_for_example() {
+        // given
+        let plaintext: &[isize] = &[1, 2, -3, 4, 0, 3, -2];
+
+        // when
+        let csum = grove_coordinate_sum(plaintext);
+
+        // then
+        assert_eq!(csum, Some(3));
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[&generated], |input| {
+            let _ = Solver::parse(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
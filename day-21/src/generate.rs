@@ -0,0 +1,157 @@
+use aoc_common::{GenerateOptions, Rng};
+use std::collections::HashSet;
+
+// keeps the numbers far away from overflowing
+const MAX_VALUE: i64 = 1_000_000_000_000;
+
+struct Monkeys {
+    lines: Vec<String>,
+    names: HashSet<String>,
+}
+
+impl Monkeys {
+    fn name(&mut self, rng: &mut Rng) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn add(&mut self, name: &str, job: String) {
+        self.lines.push(format!("{name}: {job}"));
+    }
+
+    // a new monkey that ends up yelling `value`, with only small numbers at the leaves
+    fn yelling(&mut self, rng: &mut Rng, value: i64) -> String {
+        let name = self.name(rng);
+        if (1..=20).contains(&value) {
+            self.add(&name, value.to_string());
+        } else if value < 1 {
+            let small = rng.between(1, 20);
+            let job = format!(
+                "{} - {}",
+                self.yelling(rng, small),
+                self.yelling(rng, small - value)
+            );
+            self.add(&name, job);
+        } else {
+            let factor = rng.between(2, 20);
+            let (quotient, remainder) = (value / factor, value % factor);
+            let product = if remainder == 0 {
+                name.clone()
+            } else {
+                self.name(rng)
+            };
+            let job = format!(
+                "{} * {}",
+                self.yelling(rng, factor),
+                self.yelling(rng, quotient)
+            );
+            self.add(&product, job);
+            if remainder != 0 {
+                let job = format!("{product} + {}", self.yelling(rng, remainder));
+                self.add(&name, job);
+            }
+        }
+        name
+    }
+}
+
+/// Monkeys yelling numbers, with exactly one path from `root` to `humn`, and a number for `humn`
+/// that makes both sides of `root` equal without any division leaving a remainder. The size is
+/// the number of operations between `root` and `humn`, the harder the puzzle, the more of them
+/// are subtractions and divisions, which are harder to undo.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let hard_ops = options.pick(0.0, 0.3, 0.6);
+    let mut monkeys = Monkeys {
+        lines: Vec::new(),
+        names: HashSet::new(),
+    };
+
+    // build the path from humn up to root, keeping track of what each monkey on it yells, for the
+    // number humn has to yell and for the one it yells in the first part
+    let humn = [rng.between(1, 10_000), rng.between(1, 10_000)];
+    let mut values = humn;
+    let mut current = "humn".to_owned();
+    for _ in 0..options.size_or(70) {
+        let name = monkeys.name(&mut rng);
+        let hard = rng.chance(hard_ops);
+        let operand = rng.between(2, 20);
+        let other = rng.between(1, 1000);
+        let largest = values[0].abs().max(values[1].abs());
+        let job = if hard && values[0] % operand == 0 {
+            values = values.map(|v| v / operand);
+            format!("{current} / {}", monkeys.yelling(&mut rng, operand))
+        } else if hard && rng.chance(0.5) {
+            values = values.map(|v| v - other);
+            format!("{current} - {}", monkeys.yelling(&mut rng, other))
+        } else if hard {
+            values = values.map(|v| other - v);
+            format!("{} - {current}", monkeys.yelling(&mut rng, other))
+        } else if largest * operand < MAX_VALUE && rng.chance(0.5) {
+            values = values.map(|v| v * operand);
+            format!("{} * {current}", monkeys.yelling(&mut rng, operand))
+        } else {
+            values = values.map(|v| v + other);
+            format!("{current} + {}", monkeys.yelling(&mut rng, other))
+        };
+        monkeys.add(&name, job);
+        current = name;
+    }
+    let other = monkeys.yelling(&mut rng, values[0]);
+    let root_job = if rng.chance(0.5) {
+        format!("{current} + {other}")
+    } else {
+        format!("{other} + {current}")
+    };
+    monkeys.add("root", root_job);
+    monkeys.add("humn", humn[1].to_string());
+
+    rng.shuffle(&mut monkeys.lines);
+    monkeys.lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{find_root_value, human_value, parse_input, Action, Monkey};
+    use aoc_common::Difficulty;
+
+    #[test]
+    fn generated_monkeys_can_be_made_equal() {
+        // given
+        let options = GenerateOptions {
+            size: Some(30),
+            seed: 7,
+            difficulty: Difficulty::Hard,
+        };
+        let input = generate_input(&options);
+        let monkeys = parse_input(&input).expect("expected successful parsing");
+
+        // when
+        let value = human_value(&monkeys).expect("expected a number to yell");
+
+        // then
+        // with that number, both sides of root are equal, so their difference is 0
+        let fixed: Vec<Monkey> = monkeys
+            .iter()
+            .map(|monkey| match (monkey.name, monkey.action) {
+                ("humn", _) => Monkey {
+                    action: Action::Lit(value),
+                    ..*monkey
+                },
+                ("root", Action::Add(l, r)) => Monkey {
+                    action: Action::Sub(l, r),
+                    ..*monkey
+                },
+                _ => *monkey,
+            })
+            .collect();
+        assert_eq!(find_root_value(&fixed), Some(0));
+    }
+}
@@ -0,0 +1,268 @@
+//! Day 21: Monkey Math
+
+use aoc_common::{parse_lines, Answer, Error, GenerateOptions, Solution};
+use std::collections::HashMap;
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 21 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 21;
+    type Input<'a> = Vec<Monkey<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1(monkeys: &Self::Input<'_>) -> Result<Answer, Error> {
+        find_root_value(monkeys)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("I can't find a value for the root monkey!"))
+    }
+    fn part2(monkeys: &Self::Input<'_>) -> Result<Answer, Error> {
+        human_value(monkeys)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("I have no idea what number to yell."))
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Action<'s> {
+    Lit(i64),
+    Add(&'s str, &'s str),
+    Sub(&'s str, &'s str),
+    Mul(&'s str, &'s str),
+    Div(&'s str, &'s str),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Monkey<'s> {
+    pub name: &'s str,
+    pub action: Action<'s>,
+}
+
+pub fn parse_monkey(line: &str) -> Result<Monkey<'_>, Error> {
+    let (name, rest) = line
+        .split_once(": ")
+        .ok_or_else(|| Error::syntax(line, line, "unable to split monkey name from action"))?;
+
+    if let Ok(literal) = rest.parse::<i64>() {
+        return Ok(Monkey {
+            name,
+            action: Action::Lit(literal),
+        });
+    }
+
+    if let Some((lhs, rhs)) = rest.split_once(" + ") {
+        return Ok(Monkey {
+            name,
+            action: Action::Add(lhs, rhs),
+        });
+    }
+
+    if let Some((lhs, rhs)) = rest.split_once(" - ") {
+        return Ok(Monkey {
+            name,
+            action: Action::Sub(lhs, rhs),
+        });
+    }
+
+    if let Some((lhs, rhs)) = rest.split_once(" * ") {
+        return Ok(Monkey {
+            name,
+            action: Action::Mul(lhs, rhs),
+        });
+    }
+
+    if let Some((lhs, rhs)) = rest.split_once(" / ") {
+        return Ok(Monkey {
+            name,
+            action: Action::Div(lhs, rhs),
+        });
+    }
+    Err(Error::syntax(
+        line,
+        rest,
+        "unable to interpret monkey action",
+    ))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Monkey<'_>>, Error> {
+    parse_lines(input, parse_monkey)
+}
+
+fn action_value(action: Action, values: &HashMap<&str, i64>) -> Option<i64> {
+    Some(match action {
+        Action::Lit(v) => v,
+        Action::Add(l, r) => values.get(l)? + values.get(r)?,
+        Action::Sub(l, r) => values.get(l)? - values.get(r)?,
+        Action::Mul(l, r) => values.get(l)? * values.get(r)?,
+        Action::Div(l, r) => values.get(l)? / values.get(r)?,
+    })
+}
+
+fn reverse_action_value<'s>(
+    action: Action<'s>,
+    values: &HashMap<&str, i64>,
+    result: i64,
+) -> Option<(&'s str, i64)> {
+    match action {
+        Action::Lit(_) => None,
+        Action::Add(l, r) => match (values.get(l), values.get(r)) {
+            (Some(vl), None) => Some((r, result - vl)),
+            (None, Some(vr)) => Some((l, result - vr)),
+            _ => None,
+        },
+        Action::Sub(l, r) => match (values.get(l), values.get(r)) {
+            (Some(vl), None) => Some((r, vl - result)),
+            (None, Some(vr)) => Some((l, result + vr)),
+            _ => None,
+        },
+        Action::Mul(l, r) => match (values.get(l), values.get(r)) {
+            (Some(vl), None) => Some((r, result / vl)),
+            (None, Some(vr)) => Some((l, result / vr)),
+            _ => None,
+        },
+        Action::Div(l, r) => match (values.get(l), values.get(r)) {
+            (Some(vl), None) => Some((r, vl / result)),
+            (None, Some(vr)) => Some((l, result * vr)),
+            _ => None,
+        },
+    }
+}
+
+fn monkey_values<'s>(monkeys: &[Monkey<'s>]) -> HashMap<&'s str, i64> {
+    // let's try a simple (but quadratic) solution first
+    let mut values: HashMap<&str, i64> = HashMap::with_capacity(monkeys.len());
+
+    let mut found_anything = true;
+    while found_anything {
+        found_anything = false;
+        for monkey in monkeys {
+            if values.contains_key(monkey.name) {
+                continue;
+            }
+            if let Some(value) = action_value(monkey.action, &values) {
+                values.insert(monkey.name, value);
+                found_anything = true;
+            }
+        }
+    }
+    values
+}
+
+/// The number the monkey named `root` yells.
+pub fn find_root_value(monkeys: &[Monkey]) -> Option<i64> {
+    monkey_values(monkeys).get("root").copied()
+}
+
+/// The number we have to yell so both sides of the `root` monkey are equal.
+pub fn human_value(monkeys: &[Monkey]) -> Option<i64> {
+    let non_root_monkeys: Vec<Monkey> = monkeys
+        .iter()
+        .filter(|monkey| monkey.name != "humn" && monkey.name != "root")
+        .copied()
+        .collect();
+    let partial_values = monkey_values(&non_root_monkeys);
+    let monkeys_by_name: HashMap<&str, Monkey> = monkeys
+        .iter()
+        .map(|monkey| (monkey.name, *monkey))
+        .collect();
+
+    // Assumption for this solution: there is only on path from root to humn.
+    // For more general cases, this approach won't work
+    let root_monkey = monkeys_by_name.get("root")?;
+    let (lhs, rhs) = match root_monkey.action {
+        Action::Lit(_) => return None,
+        Action::Add(l, r) => (l, r),
+        Action::Sub(l, r) => (l, r),
+        Action::Mul(l, r) => (l, r),
+        Action::Div(l, r) => (l, r),
+    };
+    let (mut unknown, mut value) = match (partial_values.get(lhs), partial_values.get(rhs)) {
+        (Some(lv), None) => (rhs, *lv),
+        (None, Some(rv)) => (lhs, *rv),
+        _ => {
+            return None;
+        }
+    };
+    loop {
+        if unknown == "humn" {
+            return Some(value);
+        }
+        (unknown, value) =
+            reverse_action_value(monkeys_by_name.get(unknown)?.action, &partial_values, value)?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32
+"#;
+
+    #[test]
+    fn find_root_value_works_for_example() {
+        // given
+        let monkeys = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let result = find_root_value(&monkeys);
+
+        // then
+        assert_eq!(result, Some(152));
+    }
+
+    #[test]
+    fn human_value_works_for_example() {
+        // given
+        let monkeys = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let result = human_value(&monkeys);
+
+        // then
+        assert_eq!(result, Some(301));
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_monkey(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
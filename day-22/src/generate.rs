@@ -0,0 +1,112 @@
+use aoc_common::{GenerateOptions, Rng};
+
+// the 11 nets of a cube, every other net is one of these rotated or flipped
+const NETS: [&str; 11] = [
+    "#...\n####\n#...",
+    "#...\n####\n.#..",
+    "#...\n####\n..#.",
+    "#...\n####\n...#",
+    ".#..\n####\n.#..",
+    ".#..\n####\n..#.",
+    "##..\n.###\n.#..",
+    "##..\n.###\n..#.",
+    "##..\n.###\n...#",
+    "##..\n.##.\n..##",
+    "###..\n..###",
+];
+
+/// A map that folds into a cube, along with a path to follow on it. The net of the cube is one
+/// of all possible nets, rotated and flipped at random. The size is the edge length of the cube,
+/// the harder the puzzle, the more walls there are.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let edge = options.size_or(50).max(1);
+    let walls = options.pick(0.02, 0.1, 0.2);
+    let net = net_sides(NETS[rng.below(NETS.len())], rng.below(4), rng.chance(0.5));
+    let map = draw_net(&mut rng, &net, edge, walls);
+
+    let mut path = String::new();
+    for _ in 0..2000 {
+        path += &rng.between(1, 2 * edge as i64).to_string();
+        path.push(*rng.choose(&['L', 'R']));
+    }
+    path += &rng.between(1, 2 * edge as i64).to_string();
+    format!("{map}\n\n{path}\n")
+}
+
+// the positions of the sides of the net, rotated clockwise and flipped, sorted by row
+fn net_sides(net: &str, rotations: usize, flip: bool) -> Vec<(i64, i64)> {
+    let mut sides: Vec<(i64, i64)> = net
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.char_indices()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (x as i64, y as i64))
+        })
+        .collect();
+    for _ in 0..rotations {
+        sides = sides.into_iter().map(|(x, y)| (-y, x)).collect();
+    }
+    if flip {
+        sides = sides.into_iter().map(|(x, y)| (-x, y)).collect();
+    }
+    let min_x = sides.iter().map(|s| s.0).min().unwrap_or(0);
+    let min_y = sides.iter().map(|s| s.1).min().unwrap_or(0);
+    let mut sides: Vec<(i64, i64)> = sides
+        .into_iter()
+        .map(|(x, y)| (x - min_x, y - min_y))
+        .collect();
+    sides.sort_unstable_by_key(|(x, y)| (*y, *x));
+    sides
+}
+
+// draw the net with sides of the given edge length, with the start in the top left corner open
+fn draw_net(rng: &mut Rng, sides: &[(i64, i64)], edge: usize, walls: f64) -> String {
+    let rows = sides.iter().map(|s| s.1).max().unwrap_or(0) + 1;
+    let mut lines: Vec<String> = Vec::with_capacity(rows as usize * edge);
+    for row in 0..rows {
+        // the sides of a row of a net are always next to each other
+        let xs: Vec<i64> = sides.iter().filter(|s| s.1 == row).map(|s| s.0).collect();
+        let offset = xs[0] as usize * edge;
+        for _ in 0..edge {
+            let tiles: String = (0..xs.len() * edge)
+                .map(|_| if rng.chance(walls) { '#' } else { '.' })
+                .collect();
+            lines.push(" ".repeat(offset) + &tiles);
+        }
+    }
+    let start = lines[0].len() - lines[0].trim_start().len();
+    lines[0].replace_range(start..=start, ".");
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{map_to_cube, parse_map};
+
+    #[test]
+    fn all_nets_fold_into_a_cube() {
+        // given
+        let mut rng = Rng::new(22);
+
+        for net in NETS {
+            for rotations in 0..4 {
+                for flip in [false, true] {
+                    // when
+                    let sides = net_sides(net, rotations, flip);
+                    let map = draw_net(&mut rng, &sides, 4, 0.1);
+
+                    // then
+                    let map = parse_map(&map).expect("expected successful parsing");
+                    let cube = map_to_cube(&map);
+                    assert!(
+                        cube.is_ok(),
+                        "{net:?} rotated {rotations} times, flipped: {flip}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use aoc_common::GenerateOptions;
+
+/// A square patch of the grove with elves scattered over it. The size is the width of the patch,
+/// the harder the puzzle, the more crowded it is. There is always at least one elf.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let size = options.size_or(70).max(1);
+    let crowd = options.pick(0.2, 0.5, 0.7);
+    let elf = rng.below(size * size);
+    (0..size)
+        .map(|y| {
+            let row: String = (0..size)
+                .map(|x| {
+                    if y * size + x == elf || rng.chance(crowd) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            row + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::Difficulty;
+
+    #[test]
+    fn small_groves_have_an_elf() {
+        for seed in 0..20 {
+            // given
+            let options = GenerateOptions {
+                size: Some(1 + seed as usize % 3),
+                seed,
+                difficulty: Difficulty::Easy,
+            };
+
+            // when
+            let input = generate_input(&options);
+
+            // then
+            assert!(input.contains('#'), "seed {seed}: {input:?}");
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Day 23: Unstable Diffusion
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+use aoc_grid::{Dir8, Point, SparseGrid};
+use std::collections::HashMap;
+
+mod generate;
+mod visualize;
+
+pub use generate::generate_input;
+pub use visualize::ElfSimulation;
+
+/// Solves both parts of day 23 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 23;
+    type Input<'a> = Elves;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(parse_input(input))
+    }
+    fn part1(elves: &Self::Input<'_>) -> Result<Answer, Error> {
+        empty_squares(&run_rounds(elves.clone(), 10))
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("Elves? I didn't see any elves."))
+    }
+    fn part2(elves: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(run_until_stagnant(elves.clone()).into())
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// The positions of the elves.
+pub type Elves = SparseGrid<()>;
+
+/// Parse the positions of the elves.
+pub fn parse_input(input: &str) -> Elves {
+    SparseGrid::parse(input, |c| (c == '#').then_some(()))
+}
+
+// the directions the elves consider in the first round, with the tiles that have to be free
+const PROPOSALS: [(Dir8, [Dir8; 3]); 4] = [
+    (Dir8::N, [Dir8::NW, Dir8::N, Dir8::NE]),
+    (Dir8::S, [Dir8::SW, Dir8::S, Dir8::SE]),
+    (Dir8::W, [Dir8::NW, Dir8::W, Dir8::SW]),
+    (Dir8::E, [Dir8::NE, Dir8::E, Dir8::SE]),
+];
+
+/// Let the elves propose and make their moves. Returns `false` if no elf moved.
+pub fn do_round(elves: &mut Elves, round_index: usize) -> bool {
+    let mut proposals: HashMap<Point, Option<Point>> = HashMap::with_capacity(elves.len());
+    for elf in elves.points() {
+        let occupied = Dir8::ALL.map(|dir| elves.contains(elf + dir.offset()));
+        if !occupied.contains(&true) {
+            continue;
+        }
+        let proposal = (0..4)
+            .map(|i| PROPOSALS[(round_index + i) % 4])
+            .find(|(_, free)| free.iter().all(|dir| !occupied[*dir as usize]));
+        if let Some((dir, _)) = proposal {
+            proposals
+                .entry(elf + dir.offset())
+                .and_modify(|e| *e = None)
+                .or_insert(Some(elf));
+        }
+    }
+
+    let mut moved = false;
+    for (to, maybe_from) in proposals.iter() {
+        if let Some(from) = maybe_from {
+            elves.remove(*from);
+            elves.insert(*to, ());
+            moved = true;
+        }
+    }
+    moved
+}
+
+pub fn run_rounds(mut elves: Elves, rounds: usize) -> Elves {
+    for i in 0..rounds {
+        do_round(&mut elves, i);
+    }
+    elves
+}
+
+/// Number of the first round in which no elf moves.
+pub fn run_until_stagnant(mut elves: Elves) -> usize {
+    let mut round_counter = 0;
+    while do_round(&mut elves, round_counter) {
+        round_counter += 1;
+    }
+    round_counter + 1
+}
+
+/// Number of empty tiles in the smallest rectangle that contains all elves.
+pub fn empty_squares(elves: &Elves) -> Option<i64> {
+    Some(elves.bounds()?.area() - elves.len() as i64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"..............
+..............
+.......#......
+.....###.#....
+...#...#.#....
+....#...##....
+...#.###......
+...##.#.##....
+....#..#......
+..............
+..............
+..............
+"#;
+    const EXAMPLE_AFTER_10: &str = r#".......#......
+...........#..
+..#.#..#......
+......#.......
+...#.....#..#.
+.#......##....
+.....##.......
+..#........#..
+....#.#..#....
+..............
+....#..#..#...
+..............
+"#;
+
+    #[test]
+    fn run_rounds_works_for_example() {
+        // given
+        let elves = parse_input(EXAMPLE);
+        let expected_elves = parse_input(EXAMPLE_AFTER_10);
+
+        // when
+        let elves = run_rounds(elves, 10);
+
+        // then
+        assert_eq!(elves, expected_elves);
+    }
+
+    #[test]
+    fn do_round_works_for_small_example() {
+        // given
+        let mut elves = parse_input(
+            r#".....
+..##.
+..#..
+.....
+..##.
+.....
+"#,
+        );
+        let expected_elves = parse_input(
+            r#"..##.
+.....
+..#..
+...#.
+..#..
+.....
+"#,
+        );
+
+        // when
+        do_round(&mut elves, 0);
+
+        // then
+        assert_eq!(elves, expected_elves);
+    }
+
+    #[test]
+    fn run_until_stagnant_works_for_example() {
+        // given
+        let elves = parse_input(EXAMPLE);
+
+        // when
+        let rounds = run_until_stagnant(elves);
+
+        // then
+        assert_eq!(rounds, 20);
+    }
+
+    #[test]
+    fn empty_squares_works_for_example() {
+        // given
+        let elves = parse_input(EXAMPLE_AFTER_10);
+
+        // when
+        let result = empty_squares(&elves);
+
+        // then
+        assert_eq!(elves.len(), 22);
+        assert_eq!(result, Some(110));
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{do_round, empty_squares, Elves};
+use aoc_tui::Simulation;
+
+/// The elves spreading out, one round at a time, until no elf moves anymore.
+#[derive(Clone, Debug)]
+pub struct ElfSimulation {
+    elves: Elves,
+    round: usize,
+}
+
+impl ElfSimulation {
+    pub fn new(elves: Elves) -> ElfSimulation {
+        ElfSimulation { elves, round: 0 }
+    }
+}
+
+impl Simulation for ElfSimulation {
+    fn step(&mut self) -> bool {
+        if !do_round(&mut self.elves, self.round) {
+            return false;
+        }
+        self.round += 1;
+        true
+    }
+
+    /// The smallest rectangle that contains all elves, elves are drawn as `#`.
+    fn render(&self) -> String {
+        match self.elves.bounds() {
+            Some(bounds) => self.elves.render(bounds, '.', |_| '#'),
+            None => String::new(),
+        }
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "round {}, {} elves, {} empty squares",
+            self.round,
+            self.elves.len(),
+            empty_squares(&self.elves).unwrap_or(0)
+        )
+    }
+
+    fn colour(&self, c: char) -> Option<(u8, u8, u8)> {
+        match c {
+            '#' => Some((80, 200, 90)),
+            _ => Some((90, 90, 90)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn elf_simulation_runs_until_no_elf_moves() {
+        // given
+        let mut simulation = ElfSimulation::new(parse_input(".....\n..##.\n..#..\n.....\n..##.\n"));
+
+        // when
+        while simulation.step() {}
+
+        // then
+        assert_eq!(simulation.round, 3);
+        assert_eq!(
+            simulation.render(),
+            "..#..\n....#\n#....\n....#\n.....\n..#..\n"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{forgotten_snacks, parse_input};
+use aoc_common::GenerateOptions;
+use aoc_grid::{Dir4, Grid};
+
+/// A valley full of blizzards that can be crossed three times. No blizzard moves up or down in the
+/// columns of the entrance and the exit, so no blizzard ever leaves the valley. The size is the
+/// width of the valley, the harder the puzzle, the more blizzards there are.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let width = options.size_or(120).max(1);
+    let height = (width / 5).max(1);
+    let blizzards = options.pick(0.3, 0.6, 0.75);
+    loop {
+        let map = Grid::from_fn(width + 2, height + 2, |p| {
+            let (x, y) = (p.x as usize, p.y as usize);
+            if (x, y) == (1, 0) || (x, y) == (width, height + 1) {
+                '.'
+            } else if x == 0 || y == 0 || x == width + 1 || y == height + 1 {
+                '#'
+            } else if rng.chance(blizzards) {
+                let vertical_allowed = x != 1 && x != width;
+                let dirs: &[Dir4] = if vertical_allowed {
+                    &Dir4::ALL
+                } else {
+                    &[Dir4::Left, Dir4::Right]
+                };
+                rng.choose(dirs).arrow()
+            } else {
+                '.'
+            }
+        });
+        let input = map.render(|c| *c);
+        // too many blizzards can make the valley impassable, try again with others then
+        if parse_input(&input).is_ok_and(|blizz| forgotten_snacks(&blizz).is_some()) {
+            return input;
+        }
+    }
+}
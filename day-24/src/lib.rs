@@ -0,0 +1,375 @@
+//! Day 24: Blizzard Basin
+
+use aoc_common::{Answer, Error, GenerateOptions, Solution};
+use aoc_grid::{Dir4, Grid, Point};
+use aoc_search::{astar, Graph};
+
+mod generate;
+mod visualize;
+
+pub use generate::generate_input;
+pub use visualize::ValleySimulation;
+
+/// Solves both parts of day 24 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 24;
+    type Input<'a> = Blizz;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1(blizz: &Self::Input<'_>) -> Result<Answer, Error> {
+        shortest_path(blizz, 0)
+            .map(Answer::from)
+            .ok_or_else(|| Error::semantic("There is no way to get though this blizzard."))
+    }
+    fn part2(blizz: &Self::Input<'_>) -> Result<Answer, Error> {
+        forgotten_snacks(blizz).map(Answer::from).ok_or_else(|| {
+            Error::semantic("I'm not going back through that blizzard for some snacks.")
+        })
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+/// The initial positions of the blizzards in each direction, excluding the walls.
+pub struct Blizz {
+    pub right: Grid<bool>,
+    pub down: Grid<bool>,
+    pub left: Grid<bool>,
+    pub up: Grid<bool>,
+}
+
+impl Blizz {
+    pub fn width(&self) -> usize {
+        self.right.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.right.height()
+    }
+
+    fn layer(&self, dir: Dir4) -> &Grid<bool> {
+        match dir {
+            Dir4::Up => &self.up,
+            Dir4::Right => &self.right,
+            Dir4::Down => &self.down,
+            Dir4::Left => &self.left,
+        }
+    }
+
+    // whether the blizzard moving in `dir` is at `p` at the given time
+    fn has_blizzard(&self, p: Point, dir: Dir4, time: usize) -> bool {
+        let (width, height) = (self.width() as i64, self.height() as i64);
+        let offset = dir.offset();
+        let time = time as i64;
+        // blizzards wrap around, so look where the blizzard that would be here now started
+        let origin = Point::new(
+            (p.x - offset.x * time).rem_euclid(width),
+            (p.y - offset.y * time).rem_euclid(height),
+        );
+        self.layer(dir)[origin]
+    }
+
+    /// Whether a tile is blocked by a blizzard (or out of bounds) at the given time.
+    pub fn blocked_at_time(&self, p: Point, time: usize) -> bool {
+        !self.right.contains(p) || Dir4::ALL.into_iter().any(|d| self.has_blizzard(p, d, time))
+    }
+
+    /// The directions of the blizzards on a tile at the given time.
+    pub fn blizzards_at_time(&self, p: Point, time: usize) -> Vec<Dir4> {
+        if !self.right.contains(p) {
+            return Vec::new();
+        }
+        Dir4::ALL
+            .into_iter()
+            .filter(|d| self.has_blizzard(p, *d, time))
+            .collect()
+    }
+
+    /// The same valley, rotated by 180°, for going back to the start.
+    pub fn rot_180(&self) -> Self {
+        let rot = |layer: &Grid<bool>| {
+            Grid::from_cells(layer.width(), layer.cells().iter().rev().copied().collect())
+        };
+        Blizz {
+            right: rot(&self.left),
+            left: rot(&self.right),
+            up: rot(&self.down),
+            down: rot(&self.up),
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Blizz, Error> {
+    let map = Grid::parse(input, |c| match c {
+        '#' | '.' | '>' | 'v' | '<' | '^' => Some(c),
+        _ => None,
+    })?;
+    if map.width() < 3 || map.height() < 3 {
+        return Err(Error::semantic(
+            "the valley needs walls around at least one tile",
+        ));
+    }
+    // the walls are always the same, so we only keep what is inside of them
+    let valley = Grid::from_fn(map.width() - 2, map.height() - 2, |p| {
+        map[p + Point::new(1, 1)]
+    });
+    let layer = |dir: Dir4| valley.map(|c| Dir4::from_arrow(*c) == Some(dir));
+
+    Ok(Blizz {
+        right: layer(Dir4::Right),
+        down: layer(Dir4::Down),
+        left: layer(Dir4::Left),
+        up: layer(Dir4::Up),
+    })
+}
+
+/// Walking through the valley from the entrance to the exit, a minute at a time.
+#[derive(Clone)]
+struct Expedition<'a> {
+    blizz: &'a Blizz,
+    // the entrance is just above the top left tile, the exit just below the bottom right tile
+    start: Point,
+    exit: Point,
+    // the blizzards repeat after this many minutes. In case this is too large, we can possibly
+    // make this value smaller by using the lcm of width and height
+    cycle: usize,
+}
+
+impl Expedition<'_> {
+    fn new(blizz: &Blizz) -> Expedition<'_> {
+        let (width, height) = (blizz.width() as i64, blizz.height() as i64);
+        Expedition {
+            blizz,
+            start: Point::new(0, -1),
+            exit: Point::new(width - 1, height),
+            cycle: blizz.width() * blizz.height(),
+        }
+    }
+
+    // entrance and exit are outside of the valley and therefore never blocked
+    fn free(&self, p: Point, time: usize) -> bool {
+        p == self.start || p == self.exit || !self.blizz.blocked_at_time(p, time)
+    }
+}
+
+impl Graph for Expedition<'_> {
+    // position and absolute time
+    type Node = (Point, usize);
+    type Key = (Point, usize);
+
+    fn key(&self, &(p, time): &(Point, usize)) -> (Point, usize) {
+        (p, time % self.cycle)
+    }
+    fn successors(
+        &self,
+        &(p, time): &(Point, usize),
+    ) -> impl Iterator<Item = ((Point, usize), u64)> {
+        // wait or move
+        std::iter::once(p)
+            .chain(p.neighbours4())
+            .filter(move |next| self.free(*next, time + 1))
+            .map(move |next| ((next, time + 1), 1))
+    }
+    fn is_goal(&self, (p, _): &(Point, usize)) -> bool {
+        *p == self.exit
+    }
+    fn heuristic(&self, (p, _): &(Point, usize)) -> u64 {
+        p.manhattan(self.exit)
+    }
+}
+
+/// Minutes until the goal is reached, starting at `start_time`. The returned time is absolute.
+pub fn shortest_path(blizz: &Blizz, start_time: usize) -> Option<usize> {
+    let expedition = Expedition::new(blizz);
+    astar(&expedition, [(expedition.start, start_time)]).map(|found| found.node.1)
+}
+
+/// The positions of the expedition from the entrance to the exit, one for every minute starting
+/// at `start_time`. Waiting shows up as the same position twice in a row.
+pub fn shortest_route(blizz: &Blizz, start_time: usize) -> Option<Vec<(Point, usize)>> {
+    let expedition = Expedition::new(blizz);
+    astar(&expedition, [(expedition.start, start_time)]).map(|found| found.path)
+}
+
+/// Time to go to the goal, back to the start and to the goal again.
+pub fn forgotten_snacks(blizz: &Blizz) -> Option<usize> {
+    let first_trip_time = shortest_path(blizz, 0)?;
+    // Seriously? Do you _really_ need these snacks?
+    // Ok, I will turn this blizzard around!
+    let inv_blizz = blizz.rot_180();
+    let back_trip_time = shortest_path(&inv_blizz, first_trip_time)?;
+    shortest_path(blizz, back_trip_time)
+}
+
+/// The route of all three trips of [`forgotten_snacks`], as positions in the original valley.
+pub fn forgotten_snacks_route(blizz: &Blizz) -> Option<Vec<(Point, usize)>> {
+    let mut route = shortest_route(blizz, 0)?;
+    let inv_blizz = blizz.rot_180();
+    let corner = Point::new(blizz.width() as i64 - 1, blizz.height() as i64 - 1);
+    let back_trip = shortest_route(&inv_blizz, route.last()?.1)?;
+    // every trip starts where the one before ended
+    route.extend(
+        back_trip
+            .into_iter()
+            .skip(1)
+            .map(|(p, time)| (corner - p, time)),
+    );
+    route.extend(shortest_route(blizz, route.last()?.1)?.into_iter().skip(1));
+    Some(route)
+}
+
+/// Draw the valley with its walls at the given time, with the expedition as `E`. Tiles with a
+/// single blizzard show its direction, tiles with several the number of blizzards.
+pub fn render_valley(blizz: &Blizz, time: usize, expedition: Point) -> String {
+    draw_valley(blizz, time, |p| p == expedition)
+}
+
+// the valley with `E` on every tile the expedition might be on
+fn draw_valley(blizz: &Blizz, time: usize, is_expedition: impl Fn(Point) -> bool) -> String {
+    let (width, height) = (blizz.width() as i64, blizz.height() as i64);
+    let walls = Grid::from_fn(blizz.width() + 2, blizz.height() + 2, |p| {
+        let p = p - Point::new(1, 1);
+        if is_expedition(p) {
+            'E'
+        } else if p == Point::new(0, -1) || p == Point::new(width - 1, height) {
+            '.'
+        } else if !blizz.right.contains(p) {
+            '#'
+        } else {
+            match blizz.blizzards_at_time(p, time)[..] {
+                [] => '.',
+                [dir] => dir.arrow(),
+                ref dirs => char::from_digit(dirs.len() as u32, 10).unwrap_or('*'),
+            }
+        }
+    });
+    walls.render(|c| *c)
+}
+
+/// Draw every minute of a route, like the puzzle description does.
+pub fn render_route(blizz: &Blizz, route: &[(Point, usize)]) -> String {
+    let mut s = String::new();
+    for (i, (p, time)) in route.iter().enumerate() {
+        let action = match i.checked_sub(1).map(|prev| route[prev].0) {
+            None => "start".to_owned(),
+            Some(prev) if prev == *p => "wait".to_owned(),
+            Some(prev) => match Dir4::ALL.into_iter().find(|d| prev.step(*d) == *p) {
+                Some(dir) => format!("move {}", dir_name(dir)),
+                None => "jump".to_owned(),
+            },
+        };
+        s += &format!("Minute {time}, {action}:\n");
+        s += &render_valley(blizz, *time, *p);
+        s.push('\n');
+    }
+    s
+}
+
+fn dir_name(dir: Dir4) -> &'static str {
+    match dir {
+        Dir4::Up => "up",
+        Dir4::Right => "right",
+        Dir4::Down => "down",
+        Dir4::Left => "left",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::fuzz;
+
+    const EXAMPLE: &str = r#"#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+"#;
+
+    #[test]
+    fn shortest_path_works_for_example() {
+        // given
+        let blizz = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let shortest_time = shortest_path(&blizz, 0);
+
+        // then
+        assert_eq!(shortest_time, Some(18));
+    }
+
+    #[test]
+    fn forgotten_snacks_works_for_example() {
+        // given
+        let blizz = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let shortest_time = forgotten_snacks(&blizz);
+
+        // then
+        assert_eq!(shortest_time, Some(54));
+    }
+
+    #[test]
+    fn parse_input_works_for_minimal_example() {
+        // given
+        let input = r#"#.##
+#v^#
+#<>#
+##.#
+"#;
+        // when
+        let result = parse_input(input);
+
+        // then
+        let blizz = result.expect("expected successful parsing");
+        assert_eq!(blizz.width(), 2);
+        assert_eq!(blizz.left.cells(), &[false, false, true, false]);
+        assert_eq!(blizz.right.cells(), &[false, false, false, true]);
+        assert_eq!(blizz.up.cells(), &[false, true, false, false]);
+        assert_eq!(blizz.down.cells(), &[true, false, false, false]);
+    }
+
+    #[test]
+    fn forgotten_snacks_route_matches_time() {
+        // given
+        let blizz = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let route = forgotten_snacks_route(&blizz).expect("expected a route");
+
+        // then
+        assert_eq!(route.len(), 55);
+        assert_eq!(route[0], (Point::new(0, -1), 0));
+        assert_eq!(route[54], (Point::new(5, 4), 54));
+        // one minute per step, moving at most one tile and never into a blizzard
+        for (i, step) in route.windows(2).enumerate() {
+            assert_eq!(step[1].1, i + 1);
+            assert!(step[0].0.manhattan(step[1].0) <= 1);
+            assert!(blizz.blizzards_at_time(step[1].0, step[1].1).is_empty());
+        }
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{draw_valley, Blizz, Expedition};
+use aoc_grid::Point;
+use aoc_tui::Simulation;
+use std::collections::HashSet;
+
+// to the exit, back to the entrance for the snacks and to the exit again
+const TRIPS: usize = 3;
+
+/// The expedition spreading through the valley, a minute at a time: every tile it might be on is
+/// drawn as `E`. Once the exit is reached, the expedition goes back for the snacks and returns.
+#[derive(Clone)]
+pub struct ValleySimulation<'a> {
+    expedition: Expedition<'a>,
+    time: usize,
+    // the tiles the expedition can be on at `time`
+    frontier: HashSet<Point>,
+    trips: usize,
+}
+
+impl ValleySimulation<'_> {
+    pub fn new(blizz: &Blizz) -> ValleySimulation<'_> {
+        let expedition = Expedition::new(blizz);
+        ValleySimulation {
+            frontier: HashSet::from([expedition.start]),
+            expedition,
+            time: 0,
+            trips: 0,
+        }
+    }
+
+    fn target(&self) -> Point {
+        if self.trips.is_multiple_of(2) {
+            self.expedition.exit
+        } else {
+            self.expedition.start
+        }
+    }
+}
+
+impl Simulation for ValleySimulation<'_> {
+    fn step(&mut self) -> bool {
+        if self.trips == TRIPS {
+            return false;
+        }
+        let next: HashSet<Point> = self
+            .frontier
+            .iter()
+            .flat_map(|p| std::iter::once(*p).chain(p.neighbours4()))
+            .filter(|p| self.expedition.free(*p, self.time + 1))
+            .collect();
+        if next.is_empty() {
+            return false;
+        }
+        self.time += 1;
+        let target = self.target();
+        if next.contains(&target) {
+            self.trips += 1;
+            self.frontier = HashSet::from([target]);
+        } else {
+            self.frontier = next;
+        }
+        true
+    }
+
+    fn render(&self) -> String {
+        draw_valley(self.expedition.blizz, self.time, |p| {
+            self.frontier.contains(&p)
+        })
+    }
+
+    fn status(&self) -> String {
+        if self.trips == TRIPS {
+            return format!("minute {}, back with the snacks", self.time);
+        }
+        let target = if self.trips.is_multiple_of(2) {
+            "exit"
+        } else {
+            "entrance"
+        };
+        format!(
+            "minute {}, trip {} of {TRIPS} to the {target}, {} possible positions",
+            self.time,
+            self.trips + 1,
+            self.frontier.len()
+        )
+    }
+
+    fn colour(&self, c: char) -> Option<(u8, u8, u8)> {
+        match c {
+            'E' => Some((90, 220, 90)),
+            '#' => Some((120, 120, 140)),
+            '.' => Some((70, 70, 70)),
+            _ => Some((150, 200, 255)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn valley_simulation_takes_as_long_as_forgotten_snacks() {
+        // given
+        let blizz = parse_input("#.######\n#>>.<^<#\n#.<..<<#\n#>v.><>#\n#<^v^^>#\n######.#\n")
+            .expect("expected successful parsing");
+        let mut simulation = ValleySimulation::new(&blizz);
+
+        // when
+        while simulation.step() {}
+
+        // then
+        assert_eq!(simulation.time, 54);
+        assert_eq!(simulation.trips, TRIPS);
+        assert!(simulation.render().ends_with("\n######E#\n"));
+    }
+}
@@ -0,0 +1,12 @@
+use crate::fmt_snafu;
+use aoc_common::GenerateOptions;
+
+/// Fuel requirements as SNAFU numbers. The size is the number of requirements, the harder the
+/// puzzle, the longer the numbers.
+pub fn generate_input(options: &GenerateOptions) -> String {
+    let mut rng = options.rng();
+    let max = 5i64.pow(options.pick(5, 12, 20));
+    (0..options.size_or(120))
+        .map(|_| fmt_snafu(rng.between(1, max)) + "\n")
+        .collect()
+}
@@ -0,0 +1,178 @@
+//! Day 25: Full of Hot Air
+
+use aoc_common::{parse_lines, Answer, Error, ErrorKind, GenerateOptions, Solution};
+
+mod generate;
+
+pub use generate::generate_input;
+
+/// Solves both parts of day 25 through the common [`Solution`] interface.
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 25;
+    type Input<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_input(input)
+    }
+    fn part1(numbers: &Self::Input<'_>) -> Result<Answer, Error> {
+        Ok(fmt_snafu(numbers.iter().sum()).into())
+    }
+    fn part2(_: &Self::Input<'_>) -> Result<Answer, Error> {
+        // there is no second puzzle on the last day
+        Ok(Answer::Empty)
+    }
+    fn generate(options: &GenerateOptions) -> String {
+        generate_input(options)
+    }
+}
+
+pub fn parse_snafu(input: &str) -> Result<i64, Error> {
+    let mut number: i64 = 0;
+    for (i, c) in input.char_indices() {
+        let digit = if c == '-' {
+            -1
+        } else if c == '=' {
+            -2
+        } else if let Some(d) = c.to_digit(3) {
+            d as i64
+        } else {
+            return Err(Error::new(
+                ErrorKind::Number,
+                format!("unable to parse snafu number: '{c}' is not a valid digit."),
+            )
+            .with_span(input, &input[i..i + c.len_utf8()]));
+        };
+        number = number
+            .checked_mul(5)
+            .and_then(|n| n.checked_add(digit))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Number, "snafu number is too large").with_span(input, input)
+            })?;
+    }
+    Ok(number)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<i64>, Error> {
+    parse_lines(input, parse_snafu)
+}
+
+pub fn fmt_snafu(num: i64) -> String {
+    // let's take an easy but not very efficient approach
+    let mut digits: Vec<char> = Vec::with_capacity(28);
+    let sgn = num.signum();
+    let mut num = num.unsigned_abs();
+    while num != 0 {
+        let d = num % 5;
+        num /= 5;
+        if let Some(c) = char::from_digit(d as u32, 3) {
+            digits.push(c);
+        } else if d == 3 {
+            digits.push('=');
+            num += 1;
+        } else if d == 4 {
+            digits.push('-');
+            num += 1;
+        }
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    // the digits are balanced, so negating a number negates each of its digits
+    if sgn < 0 {
+        for digit in &mut digits {
+            *digit = match digit {
+                '1' => '-',
+                '2' => '=',
+                '-' => '1',
+                '=' => '2',
+                _ => '0',
+            };
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::{fuzz, Rng};
+
+    const EXAMPLE: &str = r#"1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122
+"#;
+
+    #[test]
+    fn parse_input_works_for_example() {
+        // when
+        let result = parse_input(EXAMPLE);
+
+        // then
+        let numbers = result.expect("expected successful parsing");
+        assert_eq!(
+            &numbers,
+            &[1747, 906, 198, 11, 201, 31, 1257, 32, 353, 107, 7, 3, 37]
+        );
+    }
+
+    #[test]
+    fn fmt_snafu_works_for_example() {
+        // given
+        let numbers = parse_input(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let formatted: Vec<String> = numbers.iter().map(|n| fmt_snafu(*n)).collect();
+
+        // then
+        for (actual, wanted) in formatted.iter().zip(EXAMPLE.lines()) {
+            assert_eq!(actual, wanted);
+        }
+    }
+
+    #[test]
+    fn parsers_never_panic() {
+        // given
+        let generated = generate_input(&GenerateOptions {
+            size: Some(5),
+            ..Default::default()
+        });
+
+        // when
+        let result = fuzz(1, 2000, &[EXAMPLE, &generated], |input| {
+            let _ = Solver::parse(input);
+            let _ = parse_input(input);
+            let _ = parse_snafu(input);
+        });
+
+        // then
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn snafu_numbers_survive_formatting_and_parsing() {
+        // given
+        let mut rng = Rng::new(25);
+
+        for _ in 0..10_000 {
+            let number = rng.between(i64::MIN / 2, i64::MAX / 2);
+
+            // when
+            let parsed = parse_snafu(&fmt_snafu(number));
+
+            // then
+            assert_eq!(parsed, Ok(number));
+        }
+    }
+}